@@ -4,18 +4,21 @@
 //! visualization, exposed to Node.js via NAPI-RS bindings.
 //!
 //! Key capabilities:
-//! - Time-series accumulation with ring buffer (720 points = 1hr at 5s intervals)
+//! - Time-series accumulation with ring buffer (default 720 points = 1hr at 5s intervals)
 //! - Compound growth rate calculation (linear regression on recent window)
 //! - Knowledge velocity tracking (messages/min trend)
+//! - Exponential moving average smoothing for any metric
 //! - Agent lineage tree construction from flat worker list
+//! - JSON persistence of the accumulated series across restarts
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::collections::VecDeque;
 
-const MAX_POINTS: usize = 720; // 1 hour at 5-second intervals
+const DEFAULT_MAX_POINTS: usize = 720; // 1 hour at 5-second intervals
+const DEFAULT_INTERVAL_SECONDS: f64 = 5.0;
 
 /// A single point in the compounding time series
 #[napi(object)]
@@ -32,6 +35,20 @@ pub struct TimeSeriesPoint {
     pub pheromone_trails: u32,
 }
 
+/// Summary of a metric over a recent window
+#[napi(object)]
+#[derive(Clone, Debug, Serialize)]
+pub struct WindowStats {
+    pub min: f64,
+    pub max: f64,
+    pub first: f64,
+    pub last: f64,
+    /// `last - first`
+    pub delta: f64,
+    /// Number of points actually in the window
+    pub count: u32,
+}
+
 /// Worker info extracted from a snapshot for lineage building
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -48,6 +65,9 @@ struct WorkerInfo {
     depth_level: Option<u32>,
     #[serde(default)]
     team_name: Option<String>,
+    /// Handle of the worker that spawned this one, when known
+    #[serde(default)]
+    parent_handle: Option<String>,
 }
 
 /// Swarm info extracted from a snapshot
@@ -97,6 +117,16 @@ struct LineageNode {
     children: Vec<LineageNode>,
 }
 
+/// Serialized accumulator state for `to_json` / `from_json`
+#[derive(Debug, Serialize, Deserialize)]
+struct AccumulatorState {
+    points: Vec<TimeSeriesPoint>,
+    #[serde(default)]
+    max_points: Option<u32>,
+    #[serde(default)]
+    interval_seconds: Option<f64>,
+}
+
 /// Stateful time-series accumulator for compound metrics.
 ///
 /// Maintains a ring buffer of metric snapshots and computes
@@ -104,18 +134,72 @@ struct LineageNode {
 #[napi]
 pub struct CompoundAccumulator {
     points: VecDeque<TimeSeriesPoint>,
+    /// Ring buffer capacity
+    max_points: usize,
+    /// Seconds between snapshots, used to convert per-point rates to per-minute
+    interval_seconds: f64,
+}
+
+impl Default for CompoundAccumulator {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
 }
 
 #[napi]
 impl CompoundAccumulator {
-    /// Create a new empty accumulator
+    /// Create a new empty accumulator holding up to `max_points` snapshots
+    /// (default 720) taken every `interval_seconds` (default 5).
+    /// Zero or negative values fall back to the defaults.
     #[napi(constructor)]
-    pub fn new() -> Self {
+    pub fn new(max_points: Option<u32>, interval_seconds: Option<f64>) -> Self {
+        let max_points = max_points
+            .filter(|&n| n > 0)
+            .map_or(DEFAULT_MAX_POINTS, |n| n as usize);
+        let interval_seconds = interval_seconds
+            .filter(|&s| s > 0.0)
+            .unwrap_or(DEFAULT_INTERVAL_SECONDS);
         Self {
-            points: VecDeque::with_capacity(MAX_POINTS),
+            points: VecDeque::with_capacity(max_points),
+            max_points,
+            interval_seconds,
         }
     }
 
+    /// Restore an accumulator saved with `to_json`. If the saved series is
+    /// longer than the capacity, only the newest points are kept.
+    #[napi(factory)]
+    pub fn from_json(json: String) -> Result<Self> {
+        let state: AccumulatorState = serde_json::from_str(&json).map_err(|e| {
+            Error::new(
+                Status::InvalidArg,
+                format!("Failed to parse accumulator JSON: {}", e),
+            )
+        })?;
+
+        let mut acc = Self::new(state.max_points, state.interval_seconds);
+        let skip = state.points.len().saturating_sub(acc.max_points);
+        acc.points.extend(state.points.into_iter().skip(skip));
+        Ok(acc)
+    }
+
+    /// Serialize the series and configuration so it can be persisted and
+    /// reloaded with `from_json`.
+    #[napi]
+    pub fn to_json(&self) -> Result<String> {
+        let state = AccumulatorState {
+            points: self.points.iter().cloned().collect(),
+            max_points: Some(self.max_points as u32),
+            interval_seconds: Some(self.interval_seconds),
+        };
+        serde_json::to_string(&state).map_err(|e| {
+            Error::new(
+                Status::GenericFailure,
+                format!("Failed to serialize accumulator: {}", e),
+            )
+        })
+    }
+
     /// Push a JSON snapshot from the fleet server into the accumulator.
     /// Extracts metrics and appends to the ring buffer.
     #[napi]
@@ -154,7 +238,7 @@ impl CompoundAccumulator {
         };
 
         // Ring buffer: remove oldest if at capacity
-        if self.points.len() >= MAX_POINTS {
+        if self.points.len() >= self.max_points {
             self.points.pop_front();
         }
         self.points.push_back(point);
@@ -193,8 +277,161 @@ impl CompoundAccumulator {
         self.compute_rate(|p| p.credits_earned as f64, 60)
     }
 
+    /// Rate of change per minute for any metric over the last `window_points`
+    /// points, e.g. "active_workers" or "blackboard_messages".
+    /// Returns 0.0 for an unknown field name.
+    #[napi]
+    pub fn get_rate(&self, field: String, window_points: u32) -> f64 {
+        match field_extractor(&field) {
+            Some(extract) => self.compute_rate(extract, window_points as usize),
+            None => 0.0,
+        }
+    }
+
+    /// Project the next `steps_ahead` values of a metric by extending the
+    /// regression line `compute_rate` fits over the last `window_points`.
+    /// This is a simple linear extrapolation, not a forecasting model. All
+    /// metrics are counts, so projections are clamped at zero. Returns an
+    /// empty vec for an unknown field or fewer than two points.
+    #[napi]
+    pub fn forecast(&self, field: String, window_points: u32, steps_ahead: u32) -> Vec<f64> {
+        let Some(fit) = field_extractor(&field)
+            .and_then(|extract| self.regression(extract, window_points as usize))
+        else {
+            return Vec::new();
+        };
+
+        let last_x = (fit.len - 1) as f64;
+        (1..=steps_ahead)
+            .map(|step| (fit.intercept + fit.slope * (last_x + step as f64)).max(0.0))
+            .collect()
+    }
+
+    /// Whether a metric has flatlined: true when its change across the last
+    /// `window_points` points is below `epsilon`. Returns false when fewer
+    /// than `window_points` (or two) points exist, or the field is unknown.
+    #[napi]
+    pub fn is_stalled(&self, field: String, window_points: u32, epsilon: f64) -> bool {
+        let window_points = window_points as usize;
+        if window_points < 2 || self.points.len() < window_points {
+            return false;
+        }
+        let Some(extract) = field_extractor(&field) else {
+            return false;
+        };
+
+        let values = self.window_values(extract, window_points);
+        match (values.first(), values.last()) {
+            (Some(first), Some(last)) => (last - first).abs() < epsilon,
+            _ => false,
+        }
+    }
+
+    /// Min, max, first, last and delta of a metric over the last
+    /// `window_points` points. A window larger than the series uses every
+    /// point available; with no points all values are zero.
+    #[napi]
+    pub fn window_stats(&self, field: String, window_points: u32) -> Result<WindowStats> {
+        let extract = field_extractor(&field).ok_or_else(|| {
+            Error::new(Status::InvalidArg, format!("Unknown metric field: {}", field))
+        })?;
+
+        let values = self.window_values(extract, window_points as usize);
+        let (Some(&first), Some(&last)) = (values.first(), values.last()) else {
+            return Ok(WindowStats { min: 0.0, max: 0.0, first: 0.0, last: 0.0, delta: 0.0, count: 0 });
+        };
+
+        Ok(WindowStats {
+            min: values.iter().copied().fold(f64::MAX, f64::min),
+            max: values.iter().copied().fold(f64::MIN, f64::max),
+            first,
+            last,
+            delta: last - first,
+            count: values.len() as u32,
+        })
+    }
+
+    /// Minutes until a metric doubles from its latest value at the current
+    /// regression slope over `window_points`: `current / rate_per_minute`.
+    /// None when the slope is <= 0, the latest value is zero, or the field is unknown.
+    #[napi]
+    pub fn doubling_time_minutes(&self, field: String, window_points: u32) -> Option<f64> {
+        let extract = field_extractor(&field)?;
+        let current = self.points.back().map(extract)?;
+        let rate = self.compute_rate(extract, window_points as usize);
+        if rate <= 0.0 || current <= 0.0 {
+            return None;
+        }
+        Some(current / rate)
+    }
+
+    /// Pearson correlation between two metrics over the last `window_points`
+    /// points, clamped to [-1, 1]. Returns 0.0 with fewer than two points,
+    /// zero variance in either metric, or an unknown field.
+    #[napi]
+    pub fn correlation(&self, field_a: String, field_b: String, window_points: u32) -> f64 {
+        let (Some(extract_a), Some(extract_b)) = (field_extractor(&field_a), field_extractor(&field_b)) else {
+            return 0.0;
+        };
+        let a = self.window_values(extract_a, window_points as usize);
+        let b = self.window_values(extract_b, window_points as usize);
+        if a.len() < 2 {
+            return 0.0;
+        }
+
+        let n = a.len() as f64;
+        let mean_a = a.iter().sum::<f64>() / n;
+        let mean_b = b.iter().sum::<f64>() / n;
+
+        let mut cov = 0.0;
+        let mut var_a = 0.0;
+        let mut var_b = 0.0;
+        for (x, y) in a.iter().zip(&b) {
+            let dx = x - mean_a;
+            let dy = y - mean_b;
+            cov += dx * dy;
+            var_a += dx * dx;
+            var_b += dy * dy;
+        }
+
+        if var_a < f64::EPSILON || var_b < f64::EPSILON {
+            return 0.0;
+        }
+        (cov / (var_a.sqrt() * var_b.sqrt())).clamp(-1.0, 1.0)
+    }
+
+    /// Exponentially-weighted moving average of a metric over the whole series:
+    /// `ema[0] = x[0]`, `ema[i] = alpha * x[i] + (1 - alpha) * ema[i-1]`.
+    /// `field` is a `TimeSeriesPoint` field name such as "tasks_completed";
+    /// `alpha` must be in (0, 1]. Returns an empty vec when there are no points.
+    #[napi]
+    pub fn get_ema_series(&self, field: String, alpha: f64) -> Result<Vec<f64>> {
+        if !(alpha > 0.0 && alpha <= 1.0) {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("alpha must be in (0, 1], got {}", alpha),
+            ));
+        }
+        let extract = field_extractor(&field).ok_or_else(|| {
+            Error::new(Status::InvalidArg, format!("Unknown metric field: {}", field))
+        })?;
+
+        let mut ema: Vec<f64> = Vec::with_capacity(self.points.len());
+        for point in &self.points {
+            let value = extract(point);
+            let smoothed = match ema.last() {
+                Some(prev) => alpha * value + (1.0 - alpha) * prev,
+                None => value,
+            };
+            ema.push(smoothed);
+        }
+        Ok(ema)
+    }
+
     /// Build a lineage tree JSON from a flat worker list.
-    /// Groups workers by swarm, then by depth level within each swarm.
+    /// Groups workers by swarm, then nests each worker under its
+    /// `parentHandle` within the swarm. Swarms where no worker has a parent
+    /// link fall back to nesting by depth level.
     #[napi]
     pub fn build_lineage_tree(&self, workers_json: String) -> Result<String> {
         let workers: Vec<WorkerInfo> = serde_json::from_str(&workers_json).map_err(|e| {
@@ -241,19 +478,17 @@ impl CompoundAccumulator {
                 children: Vec::new(),
             };
 
+            if members.iter().any(|w| w.parent_handle.is_some()) {
+                swarm_node.children = build_parent_tree(&members);
+                root.children.push(swarm_node);
+                continue;
+            }
+
             // Build depth-based hierarchy
             let mut depth_buckets: HashMap<u32, Vec<LineageNode>> = HashMap::new();
             for w in &members {
                 let depth = w.depth_level.unwrap_or(0);
-                let node = LineageNode {
-                    id: w.handle.clone(),
-                    name: w.handle.clone(),
-                    node_type: "worker".to_string(),
-                    state: Some(w.state.clone()),
-                    health: Some(w.health.clone()),
-                    children: Vec::new(),
-                };
-                depth_buckets.entry(depth).or_default().push(node);
+                depth_buckets.entry(depth).or_default().push(worker_node(w));
             }
 
             // Nest: depth 0 at top, deeper nodes become children
@@ -297,14 +532,7 @@ impl CompoundAccumulator {
             };
 
             for w in unassigned {
-                unassigned_node.children.push(LineageNode {
-                    id: w.handle.clone(),
-                    name: w.handle.clone(),
-                    node_type: "worker".to_string(),
-                    state: Some(w.state.clone()),
-                    health: Some(w.health.clone()),
-                    children: Vec::new(),
-                });
+                unassigned_node.children.push(worker_node(w));
             }
 
             root.children.push(unassigned_node);
@@ -325,17 +553,35 @@ impl CompoundAccumulator {
     where
         F: Fn(&TimeSeriesPoint) -> f64,
     {
-        let n = self.points.len();
-        if n < 2 {
-            return 0.0;
+        match self.regression(extract, window_size) {
+            // Convert from per-point to per-minute
+            Some(fit) => fit.slope * (60.0 / self.interval_seconds),
+            None => 0.0,
         }
+    }
 
-        let start = if n > window_size { n - window_size } else { 0 };
+    /// Metric values for the last `window_size` points, oldest first
+    fn window_values<F>(&self, extract: F, window_size: usize) -> Vec<f64>
+    where
+        F: Fn(&TimeSeriesPoint) -> f64,
+    {
+        let start = self.points.len().saturating_sub(window_size);
+        self.points.iter().skip(start).map(extract).collect()
+    }
+
+    /// Least-squares line through the last `window_size` points, with x the
+    /// index within the window. None when fewer than two points are available.
+    fn regression<F>(&self, extract: F, window_size: usize) -> Option<LinearFit>
+    where
+        F: Fn(&TimeSeriesPoint) -> f64,
+    {
+        let n = self.points.len();
+        let start = n.saturating_sub(window_size);
         let window: Vec<&TimeSeriesPoint> = self.points.iter().skip(start).collect();
         let wn = window.len() as f64;
 
         if wn < 2.0 {
-            return 0.0;
+            return None;
         }
 
         // Simple linear regression: slope = (n*sum_xy - sum_x*sum_y) / (n*sum_xx - sum_x^2)
@@ -355,22 +601,107 @@ impl CompoundAccumulator {
 
         let denom = wn * sum_xx - sum_x * sum_x;
         if denom.abs() < f64::EPSILON {
-            return 0.0;
+            return None;
         }
 
-        let slope_per_point = (wn * sum_xy - sum_x * sum_y) / denom;
-        // Convert from per-point (5s) to per-minute (12 points/min)
-        slope_per_point * 12.0
+        let slope = (wn * sum_xy - sum_x * sum_y) / denom;
+        Some(LinearFit {
+            slope,
+            intercept: (sum_y - slope * sum_x) / wn,
+            len: window.len(),
+        })
+    }
+}
+
+/// Lineage node for a single worker, without children
+fn worker_node(w: &WorkerInfo) -> LineageNode {
+    LineageNode {
+        id: w.handle.clone(),
+        name: w.handle.clone(),
+        node_type: "worker".to_string(),
+        state: Some(w.state.clone()),
+        health: Some(w.health.clone()),
+        children: Vec::new(),
     }
 }
 
+/// Nest workers under their parent handles. Workers whose parent is missing
+/// (or not in `members`) become roots; members only reachable through a
+/// parent cycle are also promoted to roots so none are dropped.
+fn build_parent_tree(members: &[&WorkerInfo]) -> Vec<LineageNode> {
+    let handles: HashSet<&str> = members.iter().map(|w| w.handle.as_str()).collect();
+    let mut children_of: HashMap<&str, Vec<&WorkerInfo>> = HashMap::new();
+    let mut roots: Vec<&WorkerInfo> = Vec::new();
+
+    for &w in members {
+        match w.parent_handle.as_deref() {
+            Some(parent) if parent != w.handle && handles.contains(parent) => {
+                children_of.entry(parent).or_default().push(w);
+            }
+            _ => roots.push(w),
+        }
+    }
+
+    fn attach<'a>(
+        w: &'a WorkerInfo,
+        children_of: &HashMap<&str, Vec<&'a WorkerInfo>>,
+        visited: &mut HashSet<&'a str>,
+    ) -> LineageNode {
+        visited.insert(&w.handle);
+        let mut node = worker_node(w);
+        for &child in children_of.get(w.handle.as_str()).into_iter().flatten() {
+            if !visited.contains(child.handle.as_str()) {
+                node.children.push(attach(child, children_of, visited));
+            }
+        }
+        node
+    }
+
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut tree: Vec<LineageNode> = roots
+        .into_iter()
+        .map(|w| attach(w, &children_of, &mut visited))
+        .collect();
+    for &w in members {
+        if !visited.contains(w.handle.as_str()) {
+            tree.push(attach(w, &children_of, &mut visited));
+        }
+    }
+    tree
+}
+
+/// Regression line over a window: value ≈ intercept + slope * index
+struct LinearFit {
+    /// Change per point
+    slope: f64,
+    intercept: f64,
+    /// Number of points in the window
+    len: usize,
+}
+
+/// Map a `TimeSeriesPoint` field name to its value extractor
+fn field_extractor(field: &str) -> Option<fn(&TimeSeriesPoint) -> f64> {
+    let extract: fn(&TimeSeriesPoint) -> f64 = match field {
+        "tasks_completed" => |p| p.tasks_completed as f64,
+        "knowledge_entries" => |p| p.knowledge_entries as f64,
+        "credits_earned" => |p| p.credits_earned as f64,
+        "active_workers" => |p| p.active_workers as f64,
+        "healthy_workers" => |p| p.healthy_workers as f64,
+        "total_swarms" => |p| p.total_swarms as f64,
+        "blackboard_messages" => |p| p.blackboard_messages as f64,
+        "pheromone_trails" => |p| p.pheromone_trails as f64,
+        _ => return None,
+    };
+    Some(extract)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_accumulator_basic() {
-        let mut acc = CompoundAccumulator::new();
+        let mut acc = CompoundAccumulator::default();
         assert_eq!(acc.get_point_count(), 0);
 
         let snapshot = r#"{
@@ -396,7 +727,7 @@ mod tests {
 
     #[test]
     fn test_lineage_tree() {
-        let acc = CompoundAccumulator::new();
+        let acc = CompoundAccumulator::default();
         let workers = r#"[
             {"handle": "lead-1", "state": "working", "health": "healthy", "swarmId": "s1", "depthLevel": 0},
             {"handle": "worker-1", "state": "working", "health": "healthy", "swarmId": "s1", "depthLevel": 1},
@@ -413,19 +744,38 @@ mod tests {
 
     #[test]
     fn test_ring_buffer_cap() {
-        let mut acc = CompoundAccumulator::new();
+        let mut acc = CompoundAccumulator::default();
         let snapshot = r#"{"workers":[],"swarms":[],"tasksTotal":0,"tasksCompleted":0,"knowledgeEntries":0,"creditsTotal":0,"blackboardMessages":0,"pheromoneTrails":0}"#;
 
         for _ in 0..800 {
             acc.push_snapshot(snapshot.to_string()).unwrap();
         }
 
-        assert_eq!(acc.get_point_count(), 720); // MAX_POINTS
+        assert_eq!(acc.get_point_count(), 720); // DEFAULT_MAX_POINTS
+
+        let mut acc = CompoundAccumulator::new(Some(10), None);
+        for _ in 0..25 {
+            acc.push_snapshot(snapshot.to_string()).unwrap();
+        }
+        assert_eq!(acc.get_point_count(), 10);
+    }
+
+    #[test]
+    fn test_rate_uses_interval() {
+        // One task per point: 12/min at 5s intervals, 6/min at 10s intervals
+        let mut five = CompoundAccumulator::default();
+        let mut ten = CompoundAccumulator::new(None, Some(10.0));
+        for i in 0..10 {
+            push_point(&mut five, i, 0);
+            push_point(&mut ten, i, 0);
+        }
+        assert!((five.get_compound_rate() - 12.0).abs() < 1e-9);
+        assert!((ten.get_compound_rate() - 6.0).abs() < 1e-9);
     }
 
     #[test]
     fn test_compound_rate() {
-        let mut acc = CompoundAccumulator::new();
+        let mut acc = CompoundAccumulator::default();
 
         // Push increasing task counts
         for i in 0..20 {
@@ -440,4 +790,186 @@ mod tests {
         // Should be positive since tasks_completed is increasing
         assert!(rate > 0.0, "Rate should be positive, got {}", rate);
     }
+
+    fn push_point(acc: &mut CompoundAccumulator, tasks_completed: u32, active_workers: u32) {
+        acc.points.push_back(TimeSeriesPoint {
+            timestamp: acc.points.len() as i64 * 5000,
+            tasks_completed,
+            knowledge_entries: 0,
+            credits_earned: 0,
+            active_workers,
+            healthy_workers: 0,
+            total_swarms: 0,
+            blackboard_messages: 0,
+            pheromone_trails: 0,
+        });
+    }
+
+    #[test]
+    fn test_ema_series() {
+        let mut acc = CompoundAccumulator::default();
+        assert!(acc.get_ema_series("tasks_completed".into(), 0.5).unwrap().is_empty());
+
+        for tasks in [10, 20, 20] {
+            push_point(&mut acc, tasks, 0);
+        }
+        let ema = acc.get_ema_series("tasks_completed".into(), 0.5).unwrap();
+        assert_eq!(ema, vec![10.0, 15.0, 17.5]);
+
+        let raw = acc.get_ema_series("tasks_completed".into(), 1.0).unwrap();
+        assert_eq!(raw, vec![10.0, 20.0, 20.0]);
+
+        assert!(acc.get_ema_series("tasks_completed".into(), 0.0).is_err());
+        assert!(acc.get_ema_series("tasks_completed".into(), 1.5).is_err());
+        assert!(acc.get_ema_series("bogus".into(), 0.5).is_err());
+    }
+
+    #[test]
+    fn test_generic_rate() {
+        let mut acc = CompoundAccumulator::default();
+        for i in 0..30 {
+            // Workers grow by 2 per point for the last 10 points only
+            push_point(&mut acc, i, if i < 20 { 1 } else { 1 + (i - 19) * 2 });
+        }
+        assert!((acc.get_rate("tasks_completed".into(), 60) - acc.get_compound_rate()).abs() < 1e-9);
+        assert!((acc.get_rate("active_workers".into(), 10) - 24.0).abs() < 1e-9);
+        assert_eq!(acc.get_rate("bogus".into(), 10), 0.0);
+    }
+
+    #[test]
+    fn test_forecast() {
+        let mut acc = CompoundAccumulator::default();
+        assert!(acc.forecast("tasks_completed".into(), 10, 3).is_empty());
+
+        for (tasks, workers) in [(0, 10), (2, 8), (4, 6), (6, 4)] {
+            push_point(&mut acc, tasks, workers);
+        }
+        let projected = acc.forecast("tasks_completed".into(), 10, 3);
+        assert_eq!(projected.len(), 3);
+        for (value, expected) in projected.iter().zip([8.0, 10.0, 12.0]) {
+            assert!((value - expected).abs() < 1e-9);
+        }
+
+        // Declining metric bottoms out at zero
+        let projected = acc.forecast("active_workers".into(), 10, 4);
+        for (value, expected) in projected.iter().zip([2.0, 0.0, 0.0, 0.0]) {
+            assert!((value - expected).abs() < 1e-9);
+        }
+
+        assert!(acc.forecast("bogus".into(), 10, 3).is_empty());
+    }
+
+    #[test]
+    fn test_lineage_tree_parent_links() {
+        let acc = CompoundAccumulator::default();
+        let workers = r#"[
+            {"handle": "lead", "state": "working", "health": "healthy", "swarmId": "s1", "depthLevel": 0},
+            {"handle": "a", "state": "working", "health": "healthy", "swarmId": "s1", "depthLevel": 1, "parentHandle": "lead"},
+            {"handle": "b", "state": "working", "health": "healthy", "swarmId": "s1", "depthLevel": 1, "parentHandle": "lead"},
+            {"handle": "a1", "state": "idle", "health": "healthy", "swarmId": "s1", "depthLevel": 2, "parentHandle": "a"},
+            {"handle": "a2", "state": "idle", "health": "healthy", "swarmId": "s1", "depthLevel": 2, "parentHandle": "a"}
+        ]"#;
+
+        let tree: serde_json::Value =
+            serde_json::from_str(&acc.build_lineage_tree(workers.to_string()).unwrap()).unwrap();
+        let swarm = &tree["children"][0];
+        assert_eq!(swarm["children"].as_array().unwrap().len(), 1);
+
+        let lead = &swarm["children"][0];
+        assert_eq!(lead["id"], "lead");
+        assert_eq!(lead["children"][0]["id"], "a");
+        assert_eq!(lead["children"][1]["id"], "b");
+        assert!(lead["children"][1].get("children").is_none());
+
+        let a_children: Vec<&str> = lead["children"][0]["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(a_children, vec!["a1", "a2"]);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut acc = CompoundAccumulator::new(Some(100), Some(10.0));
+        for i in 0..15 {
+            push_point(&mut acc, i * 3, i % 4);
+        }
+
+        let restored = CompoundAccumulator::from_json(acc.to_json().unwrap()).unwrap();
+        assert_eq!(restored.get_point_count(), 15);
+        assert_eq!(
+            serde_json::to_string(&restored.get_time_series()).unwrap(),
+            serde_json::to_string(&acc.get_time_series()).unwrap()
+        );
+        assert!((restored.get_compound_rate() - acc.get_compound_rate()).abs() < 1e-9);
+
+        assert!(CompoundAccumulator::from_json("not json".into()).is_err());
+    }
+
+    #[test]
+    fn test_is_stalled() {
+        let mut acc = CompoundAccumulator::default();
+        for tasks in [1, 2, 3, 5, 5, 5, 5] {
+            push_point(&mut acc, tasks, 0);
+        }
+        assert!(acc.is_stalled("tasks_completed".into(), 4, 0.5));
+        assert!(!acc.is_stalled("tasks_completed".into(), 5, 0.5));
+        // Not enough points to judge
+        assert!(!acc.is_stalled("tasks_completed".into(), 20, 0.5));
+        assert!(!acc.is_stalled("bogus".into(), 4, 0.5));
+    }
+
+    #[test]
+    fn test_window_stats() {
+        let mut acc = CompoundAccumulator::default();
+        let empty = acc.window_stats("tasks_completed".into(), 5).unwrap();
+        assert_eq!(empty.count, 0);
+
+        for tasks in [9, 4, 7, 2, 6] {
+            push_point(&mut acc, tasks, 0);
+        }
+        let stats = acc.window_stats("tasks_completed".into(), 3).unwrap();
+        assert_eq!((stats.min, stats.max, stats.first, stats.last), (2.0, 7.0, 7.0, 6.0));
+        assert_eq!(stats.delta, -1.0);
+        assert_eq!(stats.count, 3);
+
+        // Window larger than the series uses all points
+        let stats = acc.window_stats("tasks_completed".into(), 100).unwrap();
+        assert_eq!((stats.min, stats.max, stats.first, stats.count), (2.0, 9.0, 9.0, 5));
+
+        assert!(acc.window_stats("bogus".into(), 3).is_err());
+    }
+
+    #[test]
+    fn test_doubling_time() {
+        let mut acc = CompoundAccumulator::default();
+        for i in 0..13 {
+            push_point(&mut acc, 12 + i * 2, 3);
+        }
+        // 24 tasks/min at 5s intervals; latest value 36 doubles in 1.5 minutes
+        let minutes = acc.doubling_time_minutes("tasks_completed".into(), 60).unwrap();
+        assert!((minutes - 1.5).abs() < 1e-9);
+
+        assert_eq!(acc.doubling_time_minutes("active_workers".into(), 60), None);
+        assert_eq!(acc.doubling_time_minutes("bogus".into(), 60), None);
+    }
+
+    #[test]
+    fn test_correlation() {
+        let mut acc = CompoundAccumulator::default();
+        assert_eq!(acc.correlation("tasks_completed".into(), "active_workers".into(), 10), 0.0);
+
+        for (tasks, workers) in [(1, 10), (3, 9), (5, 8), (7, 7)] {
+            push_point(&mut acc, tasks, workers);
+        }
+        let r = acc.correlation("tasks_completed".into(), "active_workers".into(), 10);
+        assert!((r + 1.0).abs() < 1e-9);
+        let r = acc.correlation("tasks_completed".into(), "tasks_completed".into(), 10);
+        assert!((r - 1.0).abs() < 1e-9);
+
+        // credits_earned never changes, so there is no variance
+        assert_eq!(acc.correlation("tasks_completed".into(), "credits_earned".into(), 10), 0.0);
+    }
 }
@@ -2,7 +2,8 @@
 //! critical path analysis, and parallelizable level extraction.
 //!
 //! All methods accept/return JSON or `#[napi(object)]` structs.
-//! No storage access — pure computation.
+//! No storage access — pure computation. The solver can also hold a graph
+//! in memory and be edited incrementally via `add_node`/`add_edge`.
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
@@ -69,158 +70,186 @@ pub struct NodeSlack {
     pub slack: f64,
     pub earliest_start: f64,
     pub latest_start: f64,
+    pub earliest_finish: f64,
+    pub latest_finish: f64,
 }
 
+/// A dependency edge: `to` depends on `from`
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DependencyEdge {
+    pub from: String,
+    pub to: String,
+}
+
+/// Graph configuration problems found by `validate`
+#[napi(object)]
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidationResult {
+    /// Whether no problems were found
+    pub is_valid: bool,
+    /// Dependencies whose `from` ID is not in the node set
+    pub missing_dependencies: Vec<DependencyEdge>,
+    /// IDs that appear on more than one node
+    pub duplicate_ids: Vec<String>,
+    /// Nodes that list themselves in `depends_on`
+    pub self_dependencies: Vec<String>,
+    /// Human-readable description of every problem
+    pub errors: Vec<String>,
+}
+
+/// Resource-constrained schedule result
+#[napi(object)]
+#[derive(Clone, Debug, Serialize)]
+pub struct ScheduleResult {
+    /// Scheduled nodes in dispatch order
+    pub entries: Vec<ScheduledNode>,
+    /// Finish time of the last node
+    pub makespan: f64,
+    /// Number of workers the schedule was built for
+    pub worker_count: u32,
+}
+
+/// Placement of a single node in a schedule
+#[napi(object)]
+#[derive(Clone, Debug, Serialize)]
+pub struct ScheduledNode {
+    pub id: String,
+    /// Worker slot (0-based) that runs the node
+    pub worker: u32,
+    pub start: f64,
+    pub finish: f64,
+}
+
+/// Adjacency list, in-degree map and id lookup built from a node set
+type Graph<'a> = (
+    HashMap<String, Vec<String>>,
+    HashMap<String, usize>,
+    HashMap<String, &'a DagNode>,
+);
+
 /// The DAG solver engine
 #[napi]
-pub struct DagSolver {}
+pub struct DagSolver {
+    /// In-memory graph for the incremental API, in insertion order
+    nodes: Vec<DagNode>,
+}
+
+impl Default for DagSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[napi]
 impl DagSolver {
     #[napi(constructor)]
     pub fn new() -> Self {
-        Self {}
+        Self { nodes: Vec::new() }
     }
 
     /// Topological sort using Kahn's algorithm with priority ordering.
-    /// Returns execution order and parallelizable levels.
+    /// Returns execution order and parallelizable levels. When `stable`
+    /// (default true), equal-priority nodes within a level are ordered by ID
+    /// so the output is reproducible across runs.
     #[napi]
-    pub fn topological_sort(&self, nodes_json: String) -> Result<TopologicalResult> {
-        let nodes: Vec<DagNode> = serde_json::from_str(&nodes_json).map_err(|e| {
-            Error::new(Status::InvalidArg, format!("Invalid nodes JSON: {}", e))
-        })?;
-
-        let (adj, in_degree, node_map) = build_graph(&nodes);
-        let node_count = nodes.len() as u32;
+    pub fn topological_sort(&self, nodes_json: String, stable: Option<bool>) -> Result<TopologicalResult> {
+        let nodes = parse_nodes(&nodes_json)?;
+        Ok(topo_sort(&nodes, stable.unwrap_or(true)))
+    }
 
-        // Kahn's algorithm with level tracking
-        let mut in_deg = in_degree.clone();
-        let mut queue: VecDeque<String> = VecDeque::new();
-        let mut order: Vec<String> = Vec::new();
-        let mut levels: Vec<Vec<String>> = Vec::new();
+    /// Detect cycles using DFS with three-coloring.
+    #[napi]
+    pub fn detect_cycles(&self, nodes_json: String) -> Result<CycleResult> {
+        let nodes = parse_nodes(&nodes_json)?;
+        Ok(find_cycles(&nodes))
+    }
 
-        // Seed queue with zero in-degree nodes
-        for (id, &deg) in &in_deg {
-            if deg == 0 {
-                queue.push_back(id.clone());
-            }
+    /// Add a node to the in-memory graph, replacing any node with the same ID.
+    #[napi]
+    pub fn add_node(&mut self, node: DagNode) {
+        match self.nodes.iter_mut().find(|n| n.id == node.id) {
+            Some(existing) => *existing = node,
+            None => self.nodes.push(node),
         }
+    }
 
-        while !queue.is_empty() {
-            // Sort current level by priority (descending)
-            let mut level: Vec<String> = queue.drain(..).collect();
-            level.sort_by(|a, b| {
-                let pa = node_map.get(a).and_then(|n| n.priority).unwrap_or(0);
-                let pb = node_map.get(b).and_then(|n| n.priority).unwrap_or(0);
-                pb.cmp(&pa)
-            });
-
-            for id in &level {
-                order.push(id.clone());
-                if let Some(neighbors) = adj.get(id) {
-                    for neighbor in neighbors {
-                        if let Some(deg) = in_deg.get_mut(neighbor) {
-                            *deg -= 1;
-                            if *deg == 0 {
-                                queue.push_back(neighbor.clone());
-                            }
-                        }
-                    }
-                }
+    /// Remove a node from the in-memory graph along with every edge touching it.
+    /// Returns false if the node was not present.
+    #[napi]
+    pub fn remove_node(&mut self, id: String) -> bool {
+        let before = self.nodes.len();
+        self.nodes.retain(|n| n.id != id);
+        if self.nodes.len() == before {
+            return false;
+        }
+        for node in &mut self.nodes {
+            if let Some(deps) = &mut node.depends_on {
+                deps.retain(|d| d != &id);
             }
-
-            levels.push(level);
         }
-
-        let is_valid = order.len() == nodes.len();
-
-        Ok(TopologicalResult {
-            order,
-            levels,
-            is_valid,
-            node_count,
-        })
+        true
     }
 
-    /// Detect cycles using DFS with three-coloring.
+    /// Add an edge to the in-memory graph: `to` depends on `from`.
+    /// Both nodes must already exist; adding an existing edge is a no-op.
     #[napi]
-    pub fn detect_cycles(&self, nodes_json: String) -> Result<CycleResult> {
-        let nodes: Vec<DagNode> = serde_json::from_str(&nodes_json).map_err(|e| {
-            Error::new(Status::InvalidArg, format!("Invalid nodes JSON: {}", e))
-        })?;
-
-        let (adj, _, _) = build_graph(&nodes);
-
-        let mut white: HashSet<String> = nodes.iter().map(|n| n.id.clone()).collect();
-        let mut gray: HashSet<String> = HashSet::new();
-        let mut black: HashSet<String> = HashSet::new();
-        let mut cycle_nodes: HashSet<String> = HashSet::new();
-        let mut cycles: Vec<Vec<String>> = Vec::new();
-
-        fn dfs(
-            node: &str,
-            adj: &HashMap<String, Vec<String>>,
-            white: &mut HashSet<String>,
-            gray: &mut HashSet<String>,
-            black: &mut HashSet<String>,
-            path: &mut Vec<String>,
-            cycle_nodes: &mut HashSet<String>,
-            cycles: &mut Vec<Vec<String>>,
-        ) {
-            white.remove(node);
-            gray.insert(node.to_string());
-            path.push(node.to_string());
-
-            if let Some(neighbors) = adj.get(node) {
-                for neighbor in neighbors {
-                    if gray.contains(neighbor) {
-                        // Found a cycle
-                        let cycle_start = path.iter().position(|n| n == neighbor).unwrap_or(0);
-                        let cycle: Vec<String> = path[cycle_start..].to_vec();
-                        for n in &cycle {
-                            cycle_nodes.insert(n.clone());
-                        }
-                        cycles.push(cycle);
-                    } else if white.contains(neighbor) {
-                        dfs(neighbor, adj, white, gray, black, path, cycle_nodes, cycles);
-                    }
-                }
-            }
+    pub fn add_edge(&mut self, from: String, to: String) -> Result<()> {
+        if !self.nodes.iter().any(|n| n.id == from) {
+            return Err(Error::new(Status::InvalidArg, format!("Unknown node: {}", from)));
+        }
+        let node = self.nodes.iter_mut().find(|n| n.id == to)
+            .ok_or_else(|| Error::new(Status::InvalidArg, format!("Unknown node: {}", to)))?;
 
-            path.pop();
-            gray.remove(node);
-            black.insert(node.to_string());
+        let deps = node.depends_on.get_or_insert_with(Vec::new);
+        if !deps.contains(&from) {
+            deps.push(from);
         }
+        Ok(())
+    }
 
-        let start_nodes: Vec<String> = white.iter().cloned().collect();
-        let mut path: Vec<String> = Vec::new();
+    /// Remove the edge where `to` depends on `from`. Returns false if absent.
+    #[napi]
+    pub fn remove_edge(&mut self, from: String, to: String) -> bool {
+        let Some(deps) = self.nodes.iter_mut()
+            .find(|n| n.id == to)
+            .and_then(|n| n.depends_on.as_mut())
+        else {
+            return false;
+        };
+        let before = deps.len();
+        deps.retain(|d| d != &from);
+        deps.len() != before
+    }
 
-        for node in start_nodes {
-            if white.contains(&node) {
-                dfs(&node, &adj, &mut white, &mut gray, &mut black, &mut path, &mut cycle_nodes, &mut cycles);
-            }
-        }
+    /// Nodes currently held in the in-memory graph.
+    #[napi]
+    pub fn graph_nodes(&self) -> Vec<DagNode> {
+        self.nodes.clone()
+    }
 
-        let has_cycles = !cycles.is_empty();
-        Ok(CycleResult {
-            has_cycles,
-            cycle_nodes: cycle_nodes.into_iter().collect(),
-            cycles,
-        })
+    /// `topological_sort` over the in-memory graph.
+    #[napi]
+    pub fn graph_topological_sort(&self) -> TopologicalResult {
+        topo_sort(&self.nodes, true)
+    }
+
+    /// `detect_cycles` over the in-memory graph.
+    #[napi]
+    pub fn graph_detect_cycles(&self) -> CycleResult {
+        find_cycles(&self.nodes)
     }
 
     /// Compute critical path (longest path through the DAG).
     /// Requires nodes to have estimated_duration set.
     #[napi]
     pub fn critical_path(&self, nodes_json: String) -> Result<CriticalPathResult> {
-        let nodes: Vec<DagNode> = serde_json::from_str(&nodes_json).map_err(|e| {
-            Error::new(Status::InvalidArg, format!("Invalid nodes JSON: {}", e))
-        })?;
+        let nodes = parse_nodes(&nodes_json)?;
 
         let (adj, _, node_map) = build_graph(&nodes);
 
         // Forward pass: compute earliest start times
-        let topo = self.topological_sort(nodes_json.clone())?;
+        let topo = topo_sort(&nodes, true);
         if !topo.is_valid {
             return Err(Error::new(Status::InvalidArg, "Graph contains cycles; cannot compute critical path"));
         }
@@ -253,7 +282,7 @@ impl DagSolver {
 
         for id in topo.order.iter().rev() {
             let duration = node_map.get(id).and_then(|n| n.estimated_duration).unwrap_or(1.0);
-            let lf = latest_finish.entry(id.clone()).or_insert(total_duration);
+            latest_finish.entry(id.clone()).or_insert(total_duration);
 
             if let Some(neighbors) = adj.get(id) {
                 for neighbor in neighbors {
@@ -276,6 +305,8 @@ impl DagSolver {
         for id in &topo.order {
             let es = earliest_start.get(id).copied().unwrap_or(0.0);
             let ls = latest_start.get(id).copied().unwrap_or(0.0);
+            let ef = earliest_finish.get(id).copied().unwrap_or(es);
+            let lf = latest_finish.get(id).copied().unwrap_or(ls);
             let slack = ls - es;
 
             if slack.abs() < 0.001 {
@@ -287,6 +318,8 @@ impl DagSolver {
                 slack,
                 earliest_start: es,
                 latest_start: ls,
+                earliest_finish: ef,
+                latest_finish: lf,
             });
         }
 
@@ -297,22 +330,82 @@ impl DagSolver {
         })
     }
 
+    /// Longest-duration path from `from` to `to`, counting both endpoints'
+    /// durations (default 1). `path` is empty and `total_duration` 0 when `to`
+    /// is not reachable from `from`; `slack` is always empty.
+    #[napi]
+    pub fn longest_path_between(
+        &self,
+        nodes_json: String,
+        from: String,
+        to: String,
+    ) -> Result<CriticalPathResult> {
+        let nodes = parse_nodes(&nodes_json)?;
+        let (adj, _, node_map) = build_graph(&nodes);
+
+        let topo = topo_sort(&nodes, true);
+        if !topo.is_valid {
+            return Err(Error::new(Status::InvalidArg, "Graph contains cycles; cannot compute longest path"));
+        }
+
+        let duration = |id: &str| node_map.get(id).and_then(|n| n.estimated_duration).unwrap_or(1.0);
+
+        // Longest distance from `from` to each reachable node, with predecessor
+        let mut best: HashMap<String, (f64, Option<String>)> = HashMap::new();
+        if node_map.contains_key(&from) {
+            best.insert(from.clone(), (duration(&from), None));
+        }
+        for id in &topo.order {
+            let Some(&(dist, _)) = best.get(id) else { continue };
+            for neighbor in adj.get(id).into_iter().flatten() {
+                let candidate = dist + duration(neighbor);
+                if best.get(neighbor).is_none_or(|(d, _)| candidate > *d) {
+                    best.insert(neighbor.clone(), (candidate, Some(id.clone())));
+                }
+            }
+        }
+
+        let Some(&(total_duration, _)) = best.get(&to) else {
+            return Ok(CriticalPathResult { path: vec![], total_duration: 0.0, slack: vec![] });
+        };
+
+        let mut path = vec![to.clone()];
+        while let Some((_, Some(prev))) = best.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+
+        Ok(CriticalPathResult { path, total_duration, slack: vec![] })
+    }
+
     /// Get IDs of nodes that have all dependencies satisfied.
     /// Useful for finding which tasks can be started immediately.
+    /// Nodes listed in `in_progress_json` are excluded, and an in-progress
+    /// dependency does not count as satisfied until it is completed.
     #[napi]
-    pub fn get_ready_nodes(&self, nodes_json: String, completed_json: String) -> Result<Vec<String>> {
-        let nodes: Vec<DagNode> = serde_json::from_str(&nodes_json).map_err(|e| {
-            Error::new(Status::InvalidArg, format!("Invalid nodes JSON: {}", e))
-        })?;
+    pub fn get_ready_nodes(
+        &self,
+        nodes_json: String,
+        completed_json: String,
+        in_progress_json: Option<String>,
+    ) -> Result<Vec<String>> {
+        let nodes = parse_nodes(&nodes_json)?;
 
         let completed: HashSet<String> = serde_json::from_str(&completed_json).map_err(|e| {
             Error::new(Status::InvalidArg, format!("Invalid completed JSON: {}", e))
         })?;
 
+        let in_progress: HashSet<String> = match in_progress_json {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                Error::new(Status::InvalidArg, format!("Invalid in-progress JSON: {}", e))
+            })?,
+            None => HashSet::new(),
+        };
+
         let mut ready: Vec<String> = Vec::new();
 
         for node in &nodes {
-            if completed.contains(&node.id) {
+            if completed.contains(&node.id) || in_progress.contains(&node.id) {
                 continue;
             }
 
@@ -335,14 +428,672 @@ impl DagSolver {
 
         Ok(ready)
     }
+
+    /// Check the node set for missing dependency targets, duplicate IDs and
+    /// self-dependencies. `schedule` runs this first and rejects invalid graphs.
+    #[napi]
+    pub fn validate(&self, nodes_json: String) -> Result<ValidationResult> {
+        let nodes = parse_nodes(&nodes_json)?;
+        Ok(validate_nodes(&nodes))
+    }
+
+    /// Remove redundant direct dependencies (A→C when A→B→C exists) while
+    /// preserving reachability. Returns the reduced node set as JSON.
+    /// Errors if the graph contains cycles.
+    #[napi]
+    pub fn transitive_reduction(&self, nodes_json: String) -> Result<String> {
+        let mut nodes = parse_nodes(&nodes_json)?;
+        if find_cycles(&nodes).has_cycles {
+            return Err(Error::new(Status::InvalidArg, "Graph contains cycles; cannot compute transitive reduction"));
+        }
+
+        let deps: HashMap<String, Vec<String>> = nodes.iter()
+            .map(|n| (n.id.clone(), n.depends_on.clone().unwrap_or_default()))
+            .collect();
+        let upstream: HashMap<&str, HashSet<String>> = deps.keys()
+            .map(|id| (id.as_str(), reachable(id, &deps).into_iter().collect()))
+            .collect();
+
+        for node in &mut nodes {
+            let Some(direct) = &node.depends_on else { continue };
+            let mut reduced: Vec<String> = Vec::new();
+            for dep in direct {
+                let implied = direct.iter()
+                    .filter(|other| *other != dep)
+                    .any(|other| upstream.get(other.as_str()).is_some_and(|up| up.contains(dep)));
+                if !implied && !reduced.contains(dep) {
+                    reduced.push(dep.clone());
+                }
+            }
+            node.depends_on = Some(reduced);
+        }
+
+        serde_json::to_string(&nodes).map_err(|e| {
+            Error::new(Status::GenericFailure, format!("Serialization error: {}", e))
+        })
+    }
+
+    /// Union two node sets into one graph, returned as JSON.
+    /// Nodes keep the order of `a`, followed by nodes only in `b`. For an ID in
+    /// both: `depends_on` is the deduplicated union (a's entries first), and
+    /// `priority` / `estimated_duration` take the larger value, with an unset
+    /// value always losing to a set one.
+    #[napi]
+    pub fn merge(&self, a_json: String, b_json: String) -> Result<String> {
+        let mut merged = parse_nodes(&a_json)?;
+        let other = parse_nodes(&b_json)?;
+
+        for node in other {
+            let Some(existing) = merged.iter_mut().find(|n| n.id == node.id) else {
+                merged.push(node);
+                continue;
+            };
+
+            existing.priority = existing.priority.max(node.priority);
+            existing.estimated_duration = match (existing.estimated_duration, node.estimated_duration) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            };
+            if let Some(extra) = node.depends_on {
+                let deps = existing.depends_on.get_or_insert_with(Vec::new);
+                for dep in extra {
+                    if !deps.contains(&dep) {
+                        deps.push(dep);
+                    }
+                }
+            }
+        }
+
+        serde_json::to_string(&merged).map_err(|e| {
+            Error::new(Status::GenericFailure, format!("Serialization error: {}", e))
+        })
+    }
+
+    /// Strongly connected components via Tarjan's algorithm, in reverse
+    /// topological order with members listed in input order. Single nodes
+    /// without a self-dependency are only reported when `include_singletons`
+    /// is true (default false), so by default each group is one cycle cluster.
+    #[napi]
+    pub fn strongly_connected_components(
+        &self,
+        nodes_json: String,
+        include_singletons: Option<bool>,
+    ) -> Result<Vec<Vec<String>>> {
+        let nodes = parse_nodes(&nodes_json)?;
+        let index: HashMap<&str, usize> = nodes.iter().enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        for (to, node) in nodes.iter().enumerate() {
+            for dep in node.depends_on.iter().flatten() {
+                if let Some(&from) = index.get(dep.as_str()) {
+                    successors[from].push(to);
+                }
+            }
+        }
+
+        let include_singletons = include_singletons.unwrap_or(false);
+        let components = tarjan_scc(&successors)
+            .into_iter()
+            .filter(|c| include_singletons || c.len() > 1 || successors[c[0]].contains(&c[0]))
+            .map(|mut c| {
+                c.sort_unstable();
+                c.into_iter().map(|i| nodes[i].id.clone()).collect()
+            })
+            .collect();
+
+        Ok(components)
+    }
+
+    /// Suggest dependency edges whose removal makes the graph acyclic.
+    /// Uses the Eades–Lin–Smyth greedy feedback-arc-set ordering, then restores
+    /// any removed edge that would not recreate a cycle, so the set is small
+    /// though not guaranteed minimum. Empty for an acyclic graph.
+    #[napi]
+    pub fn suggest_cycle_breaks(&self, nodes_json: String) -> Result<Vec<DependencyEdge>> {
+        let nodes = parse_nodes(&nodes_json)?;
+        let index: HashMap<&str, usize> = nodes.iter().enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for (to, node) in nodes.iter().enumerate() {
+            for dep in node.depends_on.iter().flatten() {
+                if let Some(&from) = index.get(dep.as_str()) {
+                    if !edges.contains(&(from, to)) {
+                        edges.push((from, to));
+                    }
+                }
+            }
+        }
+
+        let position = greedy_fas_order(nodes.len(), &edges);
+        let (mut kept, removed): (Vec<_>, Vec<_>) = edges.into_iter()
+            .partition(|&(from, to)| position[from] < position[to]);
+
+        let mut breaks: Vec<DependencyEdge> = Vec::new();
+        for (from, to) in removed {
+            // Restore the edge if `from` is not reachable from `to` without it
+            if from != to && !path_exists(to, from, &kept) {
+                kept.push((from, to));
+            } else {
+                breaks.push(DependencyEdge {
+                    from: nodes[from].id.clone(),
+                    to: nodes[to].id.clone(),
+                });
+            }
+        }
+
+        Ok(breaks)
+    }
+
+    /// All transitive dependencies of `id` (everything upstream), nearest first.
+    /// Returns an empty list for unknown IDs.
+    #[napi]
+    pub fn ancestors(&self, nodes_json: String, id: String) -> Result<Vec<String>> {
+        let nodes = parse_nodes(&nodes_json)?;
+        let deps: HashMap<String, Vec<String>> = nodes.iter()
+            .map(|n| (n.id.clone(), n.depends_on.clone().unwrap_or_default()))
+            .collect();
+        Ok(reachable(&id, &deps))
+    }
+
+    /// All nodes that transitively depend on `id` (everything downstream, i.e.
+    /// blocked if it fails), nearest first. Returns an empty list for unknown IDs.
+    #[napi]
+    pub fn descendants(&self, nodes_json: String, id: String) -> Result<Vec<String>> {
+        let nodes = parse_nodes(&nodes_json)?;
+        let (adj, _, node_map) = build_graph(&nodes);
+        if !node_map.contains_key(&id) {
+            return Ok(Vec::new());
+        }
+        Ok(reachable(&id, &adj))
+    }
+
+    /// Greedy list scheduling with at most `worker_count` concurrent nodes.
+    /// Whenever a worker is free it takes the highest-priority ready node (ties
+    /// by ID). Unlike `critical_path`, this accounts for limited parallelism,
+    /// so `makespan` is a realistic delivery estimate. Durations default to 1.
+    #[napi]
+    pub fn schedule(&self, nodes_json: String, worker_count: u32) -> Result<ScheduleResult> {
+        let nodes = parse_nodes(&nodes_json)?;
+        if worker_count == 0 {
+            return Err(Error::new(Status::InvalidArg, "worker_count must be at least 1"));
+        }
+        let validation = validate_nodes(&nodes);
+        if !validation.is_valid {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("Invalid graph: {}", validation.errors.join("; ")),
+            ));
+        }
+        if !topo_sort(&nodes, true).is_valid {
+            return Err(Error::new(Status::InvalidArg, "Graph contains cycles; cannot compute schedule"));
+        }
+
+        let (adj, mut pending, node_map) = build_graph(&nodes);
+        let mut ready: Vec<String> = nodes.iter()
+            .filter(|n| pending.get(&n.id) == Some(&0))
+            .map(|n| n.id.clone())
+            .collect();
+        let mut free_workers: Vec<u32> = (0..worker_count).rev().collect();
+        // (finish, id, worker)
+        let mut running: Vec<(f64, String, u32)> = Vec::new();
+        let mut entries: Vec<ScheduledNode> = Vec::new();
+        let mut time = 0.0_f64;
+
+        loop {
+            ready.sort_by(|a, b| {
+                let pa = node_map.get(a).and_then(|n| n.priority).unwrap_or(0);
+                let pb = node_map.get(b).and_then(|n| n.priority).unwrap_or(0);
+                pb.cmp(&pa).then_with(|| a.cmp(b))
+            });
+            while !ready.is_empty() {
+                let Some(worker) = free_workers.pop() else { break };
+                let id = ready.remove(0);
+                let duration = node_map.get(&id).and_then(|n| n.estimated_duration).unwrap_or(1.0);
+                running.push((time + duration, id.clone(), worker));
+                entries.push(ScheduledNode { id, worker, start: time, finish: time + duration });
+            }
+
+            if running.is_empty() {
+                break;
+            }
+
+            // Advance to the next completion and release everything finishing then
+            time = running.iter().map(|r| r.0).fold(f64::MAX, f64::min);
+            let (done, still_running): (Vec<_>, Vec<_>) =
+                running.into_iter().partition(|r| r.0 <= time + 1e-9);
+            running = still_running;
+
+            for (_, id, worker) in done {
+                free_workers.push(worker);
+                for dependent in adj.get(&id).into_iter().flatten() {
+                    if let Some(count) = pending.get_mut(dependent) {
+                        *count -= 1;
+                        if *count == 0 {
+                            ready.push(dependent.clone());
+                        }
+                    }
+                }
+            }
+            // Hand out the lowest-numbered free worker first
+            free_workers.sort_by(|a, b| b.cmp(a));
+        }
+
+        let makespan = entries.iter().map(|e| e.finish).fold(0.0_f64, f64::max);
+        Ok(ScheduleResult { entries, makespan, worker_count })
+    }
+
+    /// Export the graph as a Graphviz `digraph` with edges dependency → dependent.
+    /// Labels show priority and duration when set. With `highlight_critical`, nodes
+    /// and edges on the critical path are drawn in red (the graph must be acyclic).
+    /// Dependencies on unknown node IDs are omitted.
+    #[napi]
+    pub fn to_dot(&self, nodes_json: String, highlight_critical: Option<bool>) -> Result<String> {
+        let nodes = parse_nodes(&nodes_json)?;
+        let critical = self.critical_set(&nodes_json, highlight_critical)?;
+        let known: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+
+        let mut out = String::from("digraph dag {\n    rankdir=LR;\n");
+        for node in &nodes {
+            let label = node_label(node, "\\n").replace('"', "\\\"");
+            let id = node.id.replace('"', "\\\"");
+            if critical.contains(&node.id) {
+                out.push_str(&format!(
+                    "    \"{}\" [label=\"{}\", style=filled, fillcolor=\"#ffcccc\", color=red];\n",
+                    id, label
+                ));
+            } else {
+                out.push_str(&format!("    \"{}\" [label=\"{}\"];\n", id, label));
+            }
+        }
+        for node in &nodes {
+            for dep in node.depends_on.iter().flatten().filter(|d| known.contains(d.as_str())) {
+                let style = if critical.contains(dep) && critical.contains(&node.id) {
+                    " [color=red, penwidth=2]"
+                } else {
+                    ""
+                };
+                out.push_str(&format!(
+                    "    \"{}\" -> \"{}\"{};\n",
+                    dep.replace('"', "\\\""),
+                    node.id.replace('"', "\\\""),
+                    style
+                ));
+            }
+        }
+        out.push_str("}\n");
+        Ok(out)
+    }
+
+    /// Export the graph as a Mermaid `graph TD` block. Node IDs are mapped to
+    /// `n0`, `n1`, ... in input order so arbitrary IDs stay valid Mermaid; the
+    /// original ID appears in the label. `highlight_critical` works as in `to_dot`.
+    #[napi]
+    pub fn to_mermaid(&self, nodes_json: String, highlight_critical: Option<bool>) -> Result<String> {
+        let nodes = parse_nodes(&nodes_json)?;
+        let critical = self.critical_set(&nodes_json, highlight_critical)?;
+        let keys: HashMap<&str, String> = nodes.iter().enumerate()
+            .map(|(i, n)| (n.id.as_str(), format!("n{}", i)))
+            .collect();
+
+        let mut out = String::from("graph TD\n");
+        for node in &nodes {
+            let label = node_label(node, "<br/>").replace('"', "#quot;");
+            out.push_str(&format!("    {}[\"{}\"]\n", keys[node.id.as_str()], label));
+        }
+        for node in &nodes {
+            for dep in node.depends_on.iter().flatten() {
+                if let Some(dep_key) = keys.get(dep.as_str()) {
+                    out.push_str(&format!("    {} --> {}\n", dep_key, keys[node.id.as_str()]));
+                }
+            }
+        }
+        if !critical.is_empty() {
+            let members: Vec<&str> = nodes.iter()
+                .filter(|n| critical.contains(&n.id))
+                .map(|n| keys[n.id.as_str()].as_str())
+                .collect();
+            out.push_str("    classDef critical fill:#ffcccc,stroke:#cc0000,stroke-width:2px\n");
+            out.push_str(&format!("    class {} critical\n", members.join(",")));
+        }
+        Ok(out)
+    }
+}
+
+impl DagSolver {
+    /// Critical-path node IDs when highlighting is requested, otherwise empty
+    fn critical_set(&self, nodes_json: &str, highlight_critical: Option<bool>) -> Result<HashSet<String>> {
+        if !highlight_critical.unwrap_or(false) {
+            return Ok(HashSet::new());
+        }
+        let result = self.critical_path(nodes_json.to_string())?;
+        Ok(result.path.into_iter().collect())
+    }
+}
+
+fn validate_nodes(nodes: &[DagNode]) -> ValidationResult {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut duplicate_ids: Vec<String> = Vec::new();
+    for node in nodes {
+        if !seen.insert(&node.id) && !duplicate_ids.contains(&node.id) {
+            duplicate_ids.push(node.id.clone());
+        }
+    }
+
+    let mut missing_dependencies: Vec<DependencyEdge> = Vec::new();
+    let mut self_dependencies: Vec<String> = Vec::new();
+    for node in nodes {
+        for dep in node.depends_on.iter().flatten() {
+            if dep == &node.id {
+                if !self_dependencies.contains(&node.id) {
+                    self_dependencies.push(node.id.clone());
+                }
+            } else if !seen.contains(dep.as_str()) {
+                missing_dependencies.push(DependencyEdge { from: dep.clone(), to: node.id.clone() });
+            }
+        }
+    }
+
+    let mut errors: Vec<String> = Vec::new();
+    errors.extend(duplicate_ids.iter().map(|id| format!("Duplicate node id: {}", id)));
+    errors.extend(self_dependencies.iter().map(|id| format!("Node {} depends on itself", id)));
+    errors.extend(missing_dependencies.iter().map(|e| {
+        format!("Node {} depends on unknown node {}", e.to, e.from)
+    }));
+
+    ValidationResult {
+        is_valid: errors.is_empty(),
+        missing_dependencies,
+        duplicate_ids,
+        self_dependencies,
+        errors,
+    }
+}
+
+/// Eades–Lin–Smyth vertex ordering: repeatedly peel sinks to the back and
+/// sources to the front, otherwise move the vertex with the largest
+/// out-degree minus in-degree to the front. Returns each vertex's position;
+/// edges pointing backwards in this order form a feedback arc set.
+fn greedy_fas_order(count: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut alive = vec![true; count];
+    let mut front: Vec<usize> = Vec::new();
+    let mut back: Vec<usize> = Vec::new();
+
+    let degrees = |alive: &[bool], v: usize| {
+        let live = |&&(a, b): &&(usize, usize)| alive[a] && alive[b] && a != b;
+        let out = edges.iter().filter(live).filter(|e| e.0 == v).count() as i64;
+        let inc = edges.iter().filter(live).filter(|e| e.1 == v).count() as i64;
+        (out, inc)
+    };
+
+    while alive.iter().any(|&a| a) {
+        let live: Vec<usize> = (0..count).filter(|&v| alive[v]).collect();
+        if let Some(&sink) = live.iter().find(|&&v| degrees(&alive, v).0 == 0) {
+            alive[sink] = false;
+            back.push(sink);
+        } else if let Some(&source) = live.iter().find(|&&v| degrees(&alive, v).1 == 0) {
+            alive[source] = false;
+            front.push(source);
+        } else {
+            let best = live.iter().copied()
+                .max_by_key(|&v| {
+                    let (out, inc) = degrees(&alive, v);
+                    // Prefer earlier vertices on ties
+                    (out - inc, std::cmp::Reverse(v))
+                })
+                .unwrap_or(live[0]);
+            alive[best] = false;
+            front.push(best);
+        }
+    }
+
+    let mut position = vec![0; count];
+    for (pos, &v) in front.iter().chain(back.iter().rev()).enumerate() {
+        position[v] = pos;
+    }
+    position
+}
+
+/// Tarjan's strongly connected components over an index-based adjacency list
+fn tarjan_scc(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct State<'a> {
+        successors: &'a [Vec<usize>],
+        next_index: usize,
+        index: Vec<Option<usize>>,
+        lowlink: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn visit(state: &mut State, v: usize) {
+        state.index[v] = Some(state.next_index);
+        state.lowlink[v] = state.next_index;
+        state.next_index += 1;
+        state.stack.push(v);
+        state.on_stack[v] = true;
+
+        for &w in &state.successors[v] {
+            match state.index[w] {
+                None => {
+                    visit(state, w);
+                    state.lowlink[v] = state.lowlink[v].min(state.lowlink[w]);
+                }
+                Some(w_index) if state.on_stack[w] => {
+                    state.lowlink[v] = state.lowlink[v].min(w_index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(state.lowlink[v]) == state.index[v] {
+            let mut component = Vec::new();
+            while let Some(w) = state.stack.pop() {
+                state.on_stack[w] = false;
+                component.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            state.components.push(component);
+        }
+    }
+
+    let count = successors.len();
+    let mut state = State {
+        successors,
+        next_index: 0,
+        index: vec![None; count],
+        lowlink: vec![0; count],
+        on_stack: vec![false; count],
+        stack: Vec::new(),
+        components: Vec::new(),
+    };
+    for v in 0..count {
+        if state.index[v].is_none() {
+            visit(&mut state, v);
+        }
+    }
+    state.components
+}
+
+/// Whether `target` is reachable from `start` over `edges`
+fn path_exists(start: usize, target: usize, edges: &[(usize, usize)]) -> bool {
+    let mut seen: HashSet<usize> = HashSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(current) = stack.pop() {
+        if current == target {
+            return true;
+        }
+        for &(from, to) in edges {
+            if from == current && seen.insert(to) {
+                stack.push(to);
+            }
+        }
+    }
+    false
+}
+
+/// Breadth-first walk from `start` over `edges`, excluding `start` itself.
+/// Each node is reported once, so diamonds do not produce duplicates.
+fn reachable(start: &str, edges: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut seen: HashSet<&str> = HashSet::from([start]);
+    let mut queue: VecDeque<&str> = VecDeque::from([start]);
+    let mut found: Vec<String> = Vec::new();
+
+    while let Some(current) = queue.pop_front() {
+        for next in edges.get(current).into_iter().flatten() {
+            if seen.insert(next) {
+                found.push(next.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    found
+}
+
+/// Display label for exports: the ID plus priority/duration when set
+fn node_label(node: &DagNode, line_break: &str) -> String {
+    let mut label = node.id.clone();
+    if let Some(priority) = node.priority {
+        label.push_str(&format!("{}priority: {}", line_break, priority));
+    }
+    if let Some(duration) = node.estimated_duration {
+        label.push_str(&format!("{}duration: {}s", line_break, duration));
+    }
+    label
+}
+
+fn parse_nodes(nodes_json: &str) -> Result<Vec<DagNode>> {
+    serde_json::from_str(nodes_json).map_err(|e| {
+        Error::new(Status::InvalidArg, format!("Invalid nodes JSON: {}", e))
+    })
+}
+
+/// Topological sort shared by the JSON and in-memory APIs
+fn topo_sort(nodes: &[DagNode], stable: bool) -> TopologicalResult {
+    let (adj, in_degree, node_map) = build_graph(nodes);
+    let node_count = nodes.len() as u32;
+
+    // Kahn's algorithm with level tracking
+    let mut in_deg = in_degree.clone();
+    let mut queue: VecDeque<String> = VecDeque::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut levels: Vec<Vec<String>> = Vec::new();
+
+    // Seed queue with zero in-degree nodes
+    for (id, &deg) in &in_deg {
+        if deg == 0 {
+            queue.push_back(id.clone());
+        }
+    }
+
+    while !queue.is_empty() {
+        // Sort current level by priority (descending)
+        let mut level: Vec<String> = queue.drain(..).collect();
+        level.sort_by(|a, b| {
+            let pa = node_map.get(a).and_then(|n| n.priority).unwrap_or(0);
+            let pb = node_map.get(b).and_then(|n| n.priority).unwrap_or(0);
+            let by_priority = pb.cmp(&pa);
+            if stable { by_priority.then_with(|| a.cmp(b)) } else { by_priority }
+        });
+
+        for id in &level {
+            order.push(id.clone());
+            if let Some(neighbors) = adj.get(id) {
+                for neighbor in neighbors {
+                    if let Some(deg) = in_deg.get_mut(neighbor) {
+                        *deg -= 1;
+                        if *deg == 0 {
+                            queue.push_back(neighbor.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        levels.push(level);
+    }
+
+    let is_valid = order.len() == nodes.len();
+
+    TopologicalResult {
+        order,
+        levels,
+        is_valid,
+        node_count,
+    }
+}
+
+/// Cycle detection using DFS with three-coloring
+fn find_cycles(nodes: &[DagNode]) -> CycleResult {
+    let (adj, _, _) = build_graph(nodes);
+
+    let mut white: HashSet<String> = nodes.iter().map(|n| n.id.clone()).collect();
+    let mut gray: HashSet<String> = HashSet::new();
+    let mut black: HashSet<String> = HashSet::new();
+    let mut cycle_nodes: HashSet<String> = HashSet::new();
+    let mut cycles: Vec<Vec<String>> = Vec::new();
+
+    #[allow(clippy::too_many_arguments)]
+    fn dfs(
+        node: &str,
+        adj: &HashMap<String, Vec<String>>,
+        white: &mut HashSet<String>,
+        gray: &mut HashSet<String>,
+        black: &mut HashSet<String>,
+        path: &mut Vec<String>,
+        cycle_nodes: &mut HashSet<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        white.remove(node);
+        gray.insert(node.to_string());
+        path.push(node.to_string());
+
+        if let Some(neighbors) = adj.get(node) {
+            for neighbor in neighbors {
+                if gray.contains(neighbor) {
+                    // Found a cycle
+                    let cycle_start = path.iter().position(|n| n == neighbor).unwrap_or(0);
+                    let cycle: Vec<String> = path[cycle_start..].to_vec();
+                    for n in &cycle {
+                        cycle_nodes.insert(n.clone());
+                    }
+                    cycles.push(cycle);
+                } else if white.contains(neighbor) {
+                    dfs(neighbor, adj, white, gray, black, path, cycle_nodes, cycles);
+                }
+            }
+        }
+
+        path.pop();
+        gray.remove(node);
+        black.insert(node.to_string());
+    }
+
+    let start_nodes: Vec<String> = white.iter().cloned().collect();
+    let mut path: Vec<String> = Vec::new();
+
+    for node in start_nodes {
+        if white.contains(&node) {
+            dfs(&node, &adj, &mut white, &mut gray, &mut black, &mut path, &mut cycle_nodes, &mut cycles);
+        }
+    }
+
+    let has_cycles = !cycles.is_empty();
+    CycleResult {
+        has_cycles,
+        cycle_nodes: cycle_nodes.into_iter().collect(),
+        cycles,
+    }
 }
 
 /// Build adjacency list and in-degree map from nodes
-fn build_graph(nodes: &[DagNode]) -> (
-    HashMap<String, Vec<String>>,
-    HashMap<String, usize>,
-    HashMap<String, &DagNode>,
-) {
+fn build_graph(nodes: &[DagNode]) -> Graph<'_> {
     let mut adj: HashMap<String, Vec<String>> = HashMap::new();
     let mut in_degree: HashMap<String, usize> = HashMap::new();
     let mut node_map: HashMap<String, &DagNode> = HashMap::new();
@@ -371,7 +1122,9 @@ fn build_graph(nodes: &[DagNode]) -> (
 mod tests {
     use super::*;
 
-    fn make_nodes_json(nodes: &[(&str, Option<i32>, Option<f64>, Vec<&str>)]) -> String {
+    type NodeSpec<'a> = (&'a str, Option<i32>, Option<f64>, Vec<&'a str>);
+
+    fn make_nodes_json(nodes: &[NodeSpec]) -> String {
         let dag_nodes: Vec<DagNode> = nodes.iter().map(|(id, prio, dur, deps)| DagNode {
             id: id.to_string(),
             priority: *prio,
@@ -390,7 +1143,7 @@ mod tests {
             ("c", None, None, vec!["a"]),
             ("d", None, None, vec!["b", "c"]),
         ]);
-        let result = solver.topological_sort(json).unwrap();
+        let result = solver.topological_sort(json, None).unwrap();
         assert!(result.is_valid);
         assert_eq!(result.node_count, 4);
         // "a" must come before "b", "c", "d"
@@ -438,6 +1191,15 @@ mod tests {
         assert!(result.path.contains(&"a".to_string()));
         assert!(result.path.contains(&"c".to_string()));
         assert!(result.path.contains(&"d".to_string()));
+
+        let finish = |id: &str| {
+            let node = result.slack.iter().find(|s| s.id == id).unwrap();
+            (node.earliest_finish, node.latest_finish)
+        };
+        assert_eq!(finish("a"), (3.0, 3.0));
+        assert_eq!(finish("b"), (5.0, 8.0));
+        assert_eq!(finish("c"), (8.0, 8.0));
+        assert_eq!(finish("d"), (9.0, 9.0));
     }
 
     #[test]
@@ -449,10 +1211,296 @@ mod tests {
             ("c", Some(3), None, vec![]),
         ]);
         let completed_json = serde_json::to_string(&Vec::<String>::new()).unwrap();
-        let ready = solver.get_ready_nodes(nodes_json, completed_json).unwrap();
+        let ready = solver.get_ready_nodes(nodes_json, completed_json, None).unwrap();
         // "a" and "c" are ready (no deps), sorted by priority desc: c(3), a(1)
         assert_eq!(ready.len(), 2);
         assert_eq!(ready[0], "c");
         assert_eq!(ready[1], "a");
     }
+
+    #[test]
+    fn test_incremental_graph() {
+        let mut solver = DagSolver::new();
+        for id in ["a", "b", "c"] {
+            solver.add_node(DagNode { id: id.into(), priority: None, estimated_duration: None, depends_on: None });
+        }
+        solver.add_edge("a".into(), "b".into()).unwrap();
+        solver.add_edge("b".into(), "c".into()).unwrap();
+        assert!(solver.add_edge("a".into(), "ghost".into()).is_err());
+
+        let result = solver.graph_topological_sort();
+        assert!(result.is_valid);
+        assert_eq!(result.order, vec!["a", "b", "c"]);
+
+        solver.add_edge("c".into(), "a".into()).unwrap();
+        assert!(solver.graph_detect_cycles().has_cycles);
+        assert!(solver.remove_edge("c".into(), "a".into()));
+        assert!(!solver.remove_edge("c".into(), "a".into()));
+        assert!(!solver.graph_detect_cycles().has_cycles);
+
+        assert!(solver.remove_node("b".into()));
+        assert!(!solver.remove_node("b".into()));
+        let result = solver.graph_topological_sort();
+        assert_eq!(result.node_count, 2);
+        assert_eq!(result.levels.len(), 1);
+    }
+
+    #[test]
+    fn test_graph_exports() {
+        let solver = DagSolver::new();
+        let json = make_nodes_json(&[
+            ("a", Some(2), Some(3.0), vec![]),
+            ("b", None, Some(1.0), vec!["a"]),
+            ("c", None, Some(5.0), vec!["a"]),
+        ]);
+
+        let dot = solver.to_dot(json.clone(), Some(true)).unwrap();
+        assert!(dot.starts_with("digraph dag {"));
+        assert!(dot.contains("\"a\" [label=\"a\\npriority: 2\\nduration: 3s\", style=filled"));
+        assert!(dot.contains("\"b\" [label=\"b\\nduration: 1s\"];"));
+        assert!(dot.contains("\"a\" -> \"c\" [color=red, penwidth=2];"));
+        assert!(dot.contains("\"a\" -> \"b\";"));
+        assert!(dot.trim_end().ends_with('}'));
+
+        let mermaid = solver.to_mermaid(json.clone(), None).unwrap();
+        assert!(mermaid.starts_with("graph TD\n"));
+        assert!(mermaid.contains("n0[\"a<br/>priority: 2<br/>duration: 3s\"]"));
+        assert!(mermaid.contains("n0 --> n1"));
+        assert!(mermaid.contains("n0 --> n2"));
+        assert!(!mermaid.contains("classDef"));
+
+        let mermaid = solver.to_mermaid(json, Some(true)).unwrap();
+        assert!(mermaid.contains("class n0,n2 critical"));
+    }
+
+    #[test]
+    fn test_schedule_with_limited_workers() {
+        let solver = DagSolver::new();
+        let json = make_nodes_json(&[
+            ("a", Some(1), Some(2.0), vec![]),
+            ("b", Some(3), Some(3.0), vec![]),
+            ("c", Some(2), Some(1.0), vec![]),
+            ("d", None, Some(1.0), vec!["a", "b"]),
+        ]);
+
+        let result = solver.schedule(json.clone(), 1).unwrap();
+        let order: Vec<&str> = result.entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(order, vec!["b", "c", "a", "d"]);
+        assert!((result.makespan - 7.0).abs() < 1e-9);
+
+        // Two workers: b and c start at 0, a takes over when c finishes at 1
+        let result = solver.schedule(json.clone(), 2).unwrap();
+        let a = result.entries.iter().find(|e| e.id == "a").unwrap();
+        assert!((a.start - 1.0).abs() < 1e-9);
+        assert!((result.makespan - 4.0).abs() < 1e-9);
+
+        // Unlimited workers match the critical path
+        let critical = solver.critical_path(json.clone()).unwrap();
+        let result = solver.schedule(json.clone(), 10).unwrap();
+        assert!((result.makespan - critical.total_duration).abs() < 1e-9);
+
+        assert!(solver.schedule(json, 0).is_err());
+    }
+
+    #[test]
+    fn test_ancestors_and_descendants() {
+        let solver = DagSolver::new();
+        let json = make_nodes_json(&[
+            ("a", None, None, vec![]),
+            ("b", None, None, vec!["a"]),
+            ("c", None, None, vec!["a"]),
+            ("d", None, None, vec!["b", "c"]),
+            ("e", None, None, vec!["d"]),
+        ]);
+
+        let mut up = solver.ancestors(json.clone(), "e".into()).unwrap();
+        up.sort();
+        assert_eq!(up, vec!["a", "b", "c", "d"]);
+
+        let mut down = solver.descendants(json.clone(), "a".into()).unwrap();
+        down.sort();
+        assert_eq!(down, vec!["b", "c", "d", "e"]);
+
+        assert!(solver.ancestors(json.clone(), "a".into()).unwrap().is_empty());
+        assert!(solver.descendants(json.clone(), "ghost".into()).unwrap().is_empty());
+        assert!(solver.ancestors(json, "ghost".into()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_suggest_cycle_breaks_round_trip() {
+        let solver = DagSolver::new();
+        let json = make_nodes_json(&[
+            ("a", None, None, vec!["c"]),
+            ("b", None, None, vec!["a"]),
+            ("c", None, None, vec!["b"]),
+            ("d", None, None, vec!["e"]),
+            ("e", None, None, vec!["d", "a"]),
+            ("f", None, None, vec!["f"]),
+        ]);
+        let breaks = solver.suggest_cycle_breaks(json.clone()).unwrap();
+        assert_eq!(breaks.len(), 3);
+        assert!(breaks.contains(&DependencyEdge { from: "f".into(), to: "f".into() }));
+
+        let mut nodes: Vec<DagNode> = serde_json::from_str(&json).unwrap();
+        for node in &mut nodes {
+            if let Some(deps) = &mut node.depends_on {
+                deps.retain(|d| !breaks.contains(&DependencyEdge { from: d.clone(), to: node.id.clone() }));
+            }
+        }
+        let fixed = serde_json::to_string(&nodes).unwrap();
+        assert!(!solver.detect_cycles(fixed.clone()).unwrap().has_cycles);
+        assert!(solver.suggest_cycle_breaks(fixed).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate() {
+        let solver = DagSolver::new();
+        let json = make_nodes_json(&[
+            ("a", None, None, vec![]),
+            ("b", None, None, vec!["a", "ghost"]),
+            ("c", None, None, vec!["c"]),
+            ("a", None, None, vec![]),
+        ]);
+        let result = solver.validate(json.clone()).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.duplicate_ids, vec!["a"]);
+        assert_eq!(result.self_dependencies, vec!["c"]);
+        assert_eq!(result.missing_dependencies, vec![DependencyEdge { from: "ghost".into(), to: "b".into() }]);
+        assert_eq!(result.errors.len(), 3);
+        assert!(solver.schedule(json, 2).is_err());
+
+        let json = make_nodes_json(&[("a", None, None, vec![]), ("b", None, None, vec!["a"])]);
+        let result = solver.validate(json).unwrap();
+        assert!(result.is_valid);
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let solver = DagSolver::new();
+        let json = make_nodes_json(&[
+            ("a", None, None, vec!["c"]),
+            ("b", None, None, vec!["a"]),
+            ("c", None, None, vec!["b"]),
+            ("d", None, None, vec!["c", "e"]),
+            ("e", None, None, vec!["d"]),
+            ("f", None, None, vec!["e"]),
+            ("g", None, None, vec!["g"]),
+        ]);
+
+        let mut components = solver.strongly_connected_components(json.clone(), None).unwrap();
+        components.sort();
+        assert_eq!(components, vec![vec!["a", "b", "c"], vec!["d", "e"], vec!["g"]]);
+
+        let all = solver.strongly_connected_components(json, Some(true)).unwrap();
+        assert_eq!(all.len(), 4);
+        assert!(all.contains(&vec!["f".to_string()]));
+    }
+
+    #[test]
+    fn test_transitive_reduction() {
+        let solver = DagSolver::new();
+        let json = make_nodes_json(&[
+            ("a", None, None, vec![]),
+            ("b", None, None, vec!["a"]),
+            ("c", None, None, vec!["a", "b"]),
+            ("d", None, None, vec!["a", "b", "c"]),
+        ]);
+        let reduced: Vec<DagNode> = serde_json::from_str(&solver.transitive_reduction(json).unwrap()).unwrap();
+        let deps: HashMap<String, Vec<String>> = reduced.into_iter()
+            .map(|n| (n.id, n.depends_on.unwrap_or_default()))
+            .collect();
+        assert!(deps["a"].is_empty());
+        assert_eq!(deps["b"], vec!["a"]);
+        assert_eq!(deps["c"], vec!["b"]);
+        assert_eq!(deps["d"], vec!["c"]);
+
+        let cyclic = make_nodes_json(&[("a", None, None, vec!["b"]), ("b", None, None, vec!["a"])]);
+        assert!(solver.transitive_reduction(cyclic).is_err());
+    }
+
+    #[test]
+    fn test_ready_nodes_with_in_progress() {
+        let solver = DagSolver::new();
+        let nodes_json = make_nodes_json(&[
+            ("a", None, None, vec![]),
+            ("b", None, None, vec!["a"]),
+            ("c", None, None, vec![]),
+            ("d", None, None, vec!["c"]),
+        ]);
+        let completed_json = serde_json::to_string(&vec!["a"]).unwrap();
+        let in_progress_json = serde_json::to_string(&vec!["c"]).unwrap();
+        let ready = solver.get_ready_nodes(nodes_json, completed_json, Some(in_progress_json)).unwrap();
+        // "c" is running and "d" waits for it to complete
+        assert_eq!(ready, vec!["b"]);
+    }
+
+    #[test]
+    fn test_longest_path_between() {
+        let solver = DagSolver::new();
+        let json = make_nodes_json(&[
+            ("a", None, Some(1.0), vec![]),
+            ("b", None, Some(2.0), vec!["a"]),
+            ("c", None, Some(5.0), vec!["a"]),
+            ("d", None, Some(1.0), vec!["b", "c"]),
+            ("e", None, Some(4.0), vec![]),
+        ]);
+
+        let result = solver.longest_path_between(json.clone(), "a".into(), "d".into()).unwrap();
+        assert_eq!(result.path, vec!["a", "c", "d"]);
+        assert!((result.total_duration - 7.0).abs() < 1e-9);
+
+        let result = solver.longest_path_between(json.clone(), "b".into(), "d".into()).unwrap();
+        assert_eq!(result.path, vec!["b", "d"]);
+        assert!((result.total_duration - 3.0).abs() < 1e-9);
+
+        let result = solver.longest_path_between(json, "e".into(), "d".into()).unwrap();
+        assert!(result.path.is_empty());
+        assert_eq!(result.total_duration, 0.0);
+    }
+
+    #[test]
+    fn test_topological_sort_stable() {
+        let solver = DagSolver::new();
+        let json = make_nodes_json(&[
+            ("e", None, None, vec![]),
+            ("c", None, None, vec![]),
+            ("a", Some(1), None, vec![]),
+            ("d", None, None, vec!["e", "c"]),
+            ("b", None, None, vec!["c"]),
+            ("f", None, None, vec![]),
+        ]);
+        let first = solver.topological_sort(json.clone(), None).unwrap();
+        assert_eq!(first.order, vec!["a", "c", "e", "f", "b", "d"]);
+        for _ in 0..10 {
+            let again = solver.topological_sort(json.clone(), Some(true)).unwrap();
+            assert_eq!(again.order, first.order);
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let solver = DagSolver::new();
+        let a = make_nodes_json(&[
+            ("build", Some(1), Some(5.0), vec![]),
+            ("test", Some(2), None, vec!["build"]),
+        ]);
+        let b = make_nodes_json(&[
+            ("lint", None, Some(1.0), vec![]),
+            ("test", Some(1), Some(3.0), vec!["build", "lint"]),
+        ]);
+
+        let merged_json = solver.merge(a, b).unwrap();
+        let merged: Vec<DagNode> = serde_json::from_str(&merged_json).unwrap();
+        let ids: Vec<&str> = merged.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["build", "test", "lint"]);
+
+        let test = &merged[1];
+        assert_eq!(test.priority, Some(2));
+        assert_eq!(test.estimated_duration, Some(3.0));
+        assert_eq!(test.depends_on.as_deref(), Some(&["build".to_string(), "lint".to_string()][..]));
+
+        assert!(solver.validate(merged_json.clone()).unwrap().is_valid);
+        assert!(solver.topological_sort(merged_json, None).unwrap().is_valid);
+    }
 }
@@ -13,16 +13,137 @@ export interface TranslationResult {
   alternatives: Array<string>
   /** Explanation of what the command does */
   explanation: string
+  /** Whether the command matches a known risky pattern */
+  dangerous: boolean
+  /** Why the command was flagged as dangerous */
+  dangerReason?: string
+  /**
+   * Whether the command deletes data, rewrites system files, or kills
+   * processes. Every `dangerous` command is also destructive.
+   */
+  isDestructive: boolean
+  /**
+   * Why the command may need confirmation before running; the same as
+   * `danger_reason` for dangerous commands
+   */
+  warning?: string
+  /** Trigger or alias that produced the match (empty when nothing matched) */
+  matchedTrigger: string
+  /** Confidence added because the trigger covers much of the input */
+  coverage: number
+  /** Confidence added because the trigger starts the input */
+  position: number
 }
 /** Create a new translator instance */
 export declare function createTranslator(): LmshTranslator
 /** Natural language to shell translator */
 export declare class LmshTranslator {
   constructor()
-  /** Translate natural language to a shell command */
+  /**
+   * Translate natural language to a shell command
+   *
+   * This is the first of `translate_candidates`; up to three runner-up
+   * commands that differ from the chosen one are listed as alternatives.
+   */
   translate(input: string): TranslationResult
+  /**
+   * Translate, returning the no-match result when the best score is below
+   * `min_confidence` (defaults to 0.7)
+   */
+  translateStrict(input: string, minConfidence?: number | undefined | null): TranslationResult
+  /**
+   * Translate with typo tolerance scaled to each trigger's length
+   *
+   * A trigger matches a run of input words when their similarity
+   * (1 - edits / trigger length) is at least `min_similarity`, and the
+   * confidence drops with each edit. Exact matches always win over fuzzy
+   * ones, triggers shorter than six characters only match exactly, and
+   * patterns that delete, overwrite, or kill are never matched fuzzily.
+   */
+  translateFuzzy(input: string, minSimilarity: number): TranslationResult
+  /**
+   * Explain what a shell command does by matching it against the known
+   * command templates, with placeholders standing for any argument
+   *
+   * A full match scores 1.0, or 0.9 when placeholders were filled in; a
+   * template that matches the start of a longer command scores 0.7.
+   * Otherwise the explanation is pieced together from the program name
+   * and any recognized flags.
+   */
+  explainCommand(command: string): TranslationResult
+  /** Translate many inputs in one call, preserving input order */
+  translateBatch(inputs: Array<string>): Array<TranslationResult>
+  /**
+   * Translate a multi-step request, e.g. "create branch x and then push",
+   * one result per step in order
+   *
+   * Steps are separated by "and then", "then", "after that", "&&", or
+   * ";". A step that matches nothing is kept as a no-match result so the
+   * caller can see the gap.
+   */
+  translatePipeline(input: string): Array<TranslationResult>
+  /**
+   * Translate a multi-step request into one command line joined with `&&`
+   *
+   * Fails with the step's position when any step matches nothing.
+   */
+  translatePipelineJoined(input: string): string
+  /**
+   * Translate to the top `top_n` matching patterns, each as a full result
+   * with its own command, confidence, and explanation, e.g. for a picker UI
+   *
+   * Results are ordered by confidence; ties keep pattern order
+   * (built-in patterns first, then registered ones). `translate` and
+   * `translate_ranked` both pick from these.
+   */
+  translateCandidates(input: string, topN: number): Array<TranslationResult>
+  /** Alias of `translate_candidates` */
+  translateRanked(input: string, topK: number): Array<TranslationResult>
+  /** Register a custom pattern that is matched alongside the built-in ones */
+  registerPattern(triggers: Array<string>, commandTemplate: string, explanation: string, confidence: number): void
+  /**
+   * Alias of `set_shell`, kept for callers that pick a platform
+   * ("unix", "linux", "macos", or "windows") rather than a shell
+   */
+  setPlatform(platform: string): void
+  /**
+   * Set the target shell ("bash", "zsh", "sh", "powershell", "pwsh", or
+   * "cmd"). A platform name selects that platform's default shell: POSIX
+   * for "unix", "linux" and "macos", PowerShell for "windows".
+   *
+   * Defaults to the default shell of the platform the process is running
+   * on. Patterns without a template for the shell fall back to the bash one.
+   */
+  setShell(shell: string): void
+  /** Set the edit distance tolerated for fuzzy trigger matches (0 disables fuzzy matching) */
+  setMaxDistance(maxDistance: number): void
+  /**
+   * Load custom patterns from a JSON array of
+   * `{triggers, command_template, explanation, confidence}` objects.
+   *
+   * Returns the number of patterns loaded. Nothing is appended if any entry is invalid.
+   */
+  loadPatternsFromJson(json: string): number
   /** Add a custom alias */
   addAlias(alias: string, command: string): void
+  /** Remove a custom alias, returning whether it existed */
+  removeAlias(alias: string): boolean
+  /**
+   * Serialize aliases to a JSON object, with keys sorted so the output
+   * is stable across exports
+   */
+  exportAliases(): string
+  /**
+   * Merge aliases from a JSON object, overwriting existing keys
+   *
+   * Returns the number of aliases imported. Keys are lowercased like
+   * `add_alias` does.
+   */
+  importAliases(json: string): number
+  /** Save aliases to a JSON file, creating parent directories as needed */
+  saveAliases(path: string): void
+  /** Load aliases from a JSON file, merging them into the existing ones */
+  loadAliases(path: string): void
   /** Get all aliases */
   getAliases(): Record<string, string>
   /** Translate using aliases first, then patterns */
@@ -5,7 +5,9 @@
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
-use std::collections::HashMap;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
 /// A translation result containing the command and confidence
 #[napi(object)]
@@ -18,21 +20,175 @@ pub struct TranslationResult {
     pub alternatives: Vec<String>,
     /// Explanation of what the command does
     pub explanation: String,
+    /// Whether the command matches a known risky pattern
+    pub dangerous: bool,
+    /// Why the command was flagged as dangerous
+    pub danger_reason: Option<String>,
+    /// Whether the command deletes data, rewrites system files, or kills
+    /// processes. Every `dangerous` command is also destructive.
+    pub is_destructive: bool,
+    /// Why the command may need confirmation before running; the same as
+    /// `danger_reason` for dangerous commands
+    pub warning: Option<String>,
+    /// Trigger or alias that produced the match (empty when nothing matched)
+    pub matched_trigger: String,
+    /// Confidence added because the trigger covers much of the input
+    pub coverage: f64,
+    /// Confidence added because the trigger starts the input
+    pub position: f64,
 }
 
-/// Command pattern for matching
+impl TranslationResult {
+    /// Build a result, flagging the final command if it looks risky
+    fn new(command: String, confidence: f64, explanation: String) -> Self {
+        let danger_reason = detect_danger(&command);
+        let warning = detect_destructive(&command);
+        Self {
+            command,
+            confidence,
+            alternatives: vec![],
+            explanation,
+            dangerous: danger_reason.is_some(),
+            danger_reason,
+            is_destructive: warning.is_some(),
+            warning,
+            matched_trigger: String::new(),
+            coverage: 0.0,
+            position: 0.0,
+        }
+    }
+
+    /// Record which trigger matched and how its score was built
+    fn with_match(mut self, trigger: &str, score: MatchScore) -> Self {
+        self.matched_trigger = trigger.to_string();
+        self.coverage = score.coverage;
+        self.position = score.position;
+        self
+    }
+}
+
+/// Score breakdown for a trigger match
+#[derive(Debug, Clone, Copy)]
+struct MatchScore {
+    confidence: f64,
+    coverage: f64,
+    position: f64,
+}
+
+/// A pattern matched through one of its triggers
+struct PatternMatch<'a> {
+    pattern: &'a Pattern,
+    trigger: &'a str,
+    score: MatchScore,
+}
+
+/// Target shell for generated commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShellTarget {
+    /// bash, zsh, and other POSIX shells
+    Posix,
+    PowerShell,
+    /// Windows Command Prompt
+    Cmd,
+}
+
+impl ShellTarget {
+    /// Default shell for the platform of the running process
+    fn current() -> Self {
+        if std::env::consts::OS == "windows" {
+            ShellTarget::PowerShell
+        } else {
+            ShellTarget::Posix
+        }
+    }
+
+    /// Parse a shell name, or a platform name standing for that platform's
+    /// default shell
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "bash" | "zsh" | "sh" => Some(ShellTarget::Posix),
+            "unix" | "linux" | "macos" | "darwin" => Some(ShellTarget::Posix),
+            "powershell" | "pwsh" => Some(ShellTarget::PowerShell),
+            "windows" | "win32" => Some(ShellTarget::PowerShell),
+            "cmd" => Some(ShellTarget::Cmd),
+            _ => None,
+        }
+    }
+}
+
+/// Command pattern for matching, built in or registered at runtime
+#[derive(Deserialize)]
 struct Pattern {
-    triggers: Vec<&'static str>,
-    command_template: &'static str,
-    explanation: &'static str,
+    triggers: Vec<String>,
+    command_template: String,
+    /// PowerShell equivalent, when the Unix command is not available on Windows
+    #[serde(default)]
+    windows_template: Option<String>,
+    /// Command Prompt equivalent
+    #[serde(default)]
+    cmd_template: Option<String>,
+    explanation: String,
     confidence: f64,
 }
 
+impl Pattern {
+    /// Template for the given shell, falling back to the POSIX one
+    fn template_for(&self, shell: ShellTarget) -> &str {
+        let template = match shell {
+            ShellTarget::PowerShell => self.windows_template.as_deref(),
+            ShellTarget::Cmd => self.cmd_template.as_deref(),
+            ShellTarget::Posix => None,
+        };
+        template.unwrap_or(&self.command_template)
+    }
+
+    /// The POSIX template followed by whichever shell variants exist
+    fn templates(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.command_template.as_str())
+            .chain(self.windows_template.as_deref())
+            .chain(self.cmd_template.as_deref())
+    }
+}
+
+/// Default edit distance tolerated for fuzzy trigger matches
+const DEFAULT_MAX_DISTANCE: u32 = 1;
+
+/// Triggers shorter than this are only matched exactly; longer triggers
+/// tolerate one edit per this many characters, up to `max_distance`
+const MIN_FUZZY_TRIGGER_LEN: usize = 6;
+
+/// Confidence lost per edit in a fuzzy trigger match. Large enough that a
+/// fuzzy match never reaches `DEFAULT_MIN_CONFIDENCE`.
+const FUZZY_PENALTY: f64 = 0.35;
+
+/// Programs that delete, overwrite, or kill, whose patterns are never
+/// matched fuzzily
+const NO_FUZZY_PROGRAMS: &[&str] = &[
+    "rm", "rmdir", "mv", "kill", "chmod", "chown", "del", "rd", "move", "taskkill",
+    "Remove-Item", "Move-Item", "Stop-Process",
+];
+
+/// Minimum confidence accepted by `translate_strict` when none is given
+const DEFAULT_MIN_CONFIDENCE: f64 = 0.7;
+
+/// Line count used by head/tail when the input does not give one
+const DEFAULT_LINE_COUNT: &str = "20";
+
 /// Natural language to shell translator
 #[napi]
 pub struct LmshTranslator {
+    /// Built-in patterns followed by registered ones, in the order that
+    /// breaks confidence ties
     patterns: Vec<Pattern>,
     aliases: HashMap<String, String>,
+    max_distance: u32,
+    shell_target: ShellTarget,
+}
+
+impl Default for LmshTranslator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[napi]
@@ -42,341 +198,495 @@ impl LmshTranslator {
         let patterns = vec![
             // File listing
             Pattern {
-                triggers: vec!["list files", "show files", "what files", "ls", "dir"],
-                command_template: "ls -la",
-                explanation: "List all files in the current directory with details",
+                triggers: to_strings(&["list files", "show files", "what files", "ls", "dir"]),
+                command_template: "ls -la".into(),
+                windows_template: Some("Get-ChildItem -Force".into()),
+                cmd_template: Some("dir /a".into()),
+                explanation: "List all files in the current directory with details".into(),
                 confidence: 0.95,
             },
             Pattern {
-                triggers: vec!["list hidden", "show hidden", "hidden files"],
-                command_template: "ls -la",
-                explanation: "List all files including hidden ones",
+                triggers: to_strings(&["list hidden", "show hidden", "hidden files"]),
+                command_template: "ls -la".into(),
+                windows_template: Some("Get-ChildItem -Force".into()),
+                cmd_template: Some("dir /a".into()),
+                explanation: "List all files including hidden ones".into(),
                 confidence: 0.9,
             },
 
             // Directory navigation
             Pattern {
-                triggers: vec!["go to", "change directory", "cd to", "navigate to"],
-                command_template: "cd {path}",
-                explanation: "Change to the specified directory",
+                triggers: to_strings(&["go to", "change directory", "cd to", "navigate to"]),
+                command_template: "cd {path}".into(),
+                windows_template: Some("Set-Location {path}".into()),
+                cmd_template: Some("cd /d {path}".into()),
+                explanation: "Change to the specified directory".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["go home", "home directory", "go to home"],
-                command_template: "cd ~",
-                explanation: "Change to home directory",
+                triggers: to_strings(&["go home", "home directory", "go to home"]),
+                command_template: "cd ~".into(),
+                windows_template: Some("Set-Location ~".into()),
+                cmd_template: Some("cd /d %USERPROFILE%".into()),
+                explanation: "Change to home directory".into(),
                 confidence: 0.95,
             },
             Pattern {
-                triggers: vec!["go back", "go up", "parent directory", "up one level"],
-                command_template: "cd ..",
-                explanation: "Go to parent directory",
+                triggers: to_strings(&["go back", "go up", "parent directory", "up one level"]),
+                command_template: "cd ..".into(),
+                windows_template: Some("Set-Location ..".into()),
+                cmd_template: Some("cd ..".into()),
+                explanation: "Go to parent directory".into(),
                 confidence: 0.95,
             },
             Pattern {
-                triggers: vec!["current directory", "where am i", "pwd", "print working"],
-                command_template: "pwd",
-                explanation: "Print current working directory",
+                triggers: to_strings(&["current directory", "where am i", "pwd", "print working"]),
+                command_template: "pwd".into(),
+                windows_template: Some("Get-Location".into()),
+                cmd_template: Some("cd".into()),
+                explanation: "Print current working directory".into(),
                 confidence: 0.95,
             },
 
             // File operations
             Pattern {
-                triggers: vec!["create file", "make file", "touch", "new file"],
-                command_template: "touch {filename}",
-                explanation: "Create a new empty file",
+                triggers: to_strings(&["create file", "make file", "touch", "new file"]),
+                command_template: "touch {filename}".into(),
+                windows_template: Some("New-Item -ItemType File {filename}".into()),
+                cmd_template: Some("type nul > {filename}".into()),
+                explanation: "Create a new empty file".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["create directory", "make directory", "mkdir", "new folder", "make folder"],
-                command_template: "mkdir -p {dirname}",
-                explanation: "Create a new directory",
+                triggers: to_strings(&["create directory", "make directory", "mkdir", "new folder", "make folder"]),
+                command_template: "mkdir -p {dirname}".into(),
+                windows_template: Some("New-Item -ItemType Directory -Force {dirname}".into()),
+                cmd_template: Some("mkdir {dirname}".into()),
+                explanation: "Create a new directory".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["remove file", "delete file", "rm file"],
-                command_template: "rm {filename}",
-                explanation: "Remove a file",
+                triggers: to_strings(&["remove file", "delete file", "rm file"]),
+                command_template: "rm {filename}".into(),
+                windows_template: Some("Remove-Item {filename}".into()),
+                cmd_template: Some("del {filename}".into()),
+                explanation: "Remove a file".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["remove directory", "delete directory", "delete folder", "rmdir"],
-                command_template: "rm -r {dirname}",
-                explanation: "Remove a directory and its contents",
+                triggers: to_strings(&["remove directory", "delete directory", "delete folder", "rmdir"]),
+                command_template: "rm -r {dirname}".into(),
+                windows_template: Some("Remove-Item -Recurse {dirname}".into()),
+                cmd_template: Some("rmdir /s /q {dirname}".into()),
+                explanation: "Remove a directory and its contents".into(),
                 confidence: 0.8,
             },
             Pattern {
-                triggers: vec!["copy file", "copy to", "cp"],
-                command_template: "cp {source} {dest}",
-                explanation: "Copy a file",
+                triggers: to_strings(&["copy file", "copy to", "copy", "cp"]),
+                command_template: "cp {source} {dest}".into(),
+                windows_template: Some("Copy-Item {source} {dest}".into()),
+                cmd_template: Some("copy {source} {dest}".into()),
+                explanation: "Copy a file".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["move file", "rename file", "mv"],
-                command_template: "mv {source} {dest}",
-                explanation: "Move or rename a file",
+                triggers: to_strings(&["move file", "rename file", "move", "mv"]),
+                command_template: "mv {source} {dest}".into(),
+                windows_template: Some("Move-Item {source} {dest}".into()),
+                cmd_template: Some("move {source} {dest}".into()),
+                explanation: "Move or rename a file".into(),
                 confidence: 0.85,
             },
 
             // File viewing
             Pattern {
-                triggers: vec!["show file", "view file", "cat", "display file", "read file"],
-                command_template: "cat {filename}",
-                explanation: "Display file contents",
+                triggers: to_strings(&["show file", "view file", "cat", "display file", "read file"]),
+                command_template: "cat {filename}".into(),
+                windows_template: Some("Get-Content {filename}".into()),
+                cmd_template: Some("type {filename}".into()),
+                explanation: "Display file contents".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["head of file", "first lines", "head"],
-                command_template: "head -n 20 {filename}",
-                explanation: "Show first 20 lines of a file",
+                triggers: to_strings(&["head of file", "first lines", "head"]),
+                command_template: "head -n {n} {filename}".into(),
+                windows_template: Some("Get-Content {filename} -TotalCount {n}".into()),
+                cmd_template: None,
+                explanation: "Show the first lines of a file".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["tail of file", "last lines", "tail", "end of file"],
-                command_template: "tail -n 20 {filename}",
-                explanation: "Show last 20 lines of a file",
+                triggers: to_strings(&["tail of file", "last lines", "tail", "end of file"]),
+                command_template: "tail -n {n} {filename}".into(),
+                windows_template: Some("Get-Content {filename} -Tail {n}".into()),
+                cmd_template: None,
+                explanation: "Show the last lines of a file".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["less", "page through", "scroll through"],
-                command_template: "less {filename}",
-                explanation: "View file with pagination",
+                triggers: to_strings(&["less", "page through", "scroll through"]),
+                command_template: "less {filename}".into(),
+                windows_template: Some("Get-Content {filename} | Out-Host -Paging".into()),
+                cmd_template: Some("more {filename}".into()),
+                explanation: "View file with pagination".into(),
                 confidence: 0.85,
             },
 
             // Searching
             Pattern {
-                triggers: vec!["find file", "search for file", "locate file"],
-                command_template: "find . -name '{pattern}'",
-                explanation: "Find files matching a pattern",
+                triggers: to_strings(&["find file", "search for file", "locate file"]),
+                command_template: "find . -name {pattern}".into(),
+                windows_template: Some("Get-ChildItem -Recurse -Filter {pattern}".into()),
+                cmd_template: Some("dir /s /b {pattern}".into()),
+                explanation: "Find files matching a pattern".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["search in files", "grep", "find text", "search for text", "search for"],
-                command_template: "grep -r '{pattern}' .",
-                explanation: "Search for text in files recursively",
+                triggers: to_strings(&["search in files", "grep", "find text", "search for text", "search for"]),
+                command_template: "grep -r {pattern} .".into(),
+                windows_template: Some("Get-ChildItem -Recurse | Select-String -Pattern {pattern}".into()),
+                cmd_template: Some("findstr /s {pattern} *".into()),
+                explanation: "Search for text in files recursively".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["search in file", "grep in"],
-                command_template: "grep '{pattern}' {filename}",
-                explanation: "Search for text in a specific file",
+                triggers: to_strings(&["search in file", "grep in"]),
+                command_template: "grep {pattern} {filename}".into(),
+                windows_template: Some("Select-String -Pattern {pattern} -Path {filename}".into()),
+                cmd_template: Some("findstr {pattern} {filename}".into()),
+                explanation: "Search for text in a specific file".into(),
                 confidence: 0.85,
             },
 
             // Git commands
             Pattern {
-                triggers: vec!["git status", "check git", "what changed"],
-                command_template: "git status",
-                explanation: "Show git repository status",
+                triggers: to_strings(&["git status", "check git", "what changed"]),
+                command_template: "git status".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Show git repository status".into(),
                 confidence: 0.95,
             },
             Pattern {
-                triggers: vec!["git log", "commit history", "show commits", "git history"],
-                command_template: "git log --oneline -20",
-                explanation: "Show recent commit history",
+                triggers: to_strings(&["git log", "commit history", "show commits", "git history"]),
+                command_template: "git log --oneline -20".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Show recent commit history".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["git diff", "show changes", "what's different"],
-                command_template: "git diff",
-                explanation: "Show uncommitted changes",
+                triggers: to_strings(&["git diff", "show changes", "what's different"]),
+                command_template: "git diff".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Show uncommitted changes".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["git add", "stage files", "add to staging"],
-                command_template: "git add {files}",
-                explanation: "Stage files for commit",
+                triggers: to_strings(&["git add", "stage files", "add to staging"]),
+                command_template: "git add {files}".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Stage files for commit".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["git commit", "commit changes", "save changes"],
-                command_template: "git commit -m '{message}'",
-                explanation: "Commit staged changes",
+                triggers: to_strings(&["git commit", "commit changes", "save changes"]),
+                command_template: "git commit -m {message}".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Commit staged changes".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["git push", "push changes", "upload commits"],
-                command_template: "git push",
-                explanation: "Push commits to remote",
+                triggers: to_strings(&["git push", "push changes", "upload commits"]),
+                command_template: "git push".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Push commits to remote".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["git pull", "pull changes", "get latest", "download commits"],
-                command_template: "git pull",
-                explanation: "Pull latest changes from remote",
+                triggers: to_strings(&["git pull", "pull changes", "get latest", "download commits"]),
+                command_template: "git pull".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Pull latest changes from remote".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["git branch", "list branches", "show branches"],
-                command_template: "git branch -a",
-                explanation: "List all branches",
+                triggers: to_strings(&["git branch", "list branches", "show branches"]),
+                command_template: "git branch -a".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "List all branches".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["checkout branch", "switch branch", "git checkout"],
-                command_template: "git checkout {branch}",
-                explanation: "Switch to a branch",
+                triggers: to_strings(&["checkout branch", "switch branch", "git checkout"]),
+                command_template: "git checkout {branch}".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Switch to a branch".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["create branch", "new branch", "git branch create"],
-                command_template: "git checkout -b {branch}",
-                explanation: "Create and switch to a new branch",
+                triggers: to_strings(&["create branch", "new branch", "git branch create"]),
+                command_template: "git checkout -b {branch}".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Create and switch to a new branch".into(),
                 confidence: 0.9,
             },
 
             // Process management
             Pattern {
-                triggers: vec!["running processes", "show processes", "ps", "what's running"],
-                command_template: "ps aux",
-                explanation: "Show all running processes",
+                triggers: to_strings(&["processes using port", "using port", "listening on port", "what's on port"]),
+                command_template: "lsof -i :{port}".into(),
+                windows_template: Some("Get-NetTCPConnection -LocalPort {port}".into()),
+                cmd_template: Some("netstat -ano | findstr :{port}".into()),
+                explanation: "Show processes using a network port".into(),
+                confidence: 0.9,
+            },
+            Pattern {
+                triggers: to_strings(&["running processes", "show processes", "ps", "what's running"]),
+                command_template: "ps aux".into(),
+                windows_template: Some("Get-Process".into()),
+                cmd_template: Some("tasklist".into()),
+                explanation: "Show all running processes".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["kill process", "stop process", "terminate"],
-                command_template: "kill {pid}",
-                explanation: "Terminate a process by PID",
+                triggers: to_strings(&["kill process", "stop process", "terminate"]),
+                command_template: "kill {pid}".into(),
+                windows_template: Some("Stop-Process -Id {pid}".into()),
+                cmd_template: Some("taskkill /PID {pid}".into()),
+                explanation: "Terminate a process by PID".into(),
                 confidence: 0.8,
             },
             Pattern {
-                triggers: vec!["top", "system monitor", "resource usage"],
-                command_template: "top",
-                explanation: "Show system resource usage",
+                triggers: to_strings(&["top", "system monitor", "resource usage"]),
+                command_template: "top".into(),
+                windows_template: Some("Get-Process | Sort-Object CPU -Descending | Select-Object -First 20".into()),
+                cmd_template: None,
+                explanation: "Show system resource usage".into(),
                 confidence: 0.9,
             },
 
             // Disk usage
             Pattern {
-                triggers: vec!["disk space", "disk usage", "df", "free space"],
-                command_template: "df -h",
-                explanation: "Show disk space usage",
+                triggers: to_strings(&["disk space", "disk usage", "df", "free space"]),
+                command_template: "df -h".into(),
+                windows_template: Some("Get-PSDrive -PSProvider FileSystem".into()),
+                cmd_template: None,
+                explanation: "Show disk space usage".into(),
                 confidence: 0.95,
             },
             Pattern {
-                triggers: vec!["directory size", "folder size", "du", "how big"],
-                command_template: "du -sh {path}",
-                explanation: "Show directory size",
+                triggers: to_strings(&["directory size", "folder size", "du", "how big"]),
+                command_template: "du -sh {path}".into(),
+                windows_template: Some("Get-ChildItem {path} -Recurse | Measure-Object -Property Length -Sum".into()),
+                cmd_template: None,
+                explanation: "Show directory size".into(),
                 confidence: 0.85,
             },
 
             // Network
             Pattern {
-                triggers: vec!["check internet", "ping", "test connection"],
-                command_template: "ping -c 4 google.com",
-                explanation: "Test internet connectivity",
+                triggers: to_strings(&["check internet", "ping", "test connection"]),
+                command_template: "ping -c 4 google.com".into(),
+                windows_template: Some("Test-Connection google.com -Count 4".into()),
+                cmd_template: Some("ping -n 4 google.com".into()),
+                explanation: "Test internet connectivity".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["download", "curl", "fetch url", "wget"],
-                command_template: "curl -O {url}",
-                explanation: "Download a file from URL",
+                triggers: to_strings(&["download", "curl", "fetch url", "wget"]),
+                command_template: "curl -O {url}".into(),
+                windows_template: Some("Invoke-WebRequest -Uri {url} -OutFile (Split-Path -Leaf {url})".into()),
+                cmd_template: None,
+                explanation: "Download a file from URL".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["my ip", "ip address", "network info"],
-                command_template: "ifconfig || ip addr",
-                explanation: "Show network interface information",
+                triggers: to_strings(&["my ip", "ip address", "network info"]),
+                command_template: "ifconfig || ip addr".into(),
+                windows_template: Some("Get-NetIPAddress".into()),
+                cmd_template: Some("ipconfig".into()),
+                explanation: "Show network interface information".into(),
                 confidence: 0.85,
             },
 
             // Permissions
             Pattern {
-                triggers: vec!["make executable", "chmod +x", "add execute permission"],
-                command_template: "chmod +x {filename}",
-                explanation: "Make a file executable",
+                triggers: to_strings(&["make executable", "chmod +x", "add execute permission"]),
+                command_template: "chmod +x {filename}".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Make a file executable".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["change permissions", "chmod"],
-                command_template: "chmod {mode} {filename}",
-                explanation: "Change file permissions",
+                triggers: to_strings(&["change permissions", "chmod"]),
+                command_template: "chmod {mode} {filename}".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Change file permissions".into(),
                 confidence: 0.8,
             },
             Pattern {
-                triggers: vec!["change owner", "chown"],
-                command_template: "chown {owner} {filename}",
-                explanation: "Change file ownership",
+                triggers: to_strings(&["change owner", "chown"]),
+                command_template: "chown {owner} {filename}".into(),
+                windows_template: Some("icacls {filename} /setowner {owner}".into()),
+                cmd_template: None,
+                explanation: "Change file ownership".into(),
                 confidence: 0.8,
             },
 
             // Compression
             Pattern {
-                triggers: vec!["compress", "create tar", "tar", "archive"],
-                command_template: "tar -czvf {archive}.tar.gz {source}",
-                explanation: "Create a compressed archive",
+                triggers: to_strings(&["compress", "create tar", "tar", "archive"]),
+                command_template: "tar -czvf {archive}.tar.gz {source}".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Create a compressed archive".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["extract", "untar", "decompress", "unzip tar"],
-                command_template: "tar -xzvf {archive}",
-                explanation: "Extract a compressed archive",
+                triggers: to_strings(&["extract", "untar", "decompress", "unzip tar"]),
+                command_template: "tar -xzvf {archive}".into(),
+                windows_template: None,
+                cmd_template: None,
+                explanation: "Extract a compressed archive".into(),
                 confidence: 0.85,
             },
             Pattern {
-                triggers: vec!["unzip", "extract zip"],
-                command_template: "unzip {archive}",
-                explanation: "Extract a zip archive",
+                triggers: to_strings(&["unzip", "extract zip"]),
+                command_template: "unzip {archive}".into(),
+                windows_template: Some("Expand-Archive {archive}".into()),
+                cmd_template: None,
+                explanation: "Extract a zip archive".into(),
                 confidence: 0.9,
             },
 
             // System info
             Pattern {
-                triggers: vec!["system info", "os info", "uname"],
-                command_template: "uname -a",
-                explanation: "Show system information",
+                triggers: to_strings(&["system info", "os info", "uname"]),
+                command_template: "uname -a".into(),
+                windows_template: Some("Get-ComputerInfo".into()),
+                cmd_template: Some("systeminfo".into()),
+                explanation: "Show system information".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["date", "current time", "what time"],
-                command_template: "date",
-                explanation: "Show current date and time",
+                triggers: to_strings(&["date", "current time", "what time"]),
+                command_template: "date".into(),
+                windows_template: Some("Get-Date".into()),
+                cmd_template: Some("echo %DATE% %TIME%".into()),
+                explanation: "Show current date and time".into(),
                 confidence: 0.95,
             },
             Pattern {
-                triggers: vec!["uptime", "how long running", "system uptime"],
-                command_template: "uptime",
-                explanation: "Show system uptime",
+                triggers: to_strings(&["uptime", "how long running", "system uptime"]),
+                command_template: "uptime".into(),
+                windows_template: Some("(Get-Date) - (Get-CimInstance Win32_OperatingSystem).LastBootUpTime".into()),
+                cmd_template: None,
+                explanation: "Show system uptime".into(),
                 confidence: 0.95,
             },
             Pattern {
-                triggers: vec!["memory usage", "free memory", "ram"],
-                command_template: "free -h",
-                explanation: "Show memory usage",
+                triggers: to_strings(&["memory usage", "free memory", "ram"]),
+                command_template: "free -h".into(),
+                windows_template: Some("Get-CimInstance Win32_OperatingSystem | Select-Object FreePhysicalMemory, TotalVisibleMemorySize".into()),
+                cmd_template: None,
+                explanation: "Show memory usage".into(),
                 confidence: 0.9,
             },
 
             // Environment
             Pattern {
-                triggers: vec!["environment variables", "env", "show env"],
-                command_template: "env",
-                explanation: "Show environment variables",
+                triggers: to_strings(&["environment variables", "env", "show env"]),
+                command_template: "env".into(),
+                windows_template: Some("Get-ChildItem Env:".into()),
+                cmd_template: Some("set".into()),
+                explanation: "Show environment variables".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["set variable", "export"],
-                command_template: "export {var}={value}",
-                explanation: "Set an environment variable",
+                triggers: to_strings(&["set variable", "export"]),
+                command_template: "export {var}={value}".into(),
+                windows_template: Some("$env:{var} = {value}".into()),
+                cmd_template: Some("set {var}={value}".into()),
+                explanation: "Set an environment variable".into(),
                 confidence: 0.8,
             },
             Pattern {
-                triggers: vec!["echo", "print", "show variable"],
-                command_template: "echo ${var}",
-                explanation: "Print a variable or text",
+                triggers: to_strings(&["echo", "print", "show variable"]),
+                command_template: "echo ${var}".into(),
+                windows_template: Some("Write-Output $env:{var}".into()),
+                cmd_template: Some("echo %{var}%".into()),
+                explanation: "Print a variable or text".into(),
+                confidence: 0.85,
+            },
+
+            // Pipelines
+            Pattern {
+                triggers: to_strings(&["count lines", "line count", "how many lines"]),
+                command_template: "wc -l {filename}".into(),
+                windows_template: Some("Get-Content {filename} | Measure-Object -Line".into()),
+                cmd_template: None,
+                explanation: "Count the lines in a file".into(),
+                confidence: 0.9,
+            },
+            Pattern {
+                triggers: to_strings(&["count files", "how many files", "number of files"]),
+                command_template: "ls | wc -l".into(),
+                windows_template: Some("Get-ChildItem | Measure-Object".into()),
+                cmd_template: None,
+                explanation: "Count the entries in the current directory".into(),
+                confidence: 0.9,
+            },
+            Pattern {
+                triggers: to_strings(&["disk hogs", "biggest folders", "largest directories", "what's taking space"]),
+                command_template: "du -sh * | sort -h".into(),
+                windows_template: Some("Get-ChildItem | Sort-Object Length".into()),
+                cmd_template: None,
+                explanation: "List directory sizes, largest last".into(),
+                confidence: 0.9,
+            },
+            Pattern {
+                triggers: to_strings(&["largest files", "biggest files"]),
+                command_template: "du -ah . | sort -rh | head -n {n}".into(),
+                windows_template: Some("Get-ChildItem -Recurse -File | Sort-Object Length -Descending | Select-Object -First {n}".into()),
+                cmd_template: None,
+                explanation: "List the largest files under the current directory".into(),
+                confidence: 0.9,
+            },
+            Pattern {
+                triggers: to_strings(&["count matches", "count occurrences", "how many times"]),
+                command_template: "grep -o {pattern} {filename} | wc -l".into(),
+                windows_template: Some("(Select-String -Pattern {pattern} -Path {filename} -AllMatches).Matches.Count".into()),
+                cmd_template: None,
+                explanation: "Count occurrences of a pattern in a file".into(),
                 confidence: 0.85,
             },
 
             // History
             Pattern {
-                triggers: vec!["command history", "history", "previous commands"],
-                command_template: "history | tail -50",
-                explanation: "Show recent command history",
+                triggers: to_strings(&["command history", "history", "previous commands"]),
+                command_template: "history | tail -50".into(),
+                windows_template: Some("Get-History -Count 50".into()),
+                cmd_template: None,
+                explanation: "Show recent command history".into(),
                 confidence: 0.9,
             },
             Pattern {
-                triggers: vec!["clear screen", "clear", "cls"],
-                command_template: "clear",
-                explanation: "Clear the terminal screen",
+                triggers: to_strings(&["clear screen", "clear", "cls"]),
+                command_template: "clear".into(),
+                windows_template: Some("Clear-Host".into()),
+                cmd_template: Some("cls".into()),
+                explanation: "Clear the terminal screen".into(),
                 confidence: 0.95,
             },
         ];
@@ -384,62 +694,309 @@ impl LmshTranslator {
         Self {
             patterns,
             aliases: HashMap::new(),
+            max_distance: DEFAULT_MAX_DISTANCE,
+            shell_target: ShellTarget::current(),
         }
     }
 
     /// Translate natural language to a shell command
+    ///
+    /// This is the first of `translate_candidates`; up to three runner-up
+    /// commands that differ from the chosen one are listed as alternatives.
     #[napi]
     pub fn translate(&self, input: String) -> TranslationResult {
-        let input_lower = input.to_lowercase();
-        let mut best_match: Option<(&Pattern, f64)> = None;
-        let mut alternatives: Vec<String> = Vec::new();
+        let mut candidates = self.translate_candidates(input, u32::MAX).into_iter();
+        let Some(mut best) = candidates.next() else {
+            return no_match();
+        };
+
+        for candidate in candidates {
+            if best.alternatives.len() == 3 {
+                break;
+            }
+            let seen = candidate.command == best.command
+                || best.alternatives.contains(&candidate.command);
+            if !seen {
+                best.alternatives.push(candidate.command);
+            }
+        }
+        best
+    }
 
-        // Find the best matching pattern
+    /// Translate, returning the no-match result when the best score is below
+    /// `min_confidence` (defaults to 0.7)
+    #[napi]
+    pub fn translate_strict(&self, input: String, min_confidence: Option<f64>) -> TranslationResult {
+        let result = self.translate(input);
+        if result.confidence < min_confidence.unwrap_or(DEFAULT_MIN_CONFIDENCE) {
+            return no_match();
+        }
+        result
+    }
+
+    /// Translate with typo tolerance scaled to each trigger's length
+    ///
+    /// A trigger matches a run of input words when their similarity
+    /// (1 - edits / trigger length) is at least `min_similarity`, and the
+    /// confidence drops with each edit. Exact matches always win over fuzzy
+    /// ones, triggers shorter than six characters only match exactly, and
+    /// patterns that delete, overwrite, or kill are never matched fuzzily.
+    #[napi]
+    pub fn translate_fuzzy(&self, input: String, min_similarity: f64) -> TranslationResult {
+        let input_lower = normalize_input(&input);
+        let mut matches = self.exact_matches(&input_lower);
+        if matches.is_empty() {
+            matches = self.fuzzy_matches_by(&input_lower, |distance, trigger| {
+                let similarity = 1.0 - distance as f64 / trigger.chars().count() as f64;
+                similarity >= min_similarity
+            });
+        }
+
+        match matches.first() {
+            Some(m) => self.build_result(m, &input),
+            None => no_match(),
+        }
+    }
+
+    /// Explain what a shell command does by matching it against the known
+    /// command templates, with placeholders standing for any argument
+    ///
+    /// A full match scores 1.0, or 0.9 when placeholders were filled in; a
+    /// template that matches the start of a longer command scores 0.7.
+    /// Otherwise the explanation is pieced together from the program name
+    /// and any recognized flags.
+    #[napi]
+    pub fn explain_command(&self, command: String) -> TranslationResult {
+        let trimmed = command.trim();
+        let tokens = raw_args(trimmed.strip_prefix("sudo ").unwrap_or(trimmed));
+
+        let mut best: Option<(&Pattern, f64)> = None;
         for pattern in &self.patterns {
-            for trigger in &pattern.triggers {
-                if input_lower.contains(trigger) {
-                    let score = calculate_match_score(&input_lower, trigger, pattern.confidence);
-                    match &best_match {
-                        None => best_match = Some((pattern, score)),
-                        Some((_, best_score)) if score > *best_score => {
-                            if let Some((old_pattern, _)) = best_match {
-                                alternatives.push(old_pattern.command_template.to_string());
-                            }
-                            best_match = Some((pattern, score));
-                        }
-                        Some(_) => {
-                            alternatives.push(pattern.command_template.to_string());
-                        }
-                    }
-                }
+            let score = pattern
+                .templates()
+                .filter_map(|template| template_match_score(template, &tokens))
+                .fold(0.0, f64::max);
+            if score > best.map_or(0.0, |(_, s)| s) {
+                best = Some((pattern, score));
             }
         }
 
-        match best_match {
+        match best {
             Some((pattern, score)) => {
-                let command = self.substitute_placeholders(pattern.command_template, &input);
-                TranslationResult {
-                    command,
-                    confidence: score,
-                    alternatives: alternatives.into_iter().take(3).collect(),
-                    explanation: pattern.explanation.to_string(),
-                }
+                TranslationResult::new(command, score, pattern.explanation.clone())
+            }
+            None => {
+                let (explanation, confidence) = explain_from_flags(trimmed);
+                TranslationResult::new(command, confidence, explanation)
             }
-            None => TranslationResult {
-                command: String::new(),
-                confidence: 0.0,
-                alternatives: vec![],
-                explanation: "No matching command pattern found".to_string(),
-            },
         }
     }
 
+    /// Translate many inputs in one call, preserving input order
+    #[napi]
+    pub fn translate_batch(&self, inputs: Vec<String>) -> Vec<TranslationResult> {
+        inputs.into_iter().map(|input| self.translate(input)).collect()
+    }
+
+    /// Translate a multi-step request, e.g. "create branch x and then push",
+    /// one result per step in order
+    ///
+    /// Steps are separated by "and then", "then", "after that", "&&", or
+    /// ";". A step that matches nothing is kept as a no-match result so the
+    /// caller can see the gap.
+    #[napi]
+    pub fn translate_pipeline(&self, input: String) -> Vec<TranslationResult> {
+        split_steps(&input).into_iter().map(|step| self.translate(step)).collect()
+    }
+
+    /// Translate a multi-step request into one command line joined with `&&`
+    ///
+    /// Fails with the step's position when any step matches nothing.
+    #[napi]
+    pub fn translate_pipeline_joined(&self, input: String) -> Result<String> {
+        let steps = split_steps(&input);
+        let mut commands = Vec::with_capacity(steps.len());
+        for (i, step) in steps.into_iter().enumerate() {
+            let result = self.translate(step.clone());
+            if result.command.is_empty() {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Step {} could not be translated: {}", i, step),
+                ));
+            }
+            commands.push(result.command);
+        }
+        Ok(commands.join(" && "))
+    }
+
+    /// Translate to the top `top_n` matching patterns, each as a full result
+    /// with its own command, confidence, and explanation, e.g. for a picker UI
+    ///
+    /// Results are ordered by confidence; ties keep pattern order
+    /// (built-in patterns first, then registered ones). `translate` and
+    /// `translate_ranked` both pick from these.
+    #[napi]
+    pub fn translate_candidates(&self, input: String, top_n: u32) -> Vec<TranslationResult> {
+        self.ranked_matches(&normalize_input(&input))
+            .into_iter()
+            .take(top_n as usize)
+            .map(|m| self.build_result(&m, &input))
+            .collect()
+    }
+
+    /// Alias of `translate_candidates`
+    #[napi]
+    pub fn translate_ranked(&self, input: String, top_k: u32) -> Vec<TranslationResult> {
+        self.translate_candidates(input, top_k)
+    }
+
+    /// Register a custom pattern that is matched alongside the built-in ones
+    #[napi]
+    pub fn register_pattern(
+        &mut self,
+        triggers: Vec<String>,
+        command_template: String,
+        explanation: String,
+        confidence: f64,
+    ) {
+        self.patterns.push(Pattern {
+            triggers: triggers.iter().map(|t| t.to_lowercase()).collect(),
+            command_template,
+            windows_template: None,
+            cmd_template: None,
+            explanation,
+            confidence,
+        });
+    }
+
+    /// Alias of `set_shell`, kept for callers that pick a platform
+    /// ("unix", "linux", "macos", or "windows") rather than a shell
+    #[napi]
+    pub fn set_platform(&mut self, platform: String) -> Result<()> {
+        self.set_shell(platform)
+    }
+
+    /// Set the target shell ("bash", "zsh", "sh", "powershell", "pwsh", or
+    /// "cmd"). A platform name selects that platform's default shell: POSIX
+    /// for "unix", "linux" and "macos", PowerShell for "windows".
+    ///
+    /// Defaults to the default shell of the platform the process is running
+    /// on. Patterns without a template for the shell fall back to the bash one.
+    #[napi]
+    pub fn set_shell(&mut self, shell: String) -> Result<()> {
+        self.shell_target = ShellTarget::parse(&shell)
+            .ok_or_else(|| Error::new(Status::InvalidArg, format!("Unknown shell: {}", shell)))?;
+        Ok(())
+    }
+
+    /// Set the edit distance tolerated for fuzzy trigger matches (0 disables fuzzy matching)
+    #[napi]
+    pub fn set_max_distance(&mut self, max_distance: u32) {
+        self.max_distance = max_distance;
+    }
+
+    /// Load custom patterns from a JSON array of
+    /// `{triggers, command_template, explanation, confidence}` objects.
+    ///
+    /// Returns the number of patterns loaded. Nothing is appended if any entry is invalid.
+    #[napi]
+    pub fn load_patterns_from_json(&mut self, json: String) -> Result<u32> {
+        let mut loaded: Vec<Pattern> = serde_json::from_str(&json).map_err(|e| {
+            Error::new(Status::InvalidArg, format!("Invalid pattern JSON: {}", e))
+        })?;
+
+        for (i, pattern) in loaded.iter_mut().enumerate() {
+            if pattern.triggers.iter().all(|t| t.trim().is_empty()) {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Pattern {} has no triggers", i),
+                ));
+            }
+            if pattern.command_template.trim().is_empty() {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Pattern {} has an empty command template", i),
+                ));
+            }
+            if !(0.0..=1.0).contains(&pattern.confidence) {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Pattern {} has confidence outside 0.0-1.0", i),
+                ));
+            }
+            pattern.triggers.retain(|t| !t.trim().is_empty());
+            for trigger in pattern.triggers.iter_mut() {
+                *trigger = trigger.to_lowercase();
+            }
+        }
+
+        let count = loaded.len() as u32;
+        self.patterns.extend(loaded);
+        Ok(count)
+    }
+
     /// Add a custom alias
     #[napi]
     pub fn add_alias(&mut self, alias: String, command: String) {
         self.aliases.insert(alias.to_lowercase(), command);
     }
 
+    /// Remove a custom alias, returning whether it existed
+    #[napi]
+    pub fn remove_alias(&mut self, alias: String) -> bool {
+        self.aliases.remove(&alias.to_lowercase()).is_some()
+    }
+
+    /// Serialize aliases to a JSON object, with keys sorted so the output
+    /// is stable across exports
+    #[napi]
+    pub fn export_aliases(&self) -> String {
+        let sorted: BTreeMap<&String, &String> = self.aliases.iter().collect();
+        serde_json::to_string_pretty(&sorted).expect("string map always serializes")
+    }
+
+    /// Merge aliases from a JSON object, overwriting existing keys
+    ///
+    /// Returns the number of aliases imported. Keys are lowercased like
+    /// `add_alias` does.
+    #[napi]
+    pub fn import_aliases(&mut self, json: String) -> Result<u32> {
+        let loaded: HashMap<String, String> = serde_json::from_str(&json).map_err(|e| {
+            Error::new(Status::InvalidArg, format!("Invalid alias JSON: {}", e))
+        })?;
+
+        let count = loaded.len() as u32;
+        for (alias, command) in loaded {
+            self.add_alias(alias, command);
+        }
+        Ok(count)
+    }
+
+    /// Save aliases to a JSON file, creating parent directories as needed
+    #[napi]
+    pub fn save_aliases(&self, path: String) -> Result<()> {
+        let path = Path::new(&path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                Error::new(Status::GenericFailure, format!("Failed to create alias directory: {}", e))
+            })?;
+        }
+
+        std::fs::write(path, self.export_aliases()).map_err(|e| {
+            Error::new(Status::GenericFailure, format!("Failed to write aliases: {}", e))
+        })
+    }
+
+    /// Load aliases from a JSON file, merging them into the existing ones
+    #[napi]
+    pub fn load_aliases(&mut self, path: String) -> Result<()> {
+        let json = std::fs::read_to_string(&path).map_err(|e| {
+            Error::new(Status::GenericFailure, format!("Failed to read aliases: {}", e))
+        })?;
+        self.import_aliases(json)?;
+        Ok(())
+    }
+
     /// Get all aliases
     #[napi]
     pub fn get_aliases(&self) -> HashMap<String, String> {
@@ -453,13 +1010,14 @@ impl LmshTranslator {
 
         // Check aliases first
         for (alias, command) in &self.aliases {
-            if input_lower.contains(alias) {
-                return TranslationResult {
-                    command: command.clone(),
-                    confidence: 1.0,
-                    alternatives: vec![],
-                    explanation: format!("Custom alias for '{}'", alias),
-                };
+            if contains_phrase(&input_lower, alias) {
+                let score = calculate_match_score(&input_lower, alias, 1.0);
+                return TranslationResult::new(
+                    command.clone(),
+                    1.0,
+                    format!("Custom alias for '{}'", alias),
+                )
+                .with_match(alias, score);
             }
         }
 
@@ -467,59 +1025,175 @@ impl LmshTranslator {
         self.translate(input)
     }
 
+    /// Best-scoring trigger per matching pattern, highest first, falling
+    /// back to fuzzy matches when no trigger appears exactly
+    fn ranked_matches(&self, input_lower: &str) -> Vec<PatternMatch<'_>> {
+        let matches = self.exact_matches(input_lower);
+        if matches.is_empty() {
+            return self.fuzzy_matches(input_lower);
+        }
+        matches
+    }
+
+    /// Best-scoring exact trigger per matching pattern, highest first
+    fn exact_matches(&self, input_lower: &str) -> Vec<PatternMatch<'_>> {
+        let mut matches: Vec<PatternMatch> = self
+            .patterns
+            .iter()
+            .filter_map(|pattern| {
+                pattern
+                    .triggers
+                    .iter()
+                    .filter(|trigger| contains_phrase(input_lower, trigger))
+                    .map(|trigger| PatternMatch {
+                        pattern,
+                        trigger,
+                        score: calculate_match_score(input_lower, trigger, pattern.confidence),
+                    })
+                    .reduce(best_of)
+            })
+            .collect();
+
+        // Stable sort keeps pattern order for equal scores
+        matches.sort_by(|a, b| b.score.confidence.total_cmp(&a.score.confidence));
+        matches
+    }
+
+    /// Patterns whose triggers are within a few edits of a run of input
+    /// words: one edit per `MIN_FUZZY_TRIGGER_LEN` characters of the
+    /// trigger, up to `max_distance`
+    fn fuzzy_matches(&self, input_lower: &str) -> Vec<PatternMatch<'_>> {
+        if self.max_distance == 0 {
+            return vec![];
+        }
+        self.fuzzy_matches_by(input_lower, |distance, trigger| {
+            let budget = trigger.chars().count() / MIN_FUZZY_TRIGGER_LEN;
+            distance <= budget.min(self.max_distance as usize)
+        })
+    }
+
+    /// Patterns whose triggers are close to a run of input words, as judged
+    /// by `accept(edit_distance, trigger)`
+    fn fuzzy_matches_by(
+        &self,
+        input_lower: &str,
+        accept: impl Fn(usize, &str) -> bool,
+    ) -> Vec<PatternMatch<'_>> {
+        let words: Vec<&str> = input_lower.split_whitespace().collect();
+        let mut matches: Vec<PatternMatch> = self
+            .patterns
+            .iter()
+            .filter(|pattern| !is_risky_pattern(pattern))
+            .filter_map(|pattern| {
+                pattern
+                    .triggers
+                    .iter()
+                    .filter(|trigger| trigger.chars().count() >= MIN_FUZZY_TRIGGER_LEN)
+                    .filter_map(|trigger| {
+                        let (window, distance) = closest_window(&words, trigger)?;
+                        if !accept(distance, trigger) {
+                            return None;
+                        }
+                        let mut score =
+                            calculate_match_score(input_lower, &window, pattern.confidence);
+                        score.confidence =
+                            (score.confidence - FUZZY_PENALTY * distance as f64).max(0.0);
+                        Some(PatternMatch { pattern, trigger, score })
+                    })
+                    .reduce(best_of)
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.score.confidence.total_cmp(&a.score.confidence));
+        matches
+    }
+
+    /// Turn a pattern match into a full result for the given input
+    fn build_result(&self, m: &PatternMatch, input: &str) -> TranslationResult {
+        let template = m.pattern.template_for(self.shell_target);
+        let command = self.substitute_placeholders(template, input);
+        TranslationResult::new(command, m.score.confidence, m.pattern.explanation.clone())
+            .with_match(m.trigger, m.score)
+    }
+
+    /// Fill in placeholders one pipeline segment at a time, so separators
+    /// in the template survive and substituted values never split a segment
     fn substitute_placeholders(&self, template: &str, input: &str) -> String {
-        let mut result = template.to_string();
+        split_command(template)
+            .into_iter()
+            .map(|(segment, separator)| {
+                let segment = self.substitute_segment(segment, template, input);
+                if separator.is_empty() {
+                    segment
+                } else {
+                    format!("{} {} ", segment, separator)
+                }
+            })
+            .collect()
+    }
 
-        // Extract potential arguments from input
-        let words: Vec<&str> = input.split_whitespace().collect();
+    fn substitute_segment(&self, segment: &str, template: &str, input: &str) -> String {
+        let mut result = segment.to_string();
+
+        // Extract potential arguments from input, keeping quoted ones whole
+        let args = split_args(input);
+        let words: Vec<&str> = args.iter().map(String::as_str).collect();
 
         // Simple placeholder substitution
         if result.contains("{path}") || result.contains("{filename}") || result.contains("{dirname}") {
-            // Try to find a path-like argument
-            for word in &words {
-                if word.starts_with('/') || word.starts_with('.') || word.contains('.') {
-                    result = result.replace("{path}", word);
-                    result = result.replace("{filename}", word);
-                    result = result.replace("{dirname}", word);
-                    break;
-                }
+            // A quoted argument is the path unless it is needed for {pattern};
+            // otherwise look for a path-like word
+            let quoted = extract_quoted(input).filter(|_| !template.contains("{pattern}"));
+            let path = quoted.or_else(|| {
+                words
+                    .iter()
+                    .find(|w| w.starts_with('/') || w.starts_with('.') || w.contains('.'))
+                    .map(|w| w.to_string())
+            });
+            if let Some(path) = path {
+                let path = self.quote(&path);
+                result = result.replace("{path}", &path);
+                result = result.replace("{filename}", &path);
+                result = result.replace("{dirname}", &path);
             }
         }
 
         if result.contains("{pattern}") {
             // Look for quoted strings or the last word
-            if let Some(quoted) = extract_quoted(&input) {
-                result = result.replace("{pattern}", &quoted);
+            if let Some(quoted) = extract_quoted(input) {
+                result = result.replace("{pattern}", &self.quote(&quoted));
             } else if let Some(last) = words.last() {
-                result = result.replace("{pattern}", last);
+                result = result.replace("{pattern}", &self.quote(last));
             }
         }
 
         if result.contains("{branch}") {
             // Look for branch name (last word usually)
             if let Some(last) = words.last() {
-                result = result.replace("{branch}", last);
+                result = result.replace("{branch}", &self.quote(last));
             }
         }
 
         if result.contains("{message}") {
             // Look for quoted message
-            if let Some(quoted) = extract_quoted(&input) {
-                result = result.replace("{message}", &quoted);
+            if let Some(quoted) = extract_quoted(input) {
+                result = result.replace("{message}", &self.quote_literal(&quoted));
             } else {
-                result = result.replace("{message}", "update");
+                result = result.replace("{message}", &self.quote_literal("update"));
             }
         }
 
         if result.contains("{source}") && result.contains("{dest}") {
-            // Need two paths
-            let paths: Vec<&str> = words.iter()
-                .filter(|w| w.contains('/') || w.contains('.'))
-                .copied()
-                .collect();
-            if paths.len() >= 2 {
-                result = result.replace("{source}", paths[0]);
-                result = result.replace("{dest}", paths[1]);
+            // Prefer the roles given by "copy X to Y" / "from X into Y",
+            // otherwise take the first two paths
+            let extractor = PlaceholderExtractor::new(&words);
+            let pair = extractor.source().zip(extractor.dest()).or_else(|| {
+                let mut paths = words.iter().filter(|w| w.contains('/') || w.contains('.'));
+                paths.next().copied().zip(paths.next().copied())
+            });
+            if let Some((source, dest)) = pair {
+                result = result.replace("{source}", &self.quote(source));
+                result = result.replace("{dest}", &self.quote(dest));
             }
         }
 
@@ -530,6 +1204,7 @@ impl LmshTranslator {
                 .copied()
                 .collect();
             if !files.is_empty() {
+                let files: Vec<String> = files.iter().map(|f| self.quote(f)).collect();
                 result = result.replace("{files}", &files.join(" "));
             } else {
                 result = result.replace("{files}", ".");
@@ -540,7 +1215,32 @@ impl LmshTranslator {
             // Look for URL
             for word in &words {
                 if word.starts_with("http://") || word.starts_with("https://") {
-                    result = result.replace("{url}", word);
+                    result = result.replace("{url}", &self.quote(word));
+                    break;
+                }
+            }
+        }
+
+        if result.contains("{n}") {
+            // Look for a count next to "lines", "first", or "last"
+            let count = words.iter().enumerate().find_map(|(i, word)| {
+                word.parse::<u32>().ok()?;
+                let before = i.checked_sub(1).map(|j| words[j].to_lowercase());
+                let after = words.get(i + 1).map(|w| w.to_lowercase());
+                let near_keyword =
+                    matches!(before.as_deref(), Some("first" | "last" | "top" | "bottom"))
+                        || matches!(after.as_deref(), Some("lines" | "line"));
+                near_keyword.then_some(*word)
+            });
+            result = result.replace("{n}", count.unwrap_or(DEFAULT_LINE_COUNT));
+        }
+
+        if result.contains("{port}") {
+            // Look for a port number, with or without a leading colon
+            for word in &words {
+                let port = word.trim_start_matches(':');
+                if port.parse::<u16>().is_ok() {
+                    result = result.replace("{port}", port);
                     break;
                 }
             }
@@ -561,9 +1261,10 @@ impl LmshTranslator {
             for word in &words {
                 if word.contains('=') {
                     let parts: Vec<&str> = word.splitn(2, '=').collect();
-                    if parts.len() == 2 {
+                    let is_name = parts[0].chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                    if parts.len() == 2 && !parts[0].is_empty() && is_name {
                         result = result.replace("{var}", parts[0]);
-                        result = result.replace("{value}", parts[1]);
+                        result = result.replace("{value}", &self.quote_literal(parts[1]));
                         break;
                     }
                 }
@@ -584,7 +1285,7 @@ impl LmshTranslator {
             // Look for user:group or just user
             for word in &words {
                 if word.contains(':') || (word.chars().all(|c| c.is_alphanumeric() || c == '_')) {
-                    result = result.replace("{owner}", word);
+                    result = result.replace("{owner}", &self.quote(word));
                     break;
                 }
             }
@@ -595,7 +1296,7 @@ impl LmshTranslator {
             for word in &words {
                 if word.ends_with(".tar.gz") || word.ends_with(".tgz") ||
                    word.ends_with(".zip") || word.ends_with(".tar") {
-                    result = result.replace("{archive}", word);
+                    result = result.replace("{archive}", &self.quote(word));
                     break;
                 }
             }
@@ -605,25 +1306,504 @@ impl LmshTranslator {
         result = result.replace("{path}", ".")
             .replace("{filename}", "file")
             .replace("{dirname}", "directory")
-            .replace("{pattern}", "*")
+            .replace("{pattern}", &self.quote("*"))
             .replace("{branch}", "main")
-            .replace("{message}", "update")
+            .replace("{message}", &self.quote_literal("update"))
             .replace("{source}", "source")
             .replace("{dest}", "dest")
             .replace("{files}", ".")
             .replace("{url}", "https://example.com")
             .replace("{pid}", "0")
             .replace("{var}", "VAR")
-            .replace("{value}", "value")
+            .replace("{value}", &self.quote_literal("value"))
             .replace("{mode}", "755")
             .replace("{owner}", "user")
-            .replace("{archive}", "archive.tar.gz");
+            .replace("{archive}", "archive.tar.gz")
+            .replace("{port}", "8080");
 
         result
     }
+
+    /// Quote a substituted argument for the target shell
+    fn quote(&self, arg: &str) -> String {
+        match self.shell_target {
+            ShellTarget::Posix => shell_quote(arg),
+            ShellTarget::PowerShell => powershell_quote(arg),
+            ShellTarget::Cmd => cmd_quote(arg),
+        }
+    }
+
+    /// Quote a string value for the target shell, even when it needs no
+    /// escaping, so it is never read as a command or glob. Command Prompt
+    /// keeps quotes as part of the value, so it only quotes when needed.
+    fn quote_literal(&self, arg: &str) -> String {
+        match self.shell_target {
+            ShellTarget::Posix => format!("'{}'", arg.replace('\'', "'\\''")),
+            ShellTarget::PowerShell => format!("'{}'", arg.replace('\'', "''")),
+            ShellTarget::Cmd => cmd_quote(arg),
+        }
+    }
+}
+
+/// Words that introduce the source of a copy or move
+const SOURCE_KEYWORDS: &[&str] = &["from", "copy", "move", "rename", "cp", "mv"];
+
+/// Words that introduce the destination of a copy or move
+const DEST_KEYWORDS: &[&str] = &["to", "into", "as"];
+
+/// Words skipped between a keyword and its argument
+const FILLER_WORDS: &[&str] = &["a", "an", "the", "file", "folder", "directory", "my"];
+
+/// Assigns input arguments to placeholder roles by the words before them,
+/// e.g. "move report.txt to archive/" gives source `report.txt` and
+/// destination `archive/`
+struct PlaceholderExtractor<'a> {
+    words: &'a [&'a str],
+}
+
+impl<'a> PlaceholderExtractor<'a> {
+    fn new(words: &'a [&'a str]) -> Self {
+        Self { words }
+    }
+
+    /// Source argument: the word after "from", else the one after the verb
+    fn source(&self) -> Option<&'a str> {
+        self.after(&["from"]).or_else(|| self.after(SOURCE_KEYWORDS))
+    }
+
+    /// Destination argument: the word after "to", "into", or "as"
+    fn dest(&self) -> Option<&'a str> {
+        self.after(DEST_KEYWORDS)
+    }
+
+    /// First argument following one of the keywords, skipping filler words.
+    /// A keyword followed directly by another keyword has no argument.
+    fn after(&self, keywords: &[&str]) -> Option<&'a str> {
+        self.words.iter().enumerate().find_map(|(i, word)| {
+            if !is_one_of(word, keywords) {
+                return None;
+            }
+            self.words[i + 1..]
+                .iter()
+                .find(|w| !is_one_of(w, FILLER_WORDS))
+                .filter(|w| !is_one_of(w, SOURCE_KEYWORDS) && !is_one_of(w, DEST_KEYWORDS))
+                .copied()
+        })
+    }
+}
+
+/// Score how well a command's words match a template: 1.0 for an exact
+/// match, 0.9 when placeholders were filled, 0.7 when the template only
+/// matches the leading words
+fn template_match_score(template: &str, tokens: &[&str]) -> Option<f64> {
+    let parts = raw_args(template);
+    if parts.is_empty() || tokens.len() < parts.len() {
+        return None;
+    }
+    if !parts.iter().zip(tokens).all(|(part, token)| placeholder_match(part, token)) {
+        return None;
+    }
+
+    let score = if tokens.len() > parts.len() {
+        0.7
+    } else if parts.iter().any(|part| part.contains('{')) {
+        0.9
+    } else {
+        1.0
+    };
+    Some(score)
+}
+
+/// Match one template word against a command word, where each `{name}`
+/// stands for one or more characters. A placeholder never stands for a flag.
+fn placeholder_match(part: &str, token: &str) -> bool {
+    if part.starts_with('{') && token.starts_with('-') {
+        return false;
+    }
+    let Some(open) = part.find('{') else {
+        return part == token;
+    };
+    let Some(close) = part[open..].find('}').map(|i| open + i) else {
+        return part == token;
+    };
+    let (prefix, rest) = (&part[..open], &part[close + 1..]);
+    let Some(token) = token.strip_prefix(prefix) else {
+        return false;
+    };
+
+    // Try every non-empty span for the placeholder
+    token
+        .char_indices()
+        .skip(1)
+        .map(|(i, _)| i)
+        .chain(std::iter::once(token.len()))
+        .any(|end| !token.is_empty() && placeholder_match(rest, &token[end..]))
+}
+
+/// What common programs do, for commands with no matching template
+const PROGRAM_DESCRIPTIONS: &[(&str, &str)] = &[
+    ("cat", "Print file contents"),
+    ("cd", "Change directory"),
+    ("chmod", "Change file permissions"),
+    ("chown", "Change file ownership"),
+    ("cp", "Copy files"),
+    ("curl", "Transfer data from a URL"),
+    ("df", "Show disk space usage"),
+    ("du", "Show disk usage of files"),
+    ("find", "Search for files"),
+    ("git", "Run a git command"),
+    ("grep", "Search text for a pattern"),
+    ("head", "Show the first lines of a file"),
+    ("kill", "Send a signal to a process"),
+    ("ls", "List directory contents"),
+    ("mkdir", "Create directories"),
+    ("mv", "Move or rename files"),
+    ("ps", "Show running processes"),
+    ("rm", "Remove files"),
+    ("sort", "Sort lines of text"),
+    ("tail", "Show the last lines of a file"),
+    ("tar", "Work with tar archives"),
+    ("wc", "Count lines, words, and bytes"),
+];
+
+/// What common flags mean, per program
+const FLAG_DESCRIPTIONS: &[(&str, char, &str)] = &[
+    ("cp", 'r', "recursively"),
+    ("curl", 'L', "following redirects"),
+    ("curl", 'O', "saving under the remote name"),
+    ("df", 'h', "in human-readable sizes"),
+    ("du", 'a', "including files"),
+    ("du", 'h', "in human-readable sizes"),
+    ("du", 's', "as a summary"),
+    ("grep", 'i', "ignoring case"),
+    ("grep", 'n', "with line numbers"),
+    ("grep", 'r', "recursively"),
+    ("grep", 'v', "selecting non-matching lines"),
+    ("ls", 'a', "including hidden files"),
+    ("ls", 'h', "in human-readable sizes"),
+    ("ls", 'l', "in long format"),
+    ("ls", 't', "sorted by modification time"),
+    ("mkdir", 'p', "creating parent directories"),
+    ("rm", 'f', "without prompting"),
+    ("rm", 'r', "recursively"),
+    ("sort", 'h', "comparing human-readable sizes"),
+    ("sort", 'r', "in reverse order"),
+    ("tar", 'c', "creating an archive"),
+    ("tar", 'f', "using an archive file"),
+    ("tar", 'v', "listing files as they are processed"),
+    ("tar", 'x', "extracting an archive"),
+    ("tar", 'z', "with gzip compression"),
+];
+
+/// Best-effort explanation of each pipeline segment from its program and
+/// flags, with 0.5 confidence when every program is known and 0.0 otherwise
+fn explain_from_flags(command: &str) -> (String, f64) {
+    let mut explanations = Vec::new();
+    let mut all_known = true;
+
+    for (segment, _) in split_command(command) {
+        let tokens = raw_args(segment);
+        let tokens = match tokens.first() {
+            Some(&"sudo") => &tokens[1..],
+            _ => &tokens[..],
+        };
+        let Some(program) = tokens.first() else {
+            continue;
+        };
+        let Some((_, description)) = PROGRAM_DESCRIPTIONS.iter().find(|(p, _)| p == program) else {
+            all_known = false;
+            explanations.push(format!("Run '{}'", program));
+            continue;
+        };
+
+        // Short flags may be combined, as in "-la"
+        let mut notes: Vec<&str> = Vec::new();
+        for flag in tokens[1..].iter().filter(|t| t.starts_with('-') && !t.starts_with("--")) {
+            for c in flag[1..].chars() {
+                let known = FLAG_DESCRIPTIONS.iter().find(|(p, f, _)| p == program && *f == c);
+                if let Some((_, _, note)) = known {
+                    if !notes.contains(note) {
+                        notes.push(note);
+                    }
+                }
+            }
+        }
+
+        if notes.is_empty() {
+            explanations.push(description.to_string());
+        } else {
+            explanations.push(format!("{}, {}", description, notes.join(", ")));
+        }
+    }
+
+    if explanations.is_empty() {
+        return ("No matching command pattern found".to_string(), 0.0);
+    }
+    let confidence = if all_known { 0.5 } else { 0.0 };
+    (explanations.join(", then "), confidence)
+}
+
+/// Whether any of the pattern's templates deletes, overwrites, or kills,
+/// or is otherwise destructive
+fn is_risky_pattern(pattern: &Pattern) -> bool {
+    pattern.templates().any(|template| {
+        detect_destructive(template).is_some()
+            || split_command(template).iter().any(|(segment, _)| {
+                command_tokens(segment).first().is_some_and(|p| NO_FUZZY_PROGRAMS.contains(p))
+            })
+    })
+}
+
+/// Own a list of string literals
+fn to_strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+/// Whether the word is in the list, ignoring case
+fn is_one_of(word: &str, list: &[&str]) -> bool {
+    list.iter().any(|k| word.eq_ignore_ascii_case(k))
+}
+
+fn no_match() -> TranslationResult {
+    TranslationResult::new(
+        String::new(),
+        0.0,
+        "No matching command pattern found".to_string(),
+    )
+}
+
+/// Split a command line on unquoted `|`, `||`, `&&`, `&`, and `;`,
+/// pairing each segment with the separator that follows it
+fn split_command(command: &str) -> Vec<(&str, &str)> {
+    let mut segments = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    let mut chars = command.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            // A backslash escapes the next character except inside single quotes
+            (Some('"') | None, '\\') => {
+                chars.next();
+            }
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '|' | '&' | ';') => {
+                let mut end = i + 1;
+                if c != ';' && chars.peek().map(|(_, next)| *next) == Some(c) {
+                    chars.next();
+                    end += 1;
+                }
+                segments.push((command[start..i].trim(), &command[i..end]));
+                start = end;
+            }
+            (None, _) => {}
+        }
+    }
+
+    segments.push((command[start..].trim(), ""));
+    segments
+}
+
+/// Check each segment of a command against known risky patterns
+fn detect_danger(command: &str) -> Option<String> {
+    for (segment, _) in split_command(command) {
+        let reason = match command_tokens(segment).as_slice() {
+            ["rm", args @ ..] => {
+                let recursive = has_flag(args, &['r', 'R'], "--recursive");
+                match args.iter().find(|a| is_root_or_home(a)) {
+                    Some(target) if recursive => {
+                        Some(format!("Recursively removes '{}'", target))
+                    }
+                    _ => None,
+                }
+            }
+            [program, args @ ..]
+                if is_program(program, &["Remove-Item", "ri", "rd", "rmdir", "del", "erase"])
+                    && is_windows_recursive(args) =>
+            {
+                args.iter()
+                    .find(|a| is_root_or_home(a))
+                    .map(|target| format!("Recursively removes '{}'", target))
+            }
+            [program, ..] if is_program(program, &["format", "Format-Volume"]) => {
+                Some("Formats a drive, erasing its contents".to_string())
+            }
+            ["chmod", args @ ..] if args.iter().any(|a| *a == "777" || *a == "a+rwx") => {
+                Some("Makes files readable and writable by everyone".to_string())
+            }
+            ["dd", ..] => Some("Writes raw data directly to files or devices".to_string()),
+            ["git", "push", args @ ..]
+                if args.iter().any(|a| *a == "-f" || a.starts_with("--force")) =>
+            {
+                Some("Force-push overwrites remote history".to_string())
+            }
+            _ => None,
+        };
+
+        if reason.is_some() {
+            return reason;
+        }
+    }
+    None
+}
+
+/// Words of one command segment with surrounding quotes trimmed and a
+/// leading `sudo` dropped
+fn command_tokens(segment: &str) -> Vec<&str> {
+    let mut tokens: Vec<&str> = segment
+        .split_whitespace()
+        .map(|t| t.trim_matches(|c| c == '\'' || c == '"'))
+        .collect();
+    if tokens.first() == Some(&"sudo") {
+        tokens.remove(0);
+    }
+    tokens
+}
+
+/// Whether the arguments include the long flag or a short flag group
+/// (like `-rf`) containing one of the short letters
+fn has_flag(args: &[&str], short: &[char], long: &str) -> bool {
+    args.iter().any(|a| {
+        *a == long || (a.starts_with('-') && !a.starts_with("--") && a.contains(short))
+    })
+}
+
+/// Whether a token names one of the programs. Windows command names are
+/// case-insensitive, so `RD` and `remove-item` match too
+fn is_program(token: &str, names: &[&str]) -> bool {
+    names.iter().any(|name| token.eq_ignore_ascii_case(name))
+}
+
+/// Whether PowerShell or cmd arguments ask for a recursive delete
+/// (`-Recurse`, `-r`, or cmd's `/s`)
+fn is_windows_recursive(args: &[&str]) -> bool {
+    args.iter().any(|a| ["-Recurse", "-r", "/s"].iter().any(|flag| a.eq_ignore_ascii_case(flag)))
+}
+
+/// Whether an argument is a filesystem root, a drive root like `C:\`, or the
+/// home directory, in POSIX, PowerShell or cmd spelling
+fn is_root_or_home(arg: &str) -> bool {
+    match arg.trim_end_matches('*').as_bytes() {
+        b"/" | b"\\" | b"~" | b"~/" | b"~\\" | b"$HOME" | b"$env:USERPROFILE"
+        | b"%USERPROFILE%" => true,
+        [drive, b':'] | [drive, b':', b'/' | b'\\'] => drive.is_ascii_alphabetic(),
+        _ => false,
+    }
+}
+
+/// Directories whose contents the system depends on
+const SYSTEM_DIRS: &[&str] =
+    &["/bin", "/boot", "/dev", "/etc", "/lib", "/proc", "/sbin", "/sys", "/usr", "/var"];
+
+/// Whether an argument is the root or lies under a system directory
+fn is_system_path(arg: &str) -> bool {
+    let arg = arg.trim_end_matches('*');
+    arg == "/"
+        || SYSTEM_DIRS.iter().any(|dir| {
+            arg.strip_prefix(dir).is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+}
+
+/// Check a command for operations that destroy data or processes. A
+/// superset of `detect_danger`, whose reason is used for dangerous commands;
+/// the rest are routine commands that still deserve a confirmation prompt.
+fn detect_destructive(command: &str) -> Option<String> {
+    if let Some(reason) = detect_danger(command) {
+        return Some(reason);
+    }
+
+    for (segment, _) in split_command(command) {
+        let reason = match command_tokens(segment).as_slice() {
+            ["rm", args @ ..] => {
+                let recursive = has_flag(args, &['r', 'R'], "--recursive");
+                let force = has_flag(args, &['f'], "--force");
+                if let Some(target) = args.iter().find(|a| is_system_path(a)) {
+                    Some(format!("Deletes system path '{}'", target))
+                } else if recursive {
+                    Some("Recursively deletes files".to_string())
+                } else if force {
+                    Some("Deletes files without prompting".to_string())
+                } else {
+                    None
+                }
+            }
+            [program, args @ ..] if is_program(program, &["Remove-Item", "ri", "del", "erase"]) => {
+                if is_windows_recursive(args) {
+                    Some("Recursively deletes files".to_string())
+                } else if args
+                    .iter()
+                    .any(|a| a.eq_ignore_ascii_case("-Force") || a.eq_ignore_ascii_case("/q"))
+                {
+                    Some("Deletes files without prompting".to_string())
+                } else {
+                    None
+                }
+            }
+            [cmd, ..] if *cmd == "mkfs" || cmd.starts_with("mkfs.") => {
+                Some("Formats a filesystem, erasing its contents".to_string())
+            }
+            ["chmod" | "chown", args @ ..] => args
+                .iter()
+                .find(|a| is_system_path(a))
+                .map(|target| format!("Changes permissions on system path '{}'", target)),
+            [program, args @ ..]
+                if is_program(program, &["rmdir", "rd"]) && is_windows_recursive(args) =>
+            {
+                Some("Recursively deletes files".to_string())
+            }
+            ["kill" | "pkill" | "killall" | "Stop-Process" | "taskkill", ..] => {
+                Some("Terminates running processes".to_string())
+            }
+            _ => None,
+        };
+
+        if reason.is_some() {
+            return reason;
+        }
+    }
+    None
+}
+
+/// Lowercase the input and drop bare numbers so counts like
+/// "first 5 lines" still match the "first lines" trigger
+fn normalize_input(input: &str) -> String {
+    input
+        .to_lowercase()
+        .split_whitespace()
+        .filter(|w| !w.chars().all(|c| c.is_ascii_digit()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split text into words, ignoring surrounding punctuation
+fn tokenize(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .map(|w| w.trim_matches(|c: char| matches!(c, ',' | '.' | '?' | '!' | ';' | ':' | '"')))
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Whether the phrase appears in the input as a run of whole words
+fn contains_phrase(input: &str, phrase: &str) -> bool {
+    let phrase = tokenize(phrase);
+    if phrase.is_empty() {
+        return false;
+    }
+    tokenize(input).windows(phrase.len()).any(|w| w == phrase.as_slice())
+}
+
+/// Keep the higher-scoring match, preferring the earlier one on ties
+fn best_of<'a>(a: PatternMatch<'a>, b: PatternMatch<'a>) -> PatternMatch<'a> {
+    if b.score.confidence > a.score.confidence {
+        b
+    } else {
+        a
+    }
 }
 
-fn calculate_match_score(input: &str, trigger: &str, base_confidence: f64) -> f64 {
+fn calculate_match_score(input: &str, trigger: &str, base_confidence: f64) -> MatchScore {
     let input_len = input.len() as f64;
     let trigger_len = trigger.len() as f64;
 
@@ -634,7 +1814,162 @@ fn calculate_match_score(input: &str, trigger: &str, base_confidence: f64) -> f6
     // Boost score if trigger appears at the start
     let position_boost = if input.starts_with(trigger) { 0.1 } else { 0.0 };
 
-    (base_confidence + coverage_boost + position_boost).min(1.0)
+    MatchScore {
+        confidence: (base_confidence + coverage_boost + position_boost).min(1.0),
+        coverage: coverage_boost,
+        position: position_boost,
+    }
+}
+
+/// Characters that never need quoting in a shell argument
+fn is_shell_safe(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_-./~:@%+=,".contains(c)
+}
+
+/// Single-quote an argument for POSIX shells when it contains special characters
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty() && arg.chars().all(is_shell_safe) {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// Characters that never need quoting in a PowerShell argument. Unlike POSIX
+/// shells, `,` builds an array and `@` splats, so both are quoted.
+fn is_powershell_safe(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_-./\\~:".contains(c)
+}
+
+/// Single-quote an argument for PowerShell, where quotes are escaped by doubling
+fn powershell_quote(arg: &str) -> String {
+    if !arg.is_empty() && arg.chars().all(is_powershell_safe) {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', "''"))
+}
+
+/// Double-quote an argument for Command Prompt, where quotes are escaped by doubling
+fn cmd_quote(arg: &str) -> String {
+    if !arg.is_empty() && arg.chars().all(|c| is_shell_safe(c) && c != '%') {
+        return arg.to_string();
+    }
+    format!("\"{}\"", arg.replace('"', "\"\""))
+}
+
+/// The run of input words closest to the trigger, with its edit distance
+fn closest_window(words: &[&str], trigger: &str) -> Option<(String, usize)> {
+    let width = trigger.split_whitespace().count();
+    if width == 0 || words.len() < width {
+        return None;
+    }
+
+    words
+        .windows(width)
+        .map(|w| {
+            let window = w.join(" ");
+            let distance = levenshtein(&window, trigger);
+            (window, distance)
+        })
+        .min_by_key(|(_, distance)| *distance)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// Split input into words on whitespace, keeping a quoted run (quotes
+/// included) as one word. A quote only opens a run at the start of a word
+/// and when it is closed later, so apostrophes like "what's" stay ordinary
+/// characters.
+fn raw_args(input: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'');
+        let end = match quote.and_then(|q| rest[1..].find(q)) {
+            Some(end) => end + 2,
+            None => rest.find(char::is_whitespace).unwrap_or(rest.len()),
+        };
+        args.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    args
+}
+
+/// Split input into arguments, treating a quoted run as one argument
+/// without its quotes
+fn split_args(input: &str) -> Vec<String> {
+    raw_args(input)
+        .into_iter()
+        .map(|arg| {
+            let quoted = arg.len() >= 2
+                && (arg.starts_with('"') && arg.ends_with('"')
+                    || arg.starts_with('\'') && arg.ends_with('\''));
+            if quoted { &arg[1..arg.len() - 1] } else { arg }.to_string()
+        })
+        .collect()
+}
+
+/// Split a request into steps on "and then", "then", "after that", "&&",
+/// and ";", ignoring any that appear inside quotes
+fn split_steps(input: &str) -> Vec<String> {
+    let mut steps: Vec<Vec<&str>> = vec![vec![]];
+    let words = raw_args(input);
+    let mut i = 0;
+
+    while i < words.len() {
+        let word = words[i];
+        let bare = word.trim_end_matches([',', ';']).to_lowercase();
+        let quoted = word.starts_with(['"', '\'']);
+        let step = steps.last_mut().expect("steps is never empty");
+
+        let separator_len = match bare.as_str() {
+            _ if quoted => 0,
+            "&&" | ";" | "then" => 1,
+            "after" if words.get(i + 1).is_some_and(|w| {
+                w.trim_end_matches([',', ';']).eq_ignore_ascii_case("that")
+            }) => 2,
+            _ => 0,
+        };
+
+        if separator_len > 0 {
+            // "and then" belongs to the separator, not the previous step
+            if bare == "then" && step.last().is_some_and(|w| w.eq_ignore_ascii_case("and")) {
+                step.pop();
+            }
+            steps.push(vec![]);
+            i += separator_len;
+            continue;
+        }
+
+        if !quoted && word.ends_with(';') {
+            step.push(word.trim_end_matches(';'));
+            steps.push(vec![]);
+        } else {
+            step.push(word);
+        }
+        i += 1;
+    }
+
+    steps
+        .into_iter()
+        .map(|step| step.join(" ").trim_end_matches(',').to_string())
+        .filter(|step| !step.trim().is_empty())
+        .collect()
 }
 
 fn extract_quoted(input: &str) -> Option<String> {
@@ -702,4 +2037,596 @@ mod tests {
         assert_eq!(result.command, "npm run deploy");
         assert_eq!(result.confidence, 1.0);
     }
+
+    #[test]
+    fn test_register_pattern() {
+        let mut translator = LmshTranslator::new();
+        translator.register_pattern(
+            vec!["Deploy Staging".to_string()],
+            "kubectl apply -f {filename}".to_string(),
+            "Deploy manifests to staging".to_string(),
+            0.9,
+        );
+
+        let result = translator.translate("deploy staging k8s/app.yaml".to_string());
+        assert_eq!(result.command, "kubectl apply -f k8s/app.yaml");
+        assert_eq!(result.explanation, "Deploy manifests to staging");
+    }
+
+    #[test]
+    fn test_load_patterns_from_json() {
+        let mut translator = LmshTranslator::new();
+        let json = r#"[
+            {"triggers": ["list pods"], "command_template": "kubectl get pods",
+             "explanation": "List Kubernetes pods", "confidence": 0.9},
+            {"triggers": ["docker ps", "running containers"], "command_template": "docker ps",
+             "explanation": "List running containers", "confidence": 0.9}
+        ]"#;
+        assert_eq!(translator.load_patterns_from_json(json.to_string()).unwrap(), 2);
+        assert_eq!(translator.translate("list pods".to_string()).command, "kubectl get pods");
+
+        let bad = r#"[
+            {"triggers": ["deploy"], "command_template": "make deploy",
+             "explanation": "Deploy", "confidence": 0.9},
+            {"triggers": [], "command_template": "make release",
+             "explanation": "Release", "confidence": 0.9}
+        ]"#;
+        let err = translator.load_patterns_from_json(bad.to_string()).unwrap_err();
+        assert!(err.reason.contains("Pattern 1"));
+        assert!(translator.translate("deploy".to_string()).command.is_empty());
+        assert_eq!(translator.translate("list pods".to_string()).command, "kubectl get pods");
+
+        let empty = r#"[{"triggers": ["release"], "command_template": "  ",
+                         "explanation": "Release", "confidence": 0.9}]"#;
+        let err = translator.load_patterns_from_json(empty.to_string()).unwrap_err();
+        assert!(err.reason.contains("Pattern 0 has an empty command template"));
+    }
+
+    #[test]
+    fn test_translate_ranked() {
+        let translator = LmshTranslator::new();
+        let input = "list files in current directory".to_string();
+        let results = translator.translate_ranked(input.clone(), 3);
+        assert_eq!(results.len(), 2);
+        // Both saturate at 1.0; the tie keeps pattern order
+        assert_eq!(results[0].command, "ls -la");
+        assert_eq!(results[1].command, "pwd");
+        assert_eq!(results[1].explanation, "Print current working directory");
+        assert!(results.windows(2).all(|w| w[0].confidence >= w[1].confidence));
+
+        // The single-result form picks the same winner and lists the rest
+        let best = translator.translate(input.clone());
+        assert_eq!(best.command, results[0].command);
+        assert_eq!(best.confidence, results[0].confidence);
+        assert_eq!(best.alternatives, vec!["pwd"]);
+
+        let candidates = translator.translate_candidates(input.clone(), 3);
+        let commands: Vec<&str> = candidates.iter().map(|r| r.command.as_str()).collect();
+        assert_eq!(commands, vec!["ls -la", "pwd"]);
+        assert_eq!(candidates[1].explanation, results[1].explanation);
+
+        assert_eq!(translator.translate_ranked(input, 1).len(), 1);
+        assert!(translator.translate_ranked("xyznonsense".to_string(), 3).is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_triggers() {
+        let mut translator = LmshTranslator::new();
+
+        let result = translator.translate("git stats".to_string());
+        assert_eq!(result.command, "git status");
+        assert!(result.confidence < translator.translate("git status".to_string()).confidence);
+
+        assert_eq!(translator.translate("lst files".to_string()).command, "ls -la");
+
+        translator.set_max_distance(0);
+        assert!(translator.translate("git stats".to_string()).command.is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_false_positives() {
+        let mut translator = LmshTranslator::new();
+        translator.set_shell("bash".to_string()).unwrap();
+
+        // Fuzzy hits never clear the strict threshold
+        let result = translator.translate("git stats".to_string());
+        assert_eq!(result.command, "git status");
+        assert!(result.confidence < DEFAULT_MIN_CONFIDENCE);
+        assert!(translator.translate_strict("git stats".to_string(), None).command.is_empty());
+
+        // Destructive patterns and short triggers are only matched exactly
+        let cases = [
+            ("remote file x.txt", "rm"),
+            ("show more", "mv"),
+            ("read notes.txt", "head"),
+            ("show data", "date"),
+        ];
+        for (input, wrong) in cases {
+            let result = translator.translate(input.to_string());
+            assert!(!result.command.starts_with(wrong), "{} -> {}", input, result.command);
+            let strict = translator.translate_strict(input.to_string(), None);
+            assert!(!strict.command.starts_with(wrong), "{}", input);
+        }
+        assert!(is_risky_pattern(&translator.patterns[8]));
+    }
+
+    #[test]
+    fn test_translate_fuzzy() {
+        let translator = LmshTranslator::new();
+
+        let result = translator.translate_fuzzy("lst files".to_string(), 0.8);
+        assert_eq!(result.command, "ls -la");
+        assert_eq!(result.matched_trigger, "list files");
+        assert!(result.confidence < translator.translate("list files".to_string()).confidence);
+        assert!(translator.translate_fuzzy("lst files".to_string(), 0.95).command.is_empty());
+
+        // An exact trigger outranks a closer-scoring fuzzy one
+        let result = translator.translate_fuzzy("lst files in current directory".to_string(), 0.8);
+        assert_eq!(result.command, "pwd");
+
+        // Short inputs do not stretch to match long triggers
+        assert!(translator.translate_fuzzy("fils".to_string(), 0.5).command.is_empty());
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("git stats", "git status"), 1);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_dangerous_commands() {
+        let translator = LmshTranslator::new();
+
+        let result = translator.translate("delete directory /".to_string());
+        assert_eq!(result.command, "rm -r /");
+        assert!(result.dangerous);
+        assert!(result.danger_reason.unwrap().contains("'/'"));
+
+        assert!(!translator.translate("list files".to_string()).dangerous);
+        assert!(detect_danger("chmod -R 777 .").is_some());
+        assert!(detect_danger("sudo dd if=/dev/zero of=/dev/sda").is_some());
+        assert!(detect_danger("git push --force origin main").is_some());
+        assert!(detect_danger("git push origin main").is_none());
+        assert!(detect_danger("rm -rf ~").is_some());
+        assert!(detect_danger("rm -r build").is_none());
+    }
+
+    #[test]
+    fn test_destructive_commands() {
+        let mut translator = LmshTranslator::new();
+        translator.set_platform("unix".to_string()).unwrap();
+
+        let result = translator.translate("delete directory /etc".to_string());
+        assert_eq!(result.command, "rm -r /etc");
+        assert!(result.is_destructive);
+        assert!(result.warning.unwrap().contains("'/etc'"));
+        // Destructive, but not one of the catastrophic patterns
+        assert!(!result.dangerous);
+
+        let result = translator.translate("kill process 4242".to_string());
+        assert!(result.is_destructive);
+        assert!(!translator.translate("list files".to_string()).is_destructive);
+        assert!(!translator.translate("xyznonsense".to_string()).is_destructive);
+
+        assert!(detect_destructive("rm /").is_some());
+        assert!(detect_destructive("rm -rf build").is_some());
+        assert!(detect_destructive("rm notes.txt").is_none());
+        assert!(detect_destructive("sudo mkfs.ext4 /dev/sdb1").is_some());
+        assert!(detect_destructive("chmod 644 /etc/hosts").is_some());
+        assert!(detect_destructive("chmod +x run.sh").is_none());
+        assert!(!is_system_path("/etcetera"));
+
+        // Every dangerous command is destructive, with the same reason
+        let commands = [
+            "rm -r /",
+            "sudo rm -rf ~",
+            "chmod -R 777 .",
+            "dd if=/dev/zero of=/dev/sda",
+            "git push --force",
+            "rm -r /etc",
+            "kill 42",
+            "ls -la",
+        ];
+        for command in commands {
+            let result = TranslationResult::new(command.to_string(), 1.0, String::new());
+            if result.dangerous {
+                assert!(result.is_destructive, "{}", command);
+                assert_eq!(result.warning, result.danger_reason, "{}", command);
+            }
+        }
+        assert_eq!(detect_destructive("rm -r /"), detect_danger("rm -r /"));
+    }
+
+    #[test]
+    fn test_windows_danger() {
+        let mut translator = LmshTranslator::new();
+        let cases = [
+            ("bash", "rm -r /"),
+            ("powershell", "Remove-Item -Recurse /"),
+            ("cmd", "rmdir /s /q /"),
+        ];
+        for (shell, command) in cases {
+            translator.set_shell(shell.to_string()).unwrap();
+            let result = translator.translate("delete directory /".to_string());
+            assert_eq!(result.command, command);
+            assert!(result.dangerous, "{}", shell);
+        }
+
+        assert!(detect_danger("Remove-Item -Recurse -Force C:\\").is_some());
+        assert!(detect_danger("remove-item -Path ~ -Recurse").is_some());
+        assert!(detect_danger("rd /s /q C:").is_some());
+        assert!(detect_danger("RMDIR /S %USERPROFILE%").is_some());
+        assert!(detect_danger("del /s /q C:\\*").is_some());
+        assert!(detect_danger("format D:").is_some());
+        assert!(detect_danger("Format-Volume -DriveLetter D").is_some());
+        assert!(detect_danger("Remove-Item -Recurse build").is_none());
+        assert!(detect_danger("rmdir /").is_none());
+
+        assert!(detect_destructive("Remove-Item -Recurse build").is_some());
+        assert!(detect_destructive("RD /S build").is_some());
+        assert!(detect_destructive("del /s /q build").is_some());
+        assert!(detect_destructive("del /q notes.txt").is_some());
+        assert!(detect_destructive("Stop-Process -Id 42").is_some());
+        assert!(detect_destructive("del notes.txt").is_none());
+        assert!(detect_destructive("Remove-Item notes.txt").is_none());
+    }
+
+    #[test]
+    fn test_windows_templates() {
+        let mut translator = LmshTranslator::new();
+        translator.set_platform("windows".to_string()).unwrap();
+        assert_eq!(translator.translate("list files".to_string()).command, "Get-ChildItem -Force");
+        assert_eq!(
+            translator.translate("disk usage".to_string()).command,
+            "Get-PSDrive -PSProvider FileSystem"
+        );
+        // Commands without a Windows variant fall back to the Unix template
+        assert_eq!(translator.translate("git status".to_string()).command, "git status");
+
+        translator.set_platform("linux".to_string()).unwrap();
+        assert_eq!(translator.translate("disk usage".to_string()).command, "df -h");
+        assert!(translator.set_platform("beos".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_shell_targets() {
+        let mut translator = LmshTranslator::new();
+        let cases = [
+            ("bash", "ls -la", "pwd"),
+            ("zsh", "ls -la", "pwd"),
+            ("powershell", "Get-ChildItem -Force", "Get-Location"),
+            ("cmd", "dir /a", "cd"),
+        ];
+        for (shell, list, current) in cases {
+            translator.set_shell(shell.to_string()).unwrap();
+            assert_eq!(translator.translate("list files".to_string()).command, list);
+            assert_eq!(translator.translate("current directory".to_string()).command, current);
+        }
+
+        // cmd quotes with double quotes and falls back to the bash template
+        let result = translator.translate("show file \"my file.txt\"".to_string());
+        assert_eq!(result.command, "type \"my file.txt\"");
+        assert_eq!(translator.translate("git status".to_string()).command, "git status");
+        assert!(translator.translate("delete directory build".to_string()).is_destructive);
+
+        assert!(translator.set_shell("fish".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_argument_quoting() {
+        let mut translator = LmshTranslator::new();
+        translator.set_platform("unix".to_string()).unwrap();
+
+        let result = translator.translate("show file \"my file.txt\"".to_string());
+        assert_eq!(result.command, "cat 'my file.txt'");
+
+        let result = translator.translate("find file '*.rs'".to_string());
+        assert_eq!(result.command, "find . -name '*.rs'");
+
+        let result = translator.translate("create file \"my file.txt\"".to_string());
+        assert_eq!(result.command, "touch 'my file.txt'");
+
+        let result = translator.translate("copy file \"my notes.txt\" to \"backup dir/\"".to_string());
+        assert_eq!(result.command, "cp 'my notes.txt' 'backup dir/'");
+
+        let result = translator.translate("commit changes \"it's done; rm -rf ~\"".to_string());
+        assert_eq!(result.command, "git commit -m 'it'\\''s done; rm -rf ~'");
+        assert!(!result.dangerous);
+
+        let result = translator.translate("set variable GREETING=hello;ls".to_string());
+        assert_eq!(result.command, "export GREETING='hello;ls'");
+
+        assert_eq!(split_args("what's on \"a b\" 'c"), vec!["what's", "on", "a b", "'c"]);
+        assert_eq!(shell_quote("a.txt"), "a.txt");
+        assert_eq!(shell_quote("it's; rm -rf ~"), "'it'\\''s; rm -rf ~'");
+        assert_eq!(powershell_quote("it's"), "'it''s'");
+        assert_eq!(powershell_quote("C:\\Users\\me.txt"), "C:\\Users\\me.txt");
+        assert_eq!(powershell_quote("a,b"), "'a,b'");
+        assert_eq!(powershell_quote("@args"), "'@args'");
+        assert_eq!(powershell_quote("$env:PATH"), "'$env:PATH'");
+        assert_eq!(powershell_quote("a`b"), "'a`b'");
+    }
+
+    #[test]
+    fn test_placeholder_roles() {
+        let mut translator = LmshTranslator::new();
+        translator.set_shell("bash".to_string()).unwrap();
+
+        let cases = [
+            ("move report.txt to archive/", "mv report.txt archive/"),
+            ("copy notes to backup/", "cp notes backup/"),
+            ("copy the file from /tmp/a.txt into b.txt", "cp /tmp/a.txt b.txt"),
+            ("rename file draft.md as final.md", "mv draft.md final.md"),
+            // No prepositions: fall back to the first two paths
+            ("copy file a.txt b.txt", "cp a.txt b.txt"),
+        ];
+        for (input, command) in cases {
+            assert_eq!(translator.translate(input.to_string()).command, command, "{}", input);
+        }
+
+        let words = ["copy", "to", "backup/"];
+        let extractor = PlaceholderExtractor::new(&words);
+        assert_eq!(extractor.source(), None);
+        assert_eq!(extractor.dest(), Some("backup/"));
+    }
+
+    #[test]
+    fn test_explain_command() {
+        let translator = LmshTranslator::new();
+
+        let result = translator.explain_command("git log --oneline -20".to_string());
+        assert_eq!(result.explanation, "Show recent commit history");
+        assert_eq!(result.confidence, 1.0);
+        assert_eq!(result.command, "git log --oneline -20");
+
+        let result = translator.explain_command("tail -n 100 'app log.txt'".to_string());
+        assert_eq!(result.explanation, "Show the last lines of a file");
+        assert_eq!(result.confidence, 0.9);
+
+        let result = translator.explain_command("lsof -i :3000".to_string());
+        assert_eq!(result.explanation, "Show processes using a network port");
+
+        // Other shells' templates are recognized too
+        let result = translator.explain_command("Get-ChildItem -Force".to_string());
+        assert_eq!(result.explanation, "List all files in the current directory with details");
+
+        let result = translator.explain_command("git status --short".to_string());
+        assert_eq!(result.explanation, "Show git repository status");
+        assert_eq!(result.confidence, 0.7);
+
+        // No template: explain from the program and its flags
+        let result = translator.explain_command("sudo rm -rf /".to_string());
+        assert_eq!(result.explanation, "Remove files, recursively, without prompting");
+        assert_eq!(result.confidence, 0.5);
+        assert!(result.dangerous);
+
+        let result = translator.explain_command("ls -lt src | sort -r".to_string());
+        assert_eq!(
+            result.explanation,
+            "List directory contents, in long format, sorted by modification time, \
+             then Sort lines of text, in reverse order"
+        );
+        assert_eq!(result.confidence, 0.5);
+
+        let result = translator.explain_command("frobnicate --all".to_string());
+        assert_eq!(result.explanation, "Run 'frobnicate'");
+        assert_eq!(result.confidence, 0.0);
+
+        assert!(placeholder_match("{var}={value}", "PATH=/bin"));
+        assert!(placeholder_match("{archive}.tar.gz", "logs.tar.gz"));
+        assert!(!placeholder_match("{archive}.tar.gz", ".tar.gz"));
+        assert!(!placeholder_match(":{port}", "3000"));
+    }
+
+    #[test]
+    fn test_line_count_extraction() {
+        let mut translator = LmshTranslator::new();
+        translator.set_platform("unix".to_string()).unwrap();
+
+        let result = translator.translate("first 5 lines of a.txt".to_string());
+        assert_eq!(result.command, "head -n 5 a.txt");
+
+        let result = translator.translate("show last 50 lines of log.txt".to_string());
+        assert_eq!(result.command, "tail -n 50 log.txt");
+
+        let result = translator.translate("tail of file log.txt".to_string());
+        assert_eq!(result.command, "tail -n 20 log.txt");
+    }
+
+    #[test]
+    fn test_alias_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/aliases.json").to_str().unwrap().to_string();
+
+        let mut translator = LmshTranslator::new();
+        translator.add_alias("deploy".to_string(), "npm run deploy".to_string());
+        translator.add_alias("tests".to_string(), "cargo test".to_string());
+        assert!(translator.remove_alias("Tests".to_string()));
+        assert!(!translator.remove_alias("tests".to_string()));
+        translator.save_aliases(path.clone()).unwrap();
+
+        let mut restored = LmshTranslator::new();
+        restored.add_alias("build".to_string(), "cargo build".to_string());
+        restored.load_aliases(path).unwrap();
+        let aliases = restored.get_aliases();
+        assert_eq!(aliases.len(), 2);
+        assert_eq!(aliases["deploy"], "npm run deploy");
+        assert_eq!(aliases["build"], "cargo build");
+    }
+
+    #[test]
+    fn test_alias_export_import() {
+        let mut translator = LmshTranslator::new();
+        let deploy = "kubectl apply -f 'prod env/app.yaml'".to_string();
+        translator.add_alias("Deploy Prod".to_string(), deploy.clone());
+        translator.add_alias("grep \"todo\"".to_string(), "rg 'TODO|FIXME' \\ src".to_string());
+        let json = translator.export_aliases();
+
+        let mut restored = LmshTranslator::new();
+        restored.add_alias("deploy prod".to_string(), "make deploy".to_string());
+        assert_eq!(restored.import_aliases(json).unwrap(), 2);
+        assert_eq!(restored.get_aliases(), translator.get_aliases());
+        assert_eq!(
+            restored.translate_with_aliases("deploy prod".to_string()).command,
+            deploy
+        );
+
+        // Imported keys are lowercased like add_alias
+        let json = r#"{"Ship It": "make release"}"#.to_string();
+        assert_eq!(restored.import_aliases(json).unwrap(), 1);
+        assert_eq!(restored.get_aliases()["ship it"], "make release");
+        assert!(restored.import_aliases("[1, 2]".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_whole_word_matching() {
+        let translator = LmshTranslator::new();
+        // "tools" contains "ls" and "catalog" contains "cat", but neither is a whole word
+        assert!(translator.translate("tools".to_string()).command.is_empty());
+        assert!(translator.translate("catalog".to_string()).command.is_empty());
+
+        assert!(contains_phrase("please list files now", "list files"));
+        assert!(contains_phrase("where am i?", "where am i"));
+        assert!(!contains_phrase("show file list", "list files"));
+    }
+
+    #[test]
+    fn test_match_details() {
+        let mut translator = LmshTranslator::new();
+
+        let result = translator.translate("please show files".to_string());
+        assert_eq!(result.matched_trigger, "show files");
+        assert!(result.coverage > 0.0);
+        assert_eq!(result.position, 0.0);
+
+        let result = translator.translate("git status".to_string());
+        assert_eq!(result.position, 0.1);
+
+        let result = translator.translate("xyznonsense".to_string());
+        assert!(result.matched_trigger.is_empty());
+        assert_eq!(result.coverage, 0.0);
+
+        translator.add_alias("ship it".to_string(), "make release".to_string());
+        let result = translator.translate_with_aliases("ship it".to_string());
+        assert_eq!(result.matched_trigger, "ship it");
+        assert!((result.coverage - 0.2).abs() < 1e-9);
+        assert_eq!(result.position, 0.1);
+    }
+
+    #[test]
+    fn test_pipeline_patterns() {
+        let mut translator = LmshTranslator::new();
+        translator.set_platform("unix".to_string()).unwrap();
+
+        assert_eq!(
+            translator.translate("show processes using port 8080".to_string()).command,
+            "lsof -i :8080"
+        );
+        assert_eq!(
+            translator.translate("count lines in notes.txt".to_string()).command,
+            "wc -l notes.txt"
+        );
+        assert_eq!(translator.translate("find the disk hogs".to_string()).command, "du -sh * | sort -h");
+        assert_eq!(
+            translator.translate("show largest files".to_string()).command,
+            "du -ah . | sort -rh | head -n 20"
+        );
+
+        // A quoted pipe in the argument stays inside its segment
+        let result = translator.translate("count matches of 'a|b' in app.log".to_string());
+        assert_eq!(result.command, "grep -o 'a|b' app.log | wc -l");
+        assert_eq!(split_command(&result.command).len(), 2);
+    }
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(
+            split_command("ifconfig || ip addr"),
+            vec![("ifconfig", "||"), ("ip addr", "")]
+        );
+        assert_eq!(split_command("echo 'a; b' | wc"), vec![("echo 'a; b'", "|"), ("wc", "")]);
+        assert!(detect_danger("grep 'x; dd' notes.txt").is_none());
+        assert_eq!(split_command(r"echo 'a'\''; b'"), vec![(r"echo 'a'\''; b'", "")]);
+    }
+
+    #[test]
+    fn test_translate_pipeline() {
+        let mut translator = LmshTranslator::new();
+        translator.set_shell("bash".to_string()).unwrap();
+
+        let input = "create branch feature-x and then push changes".to_string();
+        let results = translator.translate_pipeline(input);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].command, "git checkout -b feature-x");
+        assert_eq!(results[1].command, "git push");
+
+        let input = "git status; frobnicate the widgets, after that git log".to_string();
+        let results = translator.translate_pipeline(input);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].command, "git status");
+        assert!(results[1].command.is_empty());
+        assert_eq!(results[1].confidence, 0.0);
+        assert_eq!(results[2].command, "git log --oneline -20");
+
+        let input = "git pull && git status then git push".to_string();
+        assert_eq!(
+            translator.translate_pipeline_joined(input).unwrap(),
+            "git pull && git status && git push"
+        );
+        let err = translator
+            .translate_pipeline_joined("git pull then frobnicate".to_string())
+            .unwrap_err();
+        assert!(err.reason.contains("Step 1"));
+
+        // Separators inside quotes stay part of the step
+        assert_eq!(
+            split_steps("commit changes \"fix a; then b\" then git push"),
+            vec!["commit changes \"fix a; then b\"", "git push"]
+        );
+        assert_eq!(split_args("what's on \"a b\""), vec!["what's", "on", "a b"]);
+    }
+
+    #[test]
+    fn test_translate_batch() {
+        let translator = LmshTranslator::new();
+        let results = translator.translate_batch(vec![
+            "git status".to_string(),
+            String::new(),
+            "   ".to_string(),
+            "disk space".to_string(),
+        ]);
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].command, "git status");
+        assert!(results[1].command.is_empty());
+        assert_eq!(results[1].confidence, 0.0);
+        assert!(results[2].command.is_empty());
+        assert_eq!(results[3].explanation, "Show disk space usage");
+    }
+
+    #[test]
+    fn test_translate_strict() {
+        let mut translator = LmshTranslator::new();
+        translator.register_pattern(
+            vec!["vague request".to_string()],
+            "echo maybe".to_string(),
+            "A low-confidence guess".to_string(),
+            0.3,
+        );
+
+        let weak = translator.translate("a vague request for something".to_string());
+        assert_eq!(weak.command, "echo maybe");
+        let strict = translator.translate_strict("a vague request for something".to_string(), None);
+        assert!(strict.command.is_empty());
+
+        // The threshold is inclusive
+        let strong = translator.translate("git status".to_string());
+        let at = translator.translate_strict("git status".to_string(), Some(strong.confidence));
+        assert_eq!(at.command, "git status");
+        let above =
+            translator.translate_strict("git status".to_string(), Some(strong.confidence + 1e-9));
+        assert!(above.command.is_empty());
+        assert_eq!(above.confidence, 0.0);
+    }
 }
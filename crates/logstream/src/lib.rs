@@ -1,16 +1,20 @@
 //! High-performance NDJSON worker log parser
 //!
 //! Parses Claude Code worker output streams (NDJSON events) using a
-//! VecDeque ring buffer. Extracts health signals, session IDs, and
-//! worker state from the event stream.
+//! VecDeque ring buffer. Extracts health signals, session IDs,
+//! tool invocations, token usage, and worker state from the event stream.
 
-use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
-const MAX_OUTPUT_LINES: usize = 1000;
-const MAX_EVENTS: usize = 500;
+const DEFAULT_MAX_OUTPUT_LINES: usize = 1000;
+const DEFAULT_MAX_EVENTS: usize = 500;
+const DEFAULT_HEALTH_TIMEOUT_MS: i64 = 60_000;
+const DEFAULT_REPEAT_THRESHOLD: u32 = 20;
+/// Longest partial line carried between chunks before it is dropped
+const MAX_LINE_BUFFER_BYTES: usize = 1024 * 1024;
 
 // --- Raw event structure from Claude Code NDJSON ---
 
@@ -21,11 +25,27 @@ struct RawEvent {
     subtype: Option<String>,
     session_id: Option<String>,
     message: Option<RawMessage>,
+    usage: Option<RawUsage>,
+    is_error: Option<bool>,
+    /// Final result text on result events
+    result: Option<String>,
+    /// Error detail, either a string or an object with a `message`
+    error: Option<serde_json::Value>,
+    /// Event time, as epoch milliseconds or an RFC 3339 string
+    timestamp: Option<serde_json::Value>,
 }
 
 #[derive(Deserialize)]
 struct RawMessage {
     content: Option<Vec<RawContent>>,
+    usage: Option<RawUsage>,
+}
+
+#[derive(Deserialize)]
+struct RawUsage {
+    input_tokens: Option<i64>,
+    output_tokens: Option<i64>,
+    cache_read_input_tokens: Option<i64>,
 }
 
 #[derive(Deserialize)]
@@ -33,6 +53,10 @@ struct RawContent {
     #[serde(rename = "type")]
     content_type: Option<String>,
     text: Option<String>,
+    /// Tool name for `tool_use` blocks
+    name: Option<String>,
+    /// Tool arguments for `tool_use` blocks
+    input: Option<serde_json::Value>,
 }
 
 // --- NAPI-exported types ---
@@ -41,7 +65,7 @@ struct RawContent {
 #[napi(object)]
 #[derive(Clone, Debug, Serialize)]
 pub struct ParsedEvent {
-    /// Event type: "system", "assistant", "result", "unknown"
+    /// Event type: "system", "assistant", "result", "warning" (parser-generated), "unknown"
     pub event_type: String,
     /// Event subtype (e.g., "init" for system events)
     pub subtype: String,
@@ -51,6 +75,12 @@ pub struct ParsedEvent {
     pub text: String,
     /// Whether this event indicates an error
     pub is_error: bool,
+    /// Coarse error class: "rate_limit", "timeout", "tool_error", "unknown" (empty if not an error)
+    pub error_kind: String,
+    /// Name of the first tool invoked in this event (empty if none)
+    pub tool_name: String,
+    /// JSON-encoded input of that tool invocation (empty if none)
+    pub tool_input: String,
     /// Timestamp (milliseconds since epoch)
     pub timestamp: i64,
 }
@@ -69,6 +99,33 @@ pub struct HealthSignal {
     pub total_events: u32,
     /// Whether the worker appears healthy
     pub is_healthy: bool,
+    /// Why the worker is unhealthy (empty when healthy)
+    pub reason: String,
+}
+
+/// Token usage accumulated from result events
+#[napi(object)]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TokenStats {
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    /// Estimated cost from the rates given to `set_pricing` (0 until set)
+    pub estimated_cost: f64,
+}
+
+/// Consistent snapshot of stream statistics for a worker summary
+#[napi(object)]
+#[derive(Clone, Debug, Serialize)]
+pub struct StreamSummary {
+    pub total_events: u32,
+    pub error_count: u32,
+    pub tool_use_count: u32,
+    pub assistant_turns: u32,
+    pub session_id: String,
+    pub state: String,
+    /// Milliseconds since the first parsed event (0 before any event)
+    pub uptime_ms: i64,
 }
 
 /// Stateful NDJSON parser with ring buffer for output history
@@ -78,8 +135,14 @@ pub struct LogStreamParser {
     events: VecDeque<ParsedEvent>,
     /// Ring buffer of text output lines
     output_lines: VecDeque<String>,
+    /// Capacity of `events`
+    max_events: usize,
+    /// Capacity of `output_lines`
+    max_output_lines: usize,
     /// Incomplete line buffer (partial data from previous chunk)
     line_buffer: String,
+    /// Set after a line overflows the buffer; input is dropped until the next newline
+    discarding_line: bool,
     /// Detected session ID
     session_id: String,
     /// Current worker state
@@ -90,81 +153,120 @@ pub struct LogStreamParser {
     error_count: u32,
     /// Total event counter
     total_events: u32,
+    /// Tool invocation counter
+    tool_use_count: u32,
+    /// Assistant message counter
+    assistant_turns: u32,
+    /// Timestamp of the first parsed event
+    first_event_at: i64,
+    /// Token usage totals
+    token_stats: TokenStats,
+    /// Per-token rates: input, output, cache read
+    pricing: (f64, f64, f64),
+    /// Idle gap after which a working worker is unhealthy
+    health_timeout_ms: i64,
+    /// Identical consecutive output lines that mark a worker as stuck (0 disables)
+    repeat_threshold: u32,
+    /// Length of the current run of identical output lines
+    repeat_count: u32,
+}
+
+impl Default for LogStreamParser {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
 }
 
 #[napi]
 impl LogStreamParser {
+    /// Create a parser keeping at most `max_output_lines` (default 1000)
+    /// output lines and `max_events` (default 500) parsed events.
     #[napi(constructor)]
-    pub fn new() -> Self {
+    pub fn new(max_output_lines: Option<u32>, max_events: Option<u32>) -> Self {
+        let max_output_lines = max_output_lines.map_or(DEFAULT_MAX_OUTPUT_LINES, |n| n.max(1) as usize);
+        let max_events = max_events.map_or(DEFAULT_MAX_EVENTS, |n| n.max(1) as usize);
         Self {
-            events: VecDeque::with_capacity(MAX_EVENTS),
-            output_lines: VecDeque::with_capacity(MAX_OUTPUT_LINES),
+            events: VecDeque::with_capacity(max_events),
+            output_lines: VecDeque::with_capacity(max_output_lines),
+            max_events,
+            max_output_lines,
             line_buffer: String::new(),
+            discarding_line: false,
             session_id: String::new(),
             state: "idle".to_string(),
             last_event_at: 0,
             error_count: 0,
             total_events: 0,
+            tool_use_count: 0,
+            assistant_turns: 0,
+            first_event_at: 0,
+            token_stats: TokenStats::default(),
+            pricing: (0.0, 0.0, 0.0),
+            health_timeout_ms: DEFAULT_HEALTH_TIMEOUT_MS,
+            repeat_threshold: DEFAULT_REPEAT_THRESHOLD,
+            repeat_count: 0,
         }
     }
 
     /// Parse a single NDJSON line. Returns a ParsedEvent if the line is valid JSON,
     /// or None if it's plain text (which gets added to the output buffer).
+    /// Events are stamped with their own `timestamp` field when present.
     #[napi]
     pub fn parse_line(&mut self, line: String) -> Option<ParsedEvent> {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            return None;
-        }
-
         let now = chrono::Utc::now().timestamp_millis();
-        self.last_event_at = now;
-
-        match serde_json::from_str::<RawEvent>(trimmed) {
-            Ok(raw) => {
-                let event = self.process_raw_event(raw, now);
-                self.push_event(event.clone());
-                Some(event)
-            }
-            Err(_) => {
-                // Not JSON — treat as plain text output
-                self.push_output(trimmed.to_string());
-                None
-            }
-        }
+        self.parse_line_at(&line, now)
     }
 
     /// Parse a batch of NDJSON lines (newline-separated chunk from stdout).
-    /// Returns all successfully parsed events.
+    /// Returns all successfully parsed events. When replaying logs, pass
+    /// `ingest_timestamp_ms` to stamp events that carry no timestamp of their own.
     #[napi]
-    pub fn parse_batch(&mut self, chunk: String) -> Vec<ParsedEvent> {
+    pub fn parse_batch(&mut self, chunk: String, ingest_timestamp_ms: Option<i64>) -> Vec<ParsedEvent> {
         let mut results = Vec::new();
+        self.parse_batch_each(chunk, ingest_timestamp_ms, |event| results.push(event.clone()));
+        results
+    }
 
-        // Prepend any leftover data from previous chunk
-        let data = if self.line_buffer.is_empty() {
-            chunk
-        } else {
-            let mut combined = std::mem::take(&mut self.line_buffer);
-            combined.push_str(&chunk);
-            combined
-        };
-
-        let mut lines: Vec<&str> = data.split('\n').collect();
-
-        // Last element might be incomplete — save for next chunk
-        if let Some(last) = lines.pop() {
-            if !last.is_empty() {
-                self.line_buffer = last.to_string();
-            }
-        }
-
-        for line in lines {
-            if let Some(event) = self.parse_line(line.to_string()) {
-                results.push(event);
+    /// Like `parse_batch`, but hands each parsed event to `callback` instead
+    /// of returning them. Returns the number of calls that were queued.
+    ///
+    /// Calls are queued in non-blocking mode. When this method is called from
+    /// the JS thread, as it normally is, the callbacks only run after it has
+    /// returned and the whole batch has been parsed, so parser state is
+    /// already up to date when they run.
+    ///
+    /// The callback receives the event as its only argument. Exceptions it
+    /// throws are not caught here: they are reported as an
+    /// `uncaughtException` on `process`, which terminates Node unless a
+    /// handler is installed, so catch errors inside the callback.
+    ///
+    /// ```js
+    /// const parser = new LogStreamParser()
+    /// const queued = parser.parseBatchWithCallback(chunk, null, (event) => {
+    ///   try {
+    ///     if (event.isError) reportError(event.errorKind, event.text)
+    ///   } catch (err) {
+    ///     console.error(err)
+    ///   }
+    /// })
+    /// ```
+    #[napi(
+        ts_args_type = "chunk: string, ingestTimestampMs: number | undefined | null, callback: (event: ParsedEvent) => void"
+    )]
+    pub fn parse_batch_with_callback(
+        &mut self,
+        chunk: String,
+        ingest_timestamp_ms: Option<i64>,
+        callback: ThreadsafeFunction<ParsedEvent, ErrorStrategy::Fatal>,
+    ) -> u32 {
+        let mut dispatched: u32 = 0;
+        self.parse_batch_each(chunk, ingest_timestamp_ms, |event| {
+            let status = callback.call(event.clone(), ThreadsafeFunctionCallMode::NonBlocking);
+            if status == napi::Status::Ok {
+                dispatched += 1;
             }
-        }
-
-        results
+        });
+        dispatched
     }
 
     /// Get the current health signal
@@ -177,18 +279,40 @@ impl LogStreamParser {
             0
         };
 
-        // Consider unhealthy if no event for 60s and state is working
-        let is_healthy = ms_since < 60_000 || self.state != "working";
+        // Consider unhealthy if no event within the timeout and state is working,
+        // or if the worker keeps printing the same line
+        let reason = if ms_since >= self.health_timeout_ms && self.state == "working" {
+            format!("no events for {}ms", ms_since)
+        } else if self.repeat_threshold > 0 && self.repeat_count >= self.repeat_threshold {
+            format!("same output line repeated {} times", self.repeat_count)
+        } else {
+            String::new()
+        };
 
         HealthSignal {
             state: self.state.clone(),
             ms_since_last_event: ms_since,
             error_count: self.error_count,
             total_events: self.total_events,
-            is_healthy,
+            is_healthy: reason.is_empty(),
+            reason,
         }
     }
 
+    /// Set how long a working worker may go without events before it is
+    /// reported unhealthy (default 60000)
+    #[napi]
+    pub fn set_health_timeout_ms(&mut self, ms: i64) {
+        self.health_timeout_ms = ms;
+    }
+
+    /// Set how many identical consecutive output lines mark the worker as
+    /// stuck (default 20, 0 disables)
+    #[napi]
+    pub fn set_repeat_threshold(&mut self, lines: u32) {
+        self.repeat_threshold = lines;
+    }
+
     /// Get recent output lines (up to `limit`)
     #[napi]
     pub fn get_recent_output(&self, limit: Option<u32>) -> Vec<String> {
@@ -201,26 +325,209 @@ impl LogStreamParser {
         self.output_lines.iter().skip(start).cloned().collect()
     }
 
+    /// Get recent parsed events, oldest first (up to `limit`)
+    #[napi]
+    pub fn get_recent_events(&self, limit: Option<u32>) -> Vec<ParsedEvent> {
+        let limit = limit.unwrap_or(100) as usize;
+        let start = self.events.len().saturating_sub(limit);
+        self.events.iter().skip(start).cloned().collect()
+    }
+
+    /// Get all buffered events of the given type, oldest first
+    #[napi]
+    pub fn get_events_by_type(&self, event_type: String) -> Vec<ParsedEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.event_type == event_type)
+            .cloned()
+            .collect()
+    }
+
+    /// Get all buffered error events, oldest first
+    #[napi]
+    pub fn get_errors(&self) -> Vec<ParsedEvent> {
+        self.events.iter().filter(|e| e.is_error).cloned().collect()
+    }
+
     /// Get detected session ID
     #[napi]
     pub fn get_session_id(&self) -> String {
         self.session_id.clone()
     }
 
+    /// Get all stream statistics in one call
+    #[napi]
+    pub fn get_summary(&self) -> StreamSummary {
+        let uptime_ms = if self.first_event_at > 0 {
+            chrono::Utc::now().timestamp_millis() - self.first_event_at
+        } else {
+            0
+        };
+
+        StreamSummary {
+            total_events: self.total_events,
+            error_count: self.error_count,
+            tool_use_count: self.tool_use_count,
+            assistant_turns: self.assistant_turns,
+            session_id: self.session_id.clone(),
+            state: self.state.clone(),
+            uptime_ms,
+        }
+    }
+
+    /// Clear all stream state so the parser can be reused for a restarted
+    /// worker. Configuration (pricing, health timeout) is kept.
+    #[napi]
+    pub fn reset(&mut self) {
+        self.events.clear();
+        self.output_lines.clear();
+        self.line_buffer.clear();
+        self.discarding_line = false;
+        self.session_id.clear();
+        self.state = "idle".to_string();
+        self.last_event_at = 0;
+        self.error_count = 0;
+        self.total_events = 0;
+        self.tool_use_count = 0;
+        self.assistant_turns = 0;
+        self.first_event_at = 0;
+        self.token_stats = TokenStats::default();
+        self.repeat_count = 0;
+    }
+
     /// Get current worker state
     #[napi]
     pub fn get_state(&self) -> String {
         self.state.clone()
     }
 
+    /// Get the number of tool invocations seen so far
+    #[napi]
+    pub fn get_tool_use_count(&self) -> u32 {
+        self.tool_use_count
+    }
+
+    /// Get token usage accumulated across all result events
+    #[napi]
+    pub fn get_token_stats(&self) -> TokenStats {
+        let (input_rate, output_rate, cache_read_rate) = self.pricing;
+        let stats = &self.token_stats;
+        TokenStats {
+            estimated_cost: stats.input_tokens as f64 * input_rate
+                + stats.output_tokens as f64 * output_rate
+                + stats.cache_read_tokens as f64 * cache_read_rate,
+            ..stats.clone()
+        }
+    }
+
+    /// Set per-token rates used for `TokenStats.estimated_cost`.
+    /// Cache reads are free unless a rate is given.
+    #[napi]
+    pub fn set_pricing(&mut self, input_per_token: f64, output_per_token: f64, cache_read_per_token: Option<f64>) {
+        self.pricing = (input_per_token, output_per_token, cache_read_per_token.unwrap_or(0.0));
+    }
+
     // --- Internal helpers ---
 
-    fn process_raw_event(&mut self, raw: RawEvent, now: i64) -> ParsedEvent {
-        let event_type = raw.event_type.unwrap_or_default();
-        let subtype = raw.subtype.unwrap_or_default();
-        let session_id = raw.session_id.unwrap_or_default();
+    /// Split a chunk into lines (carrying a trailing partial line over to the
+    /// next chunk), parse each, and hand every parsed event to `on_event`
+    fn parse_batch_each(&mut self, chunk: String, ingest_timestamp_ms: Option<i64>, mut on_event: impl FnMut(&ParsedEvent)) {
+        // Skip the rest of a line that already overflowed the buffer
+        let chunk = if self.discarding_line {
+            match chunk.find('\n') {
+                Some(end) => {
+                    self.discarding_line = false;
+                    chunk[end + 1..].to_string()
+                }
+                None => return,
+            }
+        } else {
+            chunk
+        };
+
+        // Prepend any leftover data from previous chunk
+        let data = if self.line_buffer.is_empty() {
+            chunk
+        } else {
+            let mut combined = std::mem::take(&mut self.line_buffer);
+            combined.push_str(&chunk);
+            combined
+        };
+
+        let mut lines: Vec<&str> = data.split('\n').collect();
+
+        // Last element might be incomplete — save for next chunk, unless it
+        // has grown past the cap without a newline
+        let mut dropped_bytes = 0;
+        if let Some(last) = lines.pop() {
+            if last.len() > MAX_LINE_BUFFER_BYTES {
+                dropped_bytes = last.len();
+                self.discarding_line = true;
+            } else if !last.is_empty() {
+                self.line_buffer = last.to_string();
+            }
+        }
+
+        let fallback = ingest_timestamp_ms.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        for line in lines {
+            if let Some(event) = self.parse_line_at(line, fallback) {
+                on_event(&event);
+            }
+        }
+
+        if dropped_bytes > 0 {
+            let warning = ParsedEvent {
+                event_type: "warning".to_string(),
+                subtype: "line_buffer_overflow".to_string(),
+                session_id: self.session_id.clone(),
+                text: format!(
+                    "Dropped {} bytes without a newline (limit {} bytes)",
+                    dropped_bytes, MAX_LINE_BUFFER_BYTES
+                ),
+                is_error: false,
+                error_kind: String::new(),
+                tool_name: String::new(),
+                tool_input: String::new(),
+                timestamp: fallback,
+            };
+            self.push_event(warning.clone());
+            on_event(&warning);
+        }
+    }
+
+    /// Parse one line, using `fallback_ms` when the event has no timestamp
+    fn parse_line_at(&mut self, line: &str, fallback_ms: i64) -> Option<ParsedEvent> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+
+        match serde_json::from_str::<RawEvent>(trimmed) {
+            Ok(raw) => {
+                let at = raw.timestamp.as_ref().and_then(parse_timestamp).unwrap_or(fallback_ms);
+                self.last_event_at = at;
+                let event = self.process_raw_event(raw, at);
+                self.push_event(event.clone());
+                Some(event)
+            }
+            Err(_) => {
+                // Not JSON — treat as plain text output
+                self.last_event_at = fallback_ms;
+                self.push_output(trimmed.to_string());
+                None
+            }
+        }
+    }
+
+    fn process_raw_event(&mut self, mut raw: RawEvent, now: i64) -> ParsedEvent {
+        let event_type = raw.event_type.take().unwrap_or_default();
+        let subtype = raw.subtype.take().unwrap_or_default();
+        let session_id = raw.session_id.take().unwrap_or_default();
         let mut text = String::new();
         let mut is_error = false;
+        let mut error_kind = String::new();
+        let mut tool_name = String::new();
+        let mut tool_input = String::new();
 
         // Extract session ID from init events
         if event_type == "system" && subtype == "init" && !session_id.is_empty() {
@@ -231,29 +538,60 @@ impl LogStreamParser {
         // Extract text from assistant message content
         if event_type == "assistant" {
             self.state = "working".to_string();
+            self.assistant_turns += 1;
             if let Some(msg) = &raw.message {
                 if let Some(content) = &msg.content {
                     for c in content {
-                        if c.content_type.as_deref() == Some("text") {
-                            if let Some(t) = &c.text {
-                                text.push_str(t);
-                                self.push_output(t.clone());
+                        match c.content_type.as_deref() {
+                            Some("text") => {
+                                if let Some(t) = &c.text {
+                                    text.push_str(t);
+                                    self.push_output(t.clone());
+                                }
                             }
+                            Some("tool_use") => {
+                                self.tool_use_count += 1;
+                                if tool_name.is_empty() {
+                                    tool_name = c.name.clone().unwrap_or_default();
+                                    tool_input = c.input.as_ref().map(|v| v.to_string()).unwrap_or_default();
+                                }
+                            }
+                            _ => {}
                         }
                     }
                 }
             }
         }
 
+        // Accumulate token usage (top-level on result events, nested in older formats)
+        if event_type == "result" {
+            let usage = raw.usage.as_ref().or_else(|| raw.message.as_ref().and_then(|m| m.usage.as_ref()));
+            if let Some(usage) = usage {
+                self.token_stats.input_tokens += usage.input_tokens.unwrap_or(0);
+                self.token_stats.output_tokens += usage.output_tokens.unwrap_or(0);
+                self.token_stats.cache_read_tokens += usage.cache_read_input_tokens.unwrap_or(0);
+            }
+        }
+
         // Detect errors
-        if event_type == "result" || subtype == "error" {
-            is_error = subtype == "error";
+        if event_type == "result" || subtype.starts_with("error") {
+            is_error = subtype.starts_with("error") || raw.is_error == Some(true);
             if is_error {
                 self.error_count += 1;
+                if text.is_empty() {
+                    text = error_detail(&raw);
+                }
+                let error_type = raw.error.as_ref()
+                    .and_then(|e| e.get("type"))
+                    .and_then(|t| t.as_str());
+                error_kind = classify_error(error_type, &text).to_string();
             }
         }
 
         self.total_events += 1;
+        if self.first_event_at == 0 {
+            self.first_event_at = now;
+        }
 
         ParsedEvent {
             event_type,
@@ -261,32 +599,104 @@ impl LogStreamParser {
             session_id,
             text,
             is_error,
+            error_kind,
+            tool_name,
+            tool_input,
             timestamp: now,
         }
     }
 
     fn push_event(&mut self, event: ParsedEvent) {
-        if self.events.len() >= MAX_EVENTS {
+        if self.events.len() >= self.max_events {
             self.events.pop_front();
         }
         self.events.push_back(event);
     }
 
     fn push_output(&mut self, line: String) {
-        if self.output_lines.len() >= MAX_OUTPUT_LINES {
+        if self.output_lines.back() == Some(&line) {
+            self.repeat_count += 1;
+        } else {
+            self.repeat_count = 1;
+        }
+        if self.output_lines.len() >= self.max_output_lines {
             self.output_lines.pop_front();
         }
         self.output_lines.push_back(line);
     }
 }
 
+/// Pull the most specific error text out of a raw event: message content,
+/// then the result string, then the `error` field.
+fn error_detail(raw: &RawEvent) -> String {
+    let content_text: String = raw.message.iter()
+        .flat_map(|m| m.content.iter().flatten())
+        .filter_map(|c| c.text.as_deref())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !content_text.is_empty() {
+        return content_text;
+    }
+    if let Some(result) = raw.result.as_ref().filter(|r| !r.is_empty()) {
+        return result.clone();
+    }
+    match &raw.error {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(value) => value.get("message")
+            .and_then(|m| m.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string()),
+        None => String::new(),
+    }
+}
+
+/// Read an event timestamp given as epoch milliseconds or an RFC 3339 string
+fn parse_timestamp(value: &serde_json::Value) -> Option<i64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+        serde_json::Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|dt| dt.timestamp_millis()),
+        _ => None,
+    }
+}
+
+/// Classify an error into a coarse triage bucket, trusting the structured
+/// `error.type` when it is one we know and falling back to the error text
+fn classify_error(error_type: Option<&str>, text: &str) -> &'static str {
+    match error_type {
+        Some("rate_limit_error" | "overloaded_error") => return "rate_limit",
+        Some("timeout_error") => return "timeout",
+        Some("tool_error") => return "tool_error",
+        _ => {}
+    }
+
+    // Match whole words only, so "4290" is not a 429 and "toolchain" is not a tool
+    let lower = text.to_lowercase();
+    let words: Vec<&str> =
+        lower.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    let has = |phrase: &str| {
+        let phrase: Vec<&str> = phrase.split(' ').collect();
+        words.windows(phrase.len()).any(|w| w == phrase.as_slice())
+    };
+    if ["rate limit", "429", "overloaded", "too many requests"].iter().any(|p| has(p)) {
+        "rate_limit"
+    } else if ["timeout", "timed out", "deadline exceeded"].iter().any(|p| has(p)) {
+        "timeout"
+    } else if has("tool") {
+        "tool_error"
+    } else {
+        "unknown"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_parse_system_init() {
-        let mut parser = LogStreamParser::new();
+        let mut parser = LogStreamParser::new(None, None);
         let line = r#"{"type":"system","subtype":"init","session_id":"abc123"}"#;
         let event = parser.parse_line(line.to_string()).unwrap();
         assert_eq!(event.event_type, "system");
@@ -297,7 +707,7 @@ mod tests {
 
     #[test]
     fn test_parse_assistant_message() {
-        let mut parser = LogStreamParser::new();
+        let mut parser = LogStreamParser::new(None, None);
         let line = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Hello world"}]}}"#;
         let event = parser.parse_line(line.to_string()).unwrap();
         assert_eq!(event.event_type, "assistant");
@@ -305,9 +715,39 @@ mod tests {
         assert_eq!(parser.get_state(), "working");
     }
 
+    #[test]
+    fn test_parse_tool_use() {
+        let mut parser = LogStreamParser::new(None, None);
+        let line = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Reading"},{"type":"tool_use","id":"t1","name":"Read","input":{"file_path":"/tmp/a.rs"}}]}}"#;
+        let event = parser.parse_line(line.to_string()).unwrap();
+        assert_eq!(event.text, "Reading");
+        assert_eq!(event.tool_name, "Read");
+        assert_eq!(event.tool_input, r#"{"file_path":"/tmp/a.rs"}"#);
+        assert_eq!(parser.get_tool_use_count(), 1);
+
+        let plain = parser.parse_line(r#"{"type":"assistant","message":{"content":[{"type":"text","text":"done"}]}}"#.to_string()).unwrap();
+        assert!(plain.tool_name.is_empty());
+        assert_eq!(parser.get_tool_use_count(), 1);
+    }
+
+    #[test]
+    fn test_token_stats() {
+        let mut parser = LogStreamParser::new(None, None);
+        parser.set_pricing(0.000003, 0.000015, None);
+        let line = r#"{"type":"result","subtype":"success","usage":{"input_tokens":1000,"output_tokens":200,"cache_read_input_tokens":5000}}"#;
+        parser.parse_line(line.to_string());
+        parser.parse_line(r#"{"type":"result","subtype":"success","message":{"usage":{"input_tokens":500}}}"#.to_string());
+
+        let stats = parser.get_token_stats();
+        assert_eq!(stats.input_tokens, 1500);
+        assert_eq!(stats.output_tokens, 200);
+        assert_eq!(stats.cache_read_tokens, 5000);
+        assert!((stats.estimated_cost - 0.0075).abs() < 1e-12);
+    }
+
     #[test]
     fn test_parse_plain_text() {
-        let mut parser = LogStreamParser::new();
+        let mut parser = LogStreamParser::new(None, None);
         let result = parser.parse_line("just some text".to_string());
         assert!(result.is_none());
         let output = parser.get_recent_output(None);
@@ -317,33 +757,265 @@ mod tests {
 
     #[test]
     fn test_parse_batch() {
-        let mut parser = LogStreamParser::new();
+        let mut parser = LogStreamParser::new(None, None);
         let chunk = r#"{"type":"system","subtype":"init","session_id":"s1"}
 {"type":"assistant","message":{"content":[{"type":"text","text":"hi"}]}}
 plain text
 "#;
-        let events = parser.parse_batch(chunk.to_string());
+        let events = parser.parse_batch(chunk.to_string(), None);
         assert_eq!(events.len(), 2);
         assert_eq!(parser.get_session_id(), "s1");
     }
 
+    #[test]
+    fn test_event_history() {
+        let mut parser = LogStreamParser::new(None, None);
+        let chunk = r#"{"type":"system","subtype":"init","session_id":"s1"}
+{"type":"assistant","message":{"content":[{"type":"text","text":"one"}]}}
+{"type":"assistant","message":{"content":[{"type":"text","text":"two"}]}}
+{"type":"result","subtype":"success"}
+"#;
+        parser.parse_batch(chunk.to_string(), None);
+
+        let recent = parser.get_recent_events(Some(2));
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].text, "two");
+        assert_eq!(recent[1].event_type, "result");
+        assert_eq!(parser.get_recent_events(None).len(), 4);
+
+        let turns: Vec<String> = parser.get_events_by_type("assistant".into()).into_iter().map(|e| e.text).collect();
+        assert_eq!(turns, vec!["one", "two"]);
+
+        for _ in 0..DEFAULT_MAX_EVENTS {
+            parser.parse_line(r#"{"type":"result"}"#.to_string());
+        }
+        assert_eq!(parser.get_recent_events(Some(u32::MAX)).len(), DEFAULT_MAX_EVENTS);
+        assert!(parser.get_events_by_type("assistant".into()).is_empty());
+    }
+
+    #[test]
+    fn test_error_classification() {
+        let mut parser = LogStreamParser::new(None, None);
+        let chunk = r#"{"type":"result","subtype":"error","error":{"type":"rate_limit_error","message":"Rate limit exceeded, retry later"}}
+{"type":"result","subtype":"success"}
+{"type":"result","subtype":"error_during_execution","is_error":true,"result":"Request timed out after 600s"}
+{"type":"result","subtype":"error","message":{"content":[{"type":"text","text":"Tool Bash failed: exit code 1"}]}}
+{"type":"result","subtype":"error","error":"segfault"}
+"#;
+        parser.parse_batch(chunk.to_string(), None);
+
+        let errors = parser.get_errors();
+        let kinds: Vec<&str> = errors.iter().map(|e| e.error_kind.as_str()).collect();
+        assert_eq!(kinds, vec!["rate_limit", "timeout", "tool_error", "unknown"]);
+        assert_eq!(errors[0].text, "Rate limit exceeded, retry later");
+        assert_eq!(errors[1].text, "Request timed out after 600s");
+        assert_eq!(errors[3].text, "segfault");
+        assert_eq!(parser.get_health_signal().error_count, 4);
+        assert!(parser.get_events_by_type("result".into())[1].error_kind.is_empty());
+
+        // Substrings of longer words do not count, and a known error.type wins over the text
+        assert_eq!(classify_error(None, "exit code 4290"), "unknown");
+        assert_eq!(classify_error(None, "toolchain not installed"), "unknown");
+        assert_eq!(classify_error(None, "HTTP 429: slow down"), "rate_limit");
+        assert_eq!(classify_error(None, "hit rate_limit"), "rate_limit");
+        assert_eq!(classify_error(Some("overloaded_error"), "Tool failed"), "rate_limit");
+        assert_eq!(classify_error(Some("api_error"), "Tool failed"), "tool_error");
+    }
+
+    #[test]
+    fn test_event_timestamps() {
+        let mut parser = LogStreamParser::new(None, None);
+        let chunk = r#"{"type":"system","subtype":"init","session_id":"s1","timestamp":"2024-01-01T00:00:00Z"}
+{"type":"assistant","timestamp":1704067205000,"message":{"content":[{"type":"text","text":"hi"}]}}
+{"type":"result","subtype":"success"}
+"#;
+        let events = parser.parse_batch(chunk.to_string(), Some(1_704_067_210_000));
+        assert_eq!(events[0].timestamp, 1_704_067_200_000);
+        assert_eq!(events[1].timestamp, 1_704_067_205_000);
+        assert_eq!(events[2].timestamp, 1_704_067_210_000);
+
+        // Log time, not processing time, drives the idle gap
+        let health = parser.get_health_signal();
+        assert!(health.ms_since_last_event >= chrono::Utc::now().timestamp_millis() - 1_704_067_210_000);
+
+        let live = parser.parse_line(r#"{"type":"result"}"#.to_string()).unwrap();
+        assert!(live.timestamp > 1_704_067_210_000);
+    }
+
     #[test]
     fn test_health_signal() {
-        let parser = LogStreamParser::new();
+        let parser = LogStreamParser::new(None, None);
         let health = parser.get_health_signal();
         assert_eq!(health.state, "idle");
         assert!(health.is_healthy);
         assert_eq!(health.error_count, 0);
     }
 
+    #[test]
+    fn test_health_timeout() {
+        let mut parser = LogStreamParser::new(None, None);
+        parser.set_health_timeout_ms(50);
+        parser.parse_line(r#"{"type":"assistant","message":{"content":[{"type":"text","text":"hi"}]}}"#.to_string());
+        assert!(parser.get_health_signal().is_healthy);
+
+        std::thread::sleep(std::time::Duration::from_millis(80));
+        let health = parser.get_health_signal();
+        assert_eq!(health.state, "working");
+        assert!(!health.is_healthy);
+    }
+
+    #[test]
+    fn test_repeated_output_is_unhealthy() {
+        let mut parser = LogStreamParser::new(None, None);
+        parser.set_repeat_threshold(5);
+        for _ in 0..4 {
+            parser.parse_line("Retrying...".to_string());
+        }
+        assert!(parser.get_health_signal().is_healthy);
+
+        parser.parse_line("Retrying...".to_string());
+        let health = parser.get_health_signal();
+        assert!(!health.is_healthy);
+        assert_eq!(health.reason, "same output line repeated 5 times");
+
+        parser.parse_line("Connected".to_string());
+        let health = parser.get_health_signal();
+        assert!(health.is_healthy);
+        assert!(health.reason.is_empty());
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut parser = LogStreamParser::new(None, None);
+        assert_eq!(parser.get_summary().uptime_ms, 0);
+
+        let chunk = r#"{"type":"system","subtype":"init","session_id":"s1"}
+{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Bash","input":{"command":"ls"}}]}}
+{"type":"assistant","message":{"content":[{"type":"text","text":"done"}]}}
+{"type":"result","subtype":"error","result":"boom"}
+"#;
+        let started = chrono::Utc::now().timestamp_millis() - 5_000;
+        parser.parse_batch(chunk.to_string(), Some(started));
+
+        let summary = parser.get_summary();
+        assert_eq!(summary.total_events, 4);
+        assert_eq!(summary.error_count, 1);
+        assert_eq!(summary.tool_use_count, 1);
+        assert_eq!(summary.assistant_turns, 2);
+        assert_eq!(summary.session_id, "s1");
+        assert_eq!(summary.state, "working");
+        assert!(summary.uptime_ms >= 5_000);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut parser = LogStreamParser::new(None, None);
+        let chunk = r#"{"type":"system","subtype":"init","session_id":"s1"}
+{"type":"assistant","message":{"content":[{"type":"text","text":"hi"}]}}
+{"type":"result","subtype":"error","result":"boom"}
+{"type":"assis"#;
+        parser.parse_batch(chunk.to_string(), None);
+        assert_eq!(parser.get_health_signal().total_events, 3);
+
+        parser.reset();
+        let health = parser.get_health_signal();
+        assert_eq!(health.state, "idle");
+        assert_eq!(health.ms_since_last_event, 0);
+        assert_eq!(health.error_count, 0);
+        assert_eq!(health.total_events, 0);
+        assert!(health.is_healthy);
+        assert_eq!(parser.get_session_id(), "");
+        assert!(parser.get_recent_output(None).is_empty());
+        assert!(parser.get_recent_events(None).is_empty());
+
+        // The partial line from before the reset must not leak into new input
+        let events = parser.parse_batch("{\"type\":\"result\"}\n".to_string(), None);
+        assert_eq!(events.len(), 1);
+    }
+
     #[test]
     fn test_ring_buffer_eviction() {
-        let mut parser = LogStreamParser::new();
+        let mut parser = LogStreamParser::new(None, None);
         for i in 0..1100 {
             parser.push_output(format!("line {}", i));
         }
-        assert_eq!(parser.output_lines.len(), MAX_OUTPUT_LINES);
+        assert_eq!(parser.output_lines.len(), DEFAULT_MAX_OUTPUT_LINES);
         let output = parser.get_recent_output(Some(5));
         assert_eq!(output.len(), 5);
     }
+
+    #[test]
+    fn test_configurable_buffer_sizes() {
+        let mut parser = LogStreamParser::new(Some(3), Some(2));
+        for i in 0..5 {
+            parser.parse_line(format!("line {}", i));
+            parser.parse_line(format!(r#"{{"type":"assistant","message":{{"content":[{{"type":"text","text":"turn {}"}}]}}}}"#, i));
+        }
+
+        let output = parser.get_recent_output(None);
+        assert_eq!(output, vec!["turn 3", "line 4", "turn 4"]);
+        let events = parser.get_recent_events(None);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].text, "turn 3");
+        assert_eq!(events[1].text, "turn 4");
+    }
+
+    #[test]
+    fn test_parse_batch_each_delivers_events_in_order() {
+        let mut parser = LogStreamParser::new(None, None);
+        let mut seen: Vec<String> = Vec::new();
+        parser.parse_batch_each(
+            "{\"type\":\"system\",\"subtype\":\"init\",\"session_id\":\"s1\"}\nnoise\n{\"type\":\"res".to_string(),
+            None,
+            |event| seen.push(event.event_type.clone()),
+        );
+        parser.parse_batch_each("ult\"}\n".to_string(), None, |event| seen.push(event.event_type.clone()));
+
+        assert_eq!(seen, vec!["system", "result"]);
+        assert_eq!(parser.get_session_id(), "s1");
+        assert_eq!(parser.get_recent_output(None), vec!["noise"]);
+    }
+
+    #[test]
+    fn test_line_buffer_is_capped() {
+        let mut parser = LogStreamParser::new(None, None);
+
+        // A partial object spread over several chunks is still stitched together
+        parser.parse_batch("{\"type\":".to_string(), None);
+        parser.parse_batch("\"system\",\"subtype\":".to_string(), None);
+        let events = parser.parse_batch("\"init\",\"session_id\":\"s1\"}\n".to_string(), None);
+        assert_eq!(events.len(), 1);
+        assert_eq!(parser.get_session_id(), "s1");
+
+        let half = "x".repeat(MAX_LINE_BUFFER_BYTES / 2 + 1);
+        assert!(parser.parse_batch(half.clone(), None).is_empty());
+        assert_eq!(parser.line_buffer.len(), half.len());
+
+        let events = parser.parse_batch(half, None);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "warning");
+        assert_eq!(events[0].subtype, "line_buffer_overflow");
+        assert!(parser.line_buffer.is_empty());
+
+        // More of the same overflowing line is swallowed without another warning
+        let giant = "y".repeat(2 * MAX_LINE_BUFFER_BYTES);
+        assert!(parser.parse_batch(giant, None).is_empty());
+        assert!(parser.line_buffer.is_empty());
+
+        // The tail of the overflowing line is dropped too, and the stream
+        // recovers on the next complete line
+        let output_before = parser.get_recent_output(None).len();
+        let chunk = "tail {\"type\":\"x\"}\n{\"type\":\"result\"}\n".to_string();
+        let events = parser.parse_batch(chunk, None);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "result");
+        assert_eq!(parser.get_recent_output(None).len(), output_before);
+
+        // A fresh giant line is dropped even when it arrives in one chunk
+        let events = parser.parse_batch("z".repeat(MAX_LINE_BUFFER_BYTES + 1), None);
+        assert_eq!(events[0].subtype, "line_buffer_overflow");
+        let events = parser.parse_batch("zz\nplain\n".to_string(), None);
+        assert!(events.is_empty());
+        assert_eq!(parser.get_recent_output(Some(1)), vec!["plain"]);
+    }
 }
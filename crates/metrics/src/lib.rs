@@ -1,35 +1,50 @@
 //! High-performance metrics aggregation engine.
 //!
 //! Provides native histogram observation, percentile computation,
-//! sliding window rate counters, and time-series downsampling.
+//! sliding window rate counters, time-series downsampling,
+//! Prometheus text-format export, engine state snapshots, and a
+//! lock-protected engine for concurrent use.
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
-use serde::Serialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
 
 const DEFAULT_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
 
+/// Default t-digest compression, keeping at most about 100 centroids
+const DEFAULT_COMPRESSION: u32 = 200;
+
+/// Largest accepted t-digest compression
+const MAX_COMPRESSION: u32 = 10_000;
+
 // ============================================================================
 // HISTOGRAM
 // ============================================================================
 
-/// Bucket-based histogram with O(log n) observation and O(1) percentile
+/// Bucket-based histogram with a t-digest for percentiles
 #[napi]
 pub struct MetricsHistogram {
     buckets: Vec<f64>,
     counts: Vec<u64>,
     sum: f64,
     count: u64,
-    /// Sorted observations for exact percentile (bounded by max_samples)
-    samples: Vec<f64>,
-    max_samples: usize,
+    /// Percentile sketch; memory stays bounded by its compression
+    digest: TDigest,
 }
 
 #[napi]
 impl MetricsHistogram {
+    /// `compression` (default 200) is the t-digest compression. It bounds
+    /// the digest to about `compression / 2` centroids plus an unmerged
+    /// buffer of `4 * compression` observations; series much smaller than
+    /// that keep every observation, so their percentiles are exact. This
+    /// argument used to be a raw sample cap (`max_samples`, default 10,000);
+    /// values sized for that keep far more centroids than needed. Values
+    /// above 10,000 are clamped to it.
     #[napi(constructor)]
-    pub fn new(buckets: Option<Vec<f64>>, max_samples: Option<u32>) -> Self {
+    pub fn new(buckets: Option<Vec<f64>>, compression: Option<u32>) -> Self {
         let mut b = buckets.unwrap_or_else(|| DEFAULT_BUCKETS.to_vec());
         b.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
         let counts = vec![0u64; b.len() + 1]; // +1 for +Inf
@@ -38,73 +53,227 @@ impl MetricsHistogram {
             counts,
             sum: 0.0,
             count: 0,
-            samples: Vec::new(),
-            max_samples: max_samples.unwrap_or(10_000) as usize,
+            digest: TDigest::new(
+                compression.unwrap_or(DEFAULT_COMPRESSION).clamp(1, MAX_COMPRESSION) as f64,
+            ),
         }
     }
 
     /// Record an observation
     #[napi]
     pub fn observe(&mut self, value: f64) {
-        self.sum += value;
-        self.count += 1;
-
-        // Bucket counting
-        let mut placed = false;
-        for (i, &boundary) in self.buckets.iter().enumerate() {
-            if value <= boundary {
-                self.counts[i] += 1;
-                placed = true;
-                break;
-            }
-        }
-        if !placed {
-            // +Inf bucket
-            *self.counts.last_mut().unwrap() += 1;
-        }
+        self.observe_many(value, 1);
+    }
 
-        // Sample reservoir for exact percentiles
-        if self.samples.len() < self.max_samples {
-            self.samples.push(value);
+    /// Record `value` as occurring `count` times, for pre-aggregated data.
+    /// The t-digest receives it once as a single weighted entry, so percentiles
+    /// reflect the full multiplicity without `count` separate insertions.
+    #[napi]
+    pub fn observe_many(&mut self, value: f64, count: u32) {
+        if count == 0 {
+            return;
         }
+        self.sum += value * count as f64;
+        self.count += count as u64;
+
+        // Bucket counting; values above every boundary land in +Inf
+        let idx = self.buckets.iter().position(|&boundary| value <= boundary)
+            .unwrap_or(self.buckets.len());
+        self.counts[idx] += count as u64;
+
+        self.digest.add(value, count as f64);
     }
 
     /// Get a specific percentile (0.0 to 1.0)
     #[napi]
     pub fn percentile(&mut self, p: f64) -> f64 {
-        if self.samples.is_empty() {
-            return 0.0;
-        }
-        self.samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        let idx = ((p * self.samples.len() as f64) as usize).min(self.samples.len() - 1);
-        self.samples[idx]
+        self.digest.quantile(p)
     }
 
     /// Get p50, p95, p99 in one call
     #[napi]
     pub fn get_percentiles(&mut self) -> PercentileSnapshot {
-        if self.samples.is_empty() {
+        if self.count == 0 {
             return PercentileSnapshot { p50: 0.0, p95: 0.0, p99: 0.0, mean: 0.0, count: 0, sum: 0.0 };
         }
-        self.samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        let len = self.samples.len();
         PercentileSnapshot {
-            p50: self.samples[(0.5 * len as f64) as usize],
-            p95: self.samples[((0.95 * len as f64) as usize).min(len - 1)],
-            p99: self.samples[((0.99 * len as f64) as usize).min(len - 1)],
+            p50: self.digest.quantile(0.5),
+            p95: self.digest.quantile(0.95),
+            p99: self.digest.quantile(0.99),
             mean: if self.count > 0 { self.sum / self.count as f64 } else { 0.0 },
             count: self.count as i64,
             sum: self.sum,
         }
     }
 
+    /// Add another histogram's observations into this one, e.g. to combine
+    /// per-shard metrics. Bucket boundaries must be identical.
+    #[napi]
+    pub fn merge(&mut self, other: &MetricsHistogram) -> Result<()> {
+        if self.buckets != other.buckets {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "Cannot merge histograms with different bucket boundaries".to_string(),
+            ));
+        }
+
+        for (count, other_count) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other_count;
+        }
+        self.sum += other.sum;
+        self.count += other.count;
+        for centroid in other.digest.centroids.iter().chain(&other.digest.buffer) {
+            self.digest.add(centroid.mean, centroid.weight);
+        }
+        Ok(())
+    }
+
     /// Reset all counters
     #[napi]
     pub fn reset(&mut self) {
         self.counts.fill(0);
         self.sum = 0.0;
         self.count = 0;
-        self.samples.clear();
+        self.digest.clear();
+    }
+
+    /// Cumulative bucket counts for charting, Prometheus-style
+    #[napi]
+    pub fn get_buckets(&self) -> Vec<HistogramBucket> {
+        self.bucket_snapshot()
+            .into_iter()
+            .map(|(le, count)| HistogramBucket { le, count: count as i64 })
+            .collect()
+    }
+}
+
+impl MetricsHistogram {
+    /// Each upper boundary paired with the cumulative count of observations
+    /// at or below it, ending with `(+Inf, count)`.
+    pub fn bucket_snapshot(&self) -> Vec<(f64, u64)> {
+        let mut cumulative: u64 = 0;
+        let mut snapshot: Vec<(f64, u64)> = self.buckets.iter()
+            .zip(&self.counts)
+            .map(|(&boundary, count)| {
+                cumulative += count;
+                (boundary, cumulative)
+            })
+            .collect();
+        snapshot.push((f64::INFINITY, self.count));
+        snapshot
+    }
+}
+
+/// A weighted cluster of observations in the t-digest
+#[derive(Clone, Copy, Debug)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Merging t-digest (Dunning & Ertl) with the k1 scale function.
+/// Observations are buffered and periodically merged into centroids whose
+/// maximum weight shrinks towards the tails, so extreme percentiles stay
+/// accurate while at most about `compression / 2` centroids are kept.
+#[derive(Clone, Debug)]
+struct TDigest {
+    compression: f64,
+    /// Merged centroids, sorted by mean
+    centroids: Vec<Centroid>,
+    /// Unmerged observations
+    buffer: Vec<Centroid>,
+    /// Buffer length that triggers a merge
+    buffer_limit: usize,
+}
+
+impl TDigest {
+    fn new(compression: f64) -> Self {
+        let buffer_limit = (compression as usize).max(64).saturating_mul(4);
+        Self { compression, centroids: Vec::new(), buffer: Vec::new(), buffer_limit }
+    }
+
+    fn add(&mut self, value: f64, weight: f64) {
+        self.buffer.push(Centroid { mean: value, weight });
+        if self.buffer.len() >= self.buffer_limit {
+            self.flush();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.centroids.clear();
+        self.buffer.clear();
+    }
+
+    fn total_weight(&self) -> f64 {
+        self.centroids.iter().chain(&self.buffer).map(|c| c.weight).sum()
+    }
+
+    /// Merge buffered observations into the centroid list
+    fn flush(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut items: Vec<Centroid> = std::mem::take(&mut self.centroids);
+        items.append(&mut self.buffer);
+        items.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap_or(std::cmp::Ordering::Equal));
+
+        // k1(q) = δ/2π · asin(2q − 1); a centroid may span one unit of k
+        let delta = self.compression;
+        let k = |q: f64| delta / (2.0 * std::f64::consts::PI) * (2.0 * q - 1.0).asin();
+        let k_inverse = |k: f64| ((k * 2.0 * std::f64::consts::PI / delta).sin() + 1.0) / 2.0;
+
+        let total: f64 = items.iter().map(|c| c.weight).sum();
+        let mut merged: Vec<Centroid> = Vec::with_capacity(items.len());
+        let mut weight_before = 0.0;
+        let mut q_limit = k_inverse(k(0.0) + 1.0);
+        let mut current = items[0];
+
+        for item in items.into_iter().skip(1) {
+            let q = (weight_before + current.weight + item.weight) / total;
+            if q <= q_limit {
+                let weight = current.weight + item.weight;
+                current.mean += (item.mean - current.mean) * item.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_before += current.weight;
+                merged.push(current);
+                q_limit = k_inverse(k(weight_before / total) + 1.0);
+                current = item;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Value at quantile `p` (0.0 to 1.0) by linear interpolation at rank
+    /// `p * (n - 1)`. A centroid of weight w occupies ranks `[c, c + w - 1]`
+    /// and reports its mean there; ranks between two centroids interpolate
+    /// between their means. With single-observation centroids this is the
+    /// usual interpolated percentile over the sorted samples.
+    fn quantile(&mut self, p: f64) -> f64 {
+        self.flush();
+        let Some(first) = self.centroids.first() else {
+            return 0.0;
+        };
+
+        let rank = p.clamp(0.0, 1.0) * (self.total_weight() - 1.0).max(0.0);
+        let mut prev_end = 0.0;
+        let mut prev_mean = first.mean;
+        let mut start = 0.0;
+        for (i, centroid) in self.centroids.iter().enumerate() {
+            let end = start + centroid.weight - 1.0;
+            if i > 0 && rank < start {
+                let t = (rank - prev_end) / (start - prev_end);
+                return prev_mean + t * (centroid.mean - prev_mean);
+            }
+            if rank <= end {
+                return centroid.mean;
+            }
+            prev_end = end;
+            prev_mean = centroid.mean;
+            start += centroid.weight;
+        }
+        prev_mean
     }
 }
 
@@ -119,6 +288,14 @@ pub struct PercentileSnapshot {
     pub sum: f64,
 }
 
+/// One cumulative histogram bucket; `le` is `Infinity` for the final bucket
+#[napi(object)]
+#[derive(Clone, Debug, Serialize)]
+pub struct HistogramBucket {
+    pub le: f64,
+    pub count: i64,
+}
+
 // ============================================================================
 // SLIDING WINDOW COUNTER
 // ============================================================================
@@ -158,8 +335,20 @@ impl SlidingWindowCounter {
     /// Increment the counter at the current time
     #[napi]
     pub fn increment(&mut self, now_ms: i64) {
+        self.add(now_ms, 1);
+    }
+
+    /// Add `delta` (possibly negative) to the current bucket, e.g. for batched
+    /// events. A bucket never drops below zero.
+    #[napi]
+    pub fn add(&mut self, now_ms: i64, delta: i64) {
         self.advance_to(now_ms);
-        self.buckets[self.head] += 1;
+        let bucket = &mut self.buckets[self.head];
+        *bucket = if delta >= 0 {
+            bucket.saturating_add(delta as u64)
+        } else {
+            bucket.saturating_sub(delta.unsigned_abs())
+        };
     }
 
     /// Get the current rate (events per second) over the window
@@ -198,6 +387,14 @@ impl SlidingWindowCounter {
         total as i64
     }
 
+    /// Clear all buckets
+    #[napi]
+    pub fn reset(&mut self) {
+        self.buckets.fill(0);
+        self.timestamps.fill(0);
+        self.head = 0;
+    }
+
     fn advance_to(&mut self, now_ms: i64) {
         let current_bucket_ts = self.timestamps[self.head];
 
@@ -225,6 +422,36 @@ impl SlidingWindowCounter {
 // METRICS ENGINE (container)
 // ============================================================================
 
+/// Serialized histogram for `export_state` / `from_state`
+#[derive(Debug, Serialize, Deserialize)]
+struct HistogramState {
+    buckets: Vec<f64>,
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+    compression: f64,
+    /// t-digest centroids as `[mean, weight]`
+    centroids: Vec<(f64, f64)>,
+}
+
+/// Serialized sliding window counter
+#[derive(Debug, Serialize, Deserialize)]
+struct CounterState {
+    bucket_duration_ms: i64,
+    buckets: Vec<u64>,
+    timestamps: Vec<i64>,
+    head: usize,
+}
+
+/// Serialized engine
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct EngineState {
+    #[serde(default)]
+    histograms: BTreeMap<String, HistogramState>,
+    #[serde(default)]
+    counters: BTreeMap<String, CounterState>,
+}
+
 /// Container that manages named histograms and counters
 #[napi]
 pub struct MetricsEngine {
@@ -234,6 +461,12 @@ pub struct MetricsEngine {
     counter_store: Vec<SlidingWindowCounter>,
 }
 
+impl Default for MetricsEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[napi]
 impl MetricsEngine {
     #[napi(constructor)]
@@ -246,6 +479,111 @@ impl MetricsEngine {
         }
     }
 
+    /// Rebuild an engine from `export_state` output.
+    #[napi(factory)]
+    pub fn from_state(json: String) -> Result<Self> {
+        let state: EngineState = serde_json::from_str(&json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid engine state: {}", e)))?;
+
+        let mut engine = Self::new();
+        for (name, h) in state.histograms {
+            if h.counts.len() != h.buckets.len() + 1 {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Histogram {} has {} counts for {} buckets", name, h.counts.len(), h.buckets.len()),
+                ));
+            }
+            if !h.buckets.windows(2).all(|w| w[0] <= w[1]) {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Histogram {} has unsorted buckets", name),
+                ));
+            }
+            let invalid_centroid = |&(mean, weight): &(f64, f64)| {
+                !mean.is_finite() || !weight.is_finite() || weight <= 0.0
+            };
+            if h.centroids.iter().any(invalid_centroid) {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Histogram {} has an invalid centroid", name),
+                ));
+            }
+            if !h.compression.is_finite() || h.compression > MAX_COMPRESSION as f64 {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Histogram {} has an invalid compression {}", name, h.compression),
+                ));
+            }
+            let mut digest = TDigest::new(h.compression.max(1.0));
+            for (mean, weight) in h.centroids {
+                digest.add(mean, weight);
+            }
+            engine.histograms.insert(name, engine.histogram_store.len());
+            engine.histogram_store.push(MetricsHistogram {
+                buckets: h.buckets,
+                counts: h.counts,
+                sum: h.sum,
+                count: h.count,
+                digest,
+            });
+        }
+        for (name, c) in state.counters {
+            if c.buckets.is_empty() || c.buckets.len() != c.timestamps.len() || c.head >= c.buckets.len() {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Counter {} has an inconsistent ring buffer", name),
+                ));
+            }
+            if c.bucket_duration_ms <= 0 {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Counter {} has a non-positive bucket duration", name),
+                ));
+            }
+            engine.counters.insert(name, engine.counter_store.len());
+            engine.counter_store.push(SlidingWindowCounter {
+                bucket_duration_ms: c.bucket_duration_ms,
+                bucket_count: c.buckets.len(),
+                buckets: c.buckets,
+                timestamps: c.timestamps,
+                head: c.head,
+            });
+        }
+        Ok(engine)
+    }
+
+    /// Serialize every histogram (buckets, sum, count and t-digest centroids)
+    /// and counter ring buffer for crash recovery. The digest is kept, so
+    /// percentiles survive a `from_state` round trip.
+    #[napi]
+    pub fn export_state(&self) -> String {
+        let mut state = EngineState::default();
+        for (name, &idx) in &self.histograms {
+            let h = &self.histogram_store[idx];
+            state.histograms.insert(name.clone(), HistogramState {
+                buckets: h.buckets.clone(),
+                counts: h.counts.clone(),
+                sum: h.sum,
+                count: h.count,
+                compression: h.digest.compression,
+                centroids: h.digest.centroids.iter()
+                    .chain(&h.digest.buffer)
+                    .map(|c| (c.mean, c.weight))
+                    .collect(),
+            });
+        }
+        for (name, &idx) in &self.counters {
+            let c = &self.counter_store[idx];
+            state.counters.insert(name.clone(), CounterState {
+                bucket_duration_ms: c.bucket_duration_ms,
+                buckets: c.buckets.clone(),
+                timestamps: c.timestamps.clone(),
+                head: c.head,
+            });
+        }
+        serde_json::to_string(&state).unwrap_or_else(|_| "{}".to_string())
+    }
+
     #[napi]
     pub fn create_histogram(&mut self, name: String, buckets: Option<Vec<f64>>) -> u32 {
         let idx = self.histogram_store.len();
@@ -294,6 +632,55 @@ impl MetricsEngine {
         }
     }
 
+    /// Delete a histogram. Returns false if no histogram has that name.
+    #[napi]
+    pub fn remove_histogram(&mut self, name: String) -> bool {
+        match self.histograms.remove(&name) {
+            Some(idx) => {
+                remove_compacted(&mut self.histogram_store, &mut self.histograms, idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Delete a counter. Returns false if no counter has that name.
+    #[napi]
+    pub fn remove_counter(&mut self, name: String) -> bool {
+        match self.counters.remove(&name) {
+            Some(idx) => {
+                remove_compacted(&mut self.counter_store, &mut self.counters, idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Clear a histogram's observations, keeping its buckets.
+    /// Returns false if no histogram has that name.
+    #[napi]
+    pub fn reset_histogram(&mut self, name: String) -> bool {
+        match self.histograms.get(&name) {
+            Some(&idx) => {
+                self.histogram_store[idx].reset();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Clear a counter's window. Returns false if no counter has that name.
+    #[napi]
+    pub fn reset_counter(&mut self, name: String) -> bool {
+        match self.counters.get(&name) {
+            Some(&idx) => {
+                self.counter_store[idx].reset();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Get a snapshot of all metrics
     #[napi]
     pub fn get_snapshot(&mut self) -> String {
@@ -321,31 +708,226 @@ impl MetricsEngine {
 
         serde_json::to_string(&result).unwrap_or_else(|_| "{}".to_string())
     }
+
+    /// Export all metrics in the Prometheus text exposition format.
+    /// Histograms emit cumulative `_bucket{le="..."}` lines plus `_sum` and
+    /// `_count`; counters emit their current per-second rate as a `_total`
+    /// gauge. Names are sanitized to valid Prometheus identifiers and metrics
+    /// are sorted by name.
+    #[napi]
+    pub fn export_prometheus(&mut self) -> String {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut out = String::new();
+
+        let mut histograms: Vec<(&String, &usize)> = self.histograms.iter().collect();
+        histograms.sort();
+        for (name, &idx) in histograms {
+            let metric = sanitize_metric_name(name);
+            let h = &self.histogram_store[idx];
+            out.push_str(&format!("# TYPE {} histogram\n", metric));
+
+            for (boundary, cumulative) in h.bucket_snapshot() {
+                let le = if boundary.is_infinite() { "+Inf".to_string() } else { boundary.to_string() };
+                out.push_str(&format!("{}_bucket{{le=\"{}\"}} {}\n", metric, le, cumulative));
+            }
+            out.push_str(&format!("{}_sum {}\n", metric, h.sum));
+            out.push_str(&format!("{}_count {}\n", metric, h.count));
+        }
+
+        let mut counters: Vec<(&String, &usize)> = self.counters.iter().collect();
+        counters.sort();
+        for (name, &idx) in counters {
+            let metric = format!("{}_total", sanitize_metric_name(name));
+            let rate = self.counter_store[idx].get_rate(now);
+            out.push_str(&format!("# TYPE {} gauge\n", metric));
+            out.push_str(&format!("{} {}\n", metric, rate));
+        }
+
+        out
+    }
+}
+
+// ============================================================================
+// SHARED METRICS ENGINE
+// ============================================================================
+
+/// Process-wide engines registered by name for `SharedMetricsEngine::get`
+static SHARED_ENGINES: OnceLock<Mutex<HashMap<String, Arc<Mutex<MetricsEngine>>>>> =
+    OnceLock::new();
+
+/// `MetricsEngine` behind a mutex so one instance can be observed from
+/// several threads. Cloning shares the same underlying engine.
+///
+/// Node worker threads each load their own copy of the addon object, so a
+/// `SharedMetricsEngine` constructed in one cannot be handed to another;
+/// use `SharedMetricsEngine.get(name)` in each thread to reach the same
+/// process-wide engine instead.
+#[napi]
+#[derive(Clone, Default)]
+pub struct SharedMetricsEngine {
+    inner: Arc<Mutex<MetricsEngine>>,
+}
+
+#[napi]
+impl SharedMetricsEngine {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide engine registered under `name`, created empty on
+    /// first use. Every call with the same name, from any thread, shares it.
+    #[napi(factory)]
+    pub fn get(name: String) -> Self {
+        let registry = SHARED_ENGINES.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut registry = registry.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let inner = registry.entry(name).or_default().clone();
+        Self { inner }
+    }
+
+    #[napi]
+    pub fn create_histogram(&self, name: String, buckets: Option<Vec<f64>>) -> u32 {
+        self.lock().create_histogram(name, buckets)
+    }
+
+    #[napi]
+    pub fn observe_histogram(&self, name: String, value: f64) {
+        self.lock().observe_histogram(name, value);
+    }
+
+    #[napi]
+    pub fn get_histogram_percentiles(&self, name: String) -> Option<PercentileSnapshot> {
+        self.lock().get_histogram_percentiles(name)
+    }
+
+    #[napi]
+    pub fn create_counter(&self, name: String, window_seconds: u32, bucket_count: u32) -> u32 {
+        self.lock().create_counter(name, window_seconds, bucket_count)
+    }
+
+    #[napi]
+    pub fn increment_counter(&self, name: String, now_ms: i64) {
+        self.lock().increment_counter(name, now_ms);
+    }
+
+    #[napi]
+    pub fn get_counter_rate(&self, name: String, now_ms: i64) -> f64 {
+        self.lock().get_counter_rate(name, now_ms)
+    }
+
+    /// Delete a histogram. Returns false if no histogram has that name.
+    #[napi]
+    pub fn remove_histogram(&self, name: String) -> bool {
+        self.lock().remove_histogram(name)
+    }
+
+    /// Delete a counter. Returns false if no counter has that name.
+    #[napi]
+    pub fn remove_counter(&self, name: String) -> bool {
+        self.lock().remove_counter(name)
+    }
+
+    /// Clear a histogram's observations, keeping its buckets.
+    /// Returns false if no histogram has that name.
+    #[napi]
+    pub fn reset_histogram(&self, name: String) -> bool {
+        self.lock().reset_histogram(name)
+    }
+
+    /// Clear a counter's window. Returns false if no counter has that name.
+    #[napi]
+    pub fn reset_counter(&self, name: String) -> bool {
+        self.lock().reset_counter(name)
+    }
+
+    /// Get a snapshot of all metrics
+    #[napi]
+    pub fn get_snapshot(&self) -> String {
+        self.lock().get_snapshot()
+    }
+
+    #[napi]
+    pub fn export_prometheus(&self) -> String {
+        self.lock().export_prometheus()
+    }
+
+    #[napi]
+    pub fn export_state(&self) -> String {
+        self.lock().export_state()
+    }
+
+    /// A panic while holding the lock leaves the engine usable, so recover
+    /// the guard rather than propagating the poison.
+    fn lock(&self) -> MutexGuard<'_, MetricsEngine> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Remove `store[idx]` by swapping in the last element, then repoint the
+/// name that referred to the moved element so every index stays valid.
+fn remove_compacted<T>(store: &mut Vec<T>, index: &mut HashMap<String, usize>, idx: usize) {
+    let last = store.len() - 1;
+    store.swap_remove(idx);
+    if idx != last {
+        if let Some(moved) = index.values_mut().find(|i| **i == last) {
+            *moved = idx;
+        }
+    }
 }
 
-/// Downsample a time series by averaging consecutive groups of `factor` points.
+/// Map a metric name onto the Prometheus identifier charset
+/// `[a-zA-Z_:][a-zA-Z0-9_:]*`, replacing anything else with `_`.
+fn sanitize_metric_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == ':' { c } else { '_' })
+        .collect();
+    if sanitized.is_empty() || sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// Downsample a time series by aggregating consecutive groups of `factor` points.
+/// `agg` is applied column-wise: "avg" (default), "max", "min", "sum" or "last".
 #[napi]
-pub fn downsample(points_json: String, factor: u32) -> Result<String> {
+pub fn downsample(points_json: String, factor: u32, agg: Option<String>) -> Result<String> {
     let points: Vec<Vec<f64>> = serde_json::from_str(&points_json)
         .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid points: {}", e)))?;
 
+    let agg = agg.unwrap_or_else(|| "avg".to_string());
+    let combine: fn(f64, f64) -> f64 = match agg.as_str() {
+        "avg" | "sum" => |acc, val| acc + val,
+        "max" => f64::max,
+        "min" => f64::min,
+        "last" => |_, val| val,
+        other => {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("Unknown aggregation: {} (expected avg, max, min, sum or last)", other),
+            ))
+        }
+    };
+
     let factor = factor.max(1) as usize;
     let mut result: Vec<Vec<f64>> = Vec::new();
 
     for chunk in points.chunks(factor) {
         if chunk.is_empty() { continue; }
         let cols = chunk[0].len();
-        let mut avg = vec![0.0; cols];
-        for point in chunk {
+        let mut acc = chunk[0].clone();
+        for point in &chunk[1..] {
             for (i, &val) in point.iter().enumerate() {
-                if i < cols { avg[i] += val; }
+                if i < cols { acc[i] = combine(acc[i], val); }
             }
         }
-        let n = chunk.len() as f64;
-        for val in &mut avg {
-            *val /= n;
+        if agg == "avg" {
+            let n = chunk.len() as f64;
+            for val in &mut acc {
+                *val /= n;
+            }
         }
-        result.push(avg);
+        result.push(acc);
     }
 
     serde_json::to_string(&result)
@@ -366,6 +948,10 @@ mod tests {
         assert!(p.p50 > 0.0);
         assert!(p.p95 > p.p50);
         assert_eq!(p.count, 100);
+        assert_eq!(h.digest.compression, DEFAULT_COMPRESSION as f64);
+        assert_eq!(h.digest.buffer_limit, 4 * DEFAULT_COMPRESSION as usize);
+        let capped = MetricsHistogram::new(None, Some(u32::MAX));
+        assert_eq!(capped.digest.compression, MAX_COMPRESSION as f64);
     }
 
     #[test]
@@ -379,15 +965,315 @@ mod tests {
         assert!(rate > 0.0);
     }
 
+    #[test]
+    fn test_sliding_window_add() {
+        let mut counter = SlidingWindowCounter::new(60, 60);
+        let now = 1_000_000i64;
+        counter.add(now, 25);
+        counter.increment(now);
+        assert_eq!(counter.get_count(now), 26);
+
+        counter.add(now, -6);
+        assert_eq!(counter.get_count(now), 20);
+
+        // Clamped at zero rather than wrapping
+        counter.add(now, -100);
+        assert_eq!(counter.get_count(now), 0);
+    }
+
     #[test]
     fn test_downsample() {
         let points = serde_json::to_string(&vec![
             vec![1.0, 10.0], vec![2.0, 20.0],
             vec![3.0, 30.0], vec![4.0, 40.0],
         ]).unwrap();
-        let result = downsample(points, 2).unwrap();
+        let result = downsample(points, 2, None).unwrap();
         let ds: Vec<Vec<f64>> = serde_json::from_str(&result).unwrap();
         assert_eq!(ds.len(), 2);
         assert_eq!(ds[0], vec![1.5, 15.0]);
     }
+
+    #[test]
+    fn test_downsample_aggregations() {
+        let points = serde_json::to_string(&vec![
+            vec![1.0, 40.0], vec![2.0, 10.0],
+            vec![3.0, 30.0], vec![4.0, 20.0],
+        ]).unwrap();
+        let run = |agg: &str| -> Vec<Vec<f64>> {
+            serde_json::from_str(&downsample(points.clone(), 2, Some(agg.into())).unwrap()).unwrap()
+        };
+
+        assert_eq!(run("max"), vec![vec![2.0, 40.0], vec![4.0, 30.0]]);
+        assert_eq!(run("sum"), vec![vec![3.0, 50.0], vec![7.0, 50.0]]);
+        assert_eq!(run("min"), vec![vec![1.0, 10.0], vec![3.0, 20.0]]);
+        assert_eq!(run("last"), vec![vec![2.0, 10.0], vec![4.0, 20.0]]);
+        assert!(downsample(points, 2, Some("median".into())).is_err());
+    }
+
+    #[test]
+    fn test_export_prometheus() {
+        let mut engine = MetricsEngine::new();
+        engine.create_histogram("http.latency-ms".into(), Some(vec![0.1, 1.0]));
+        for value in [0.05, 0.5, 0.7, 3.0] {
+            engine.observe_histogram("http.latency-ms".into(), value);
+        }
+        engine.create_counter("2xx responses".into(), 60, 60);
+
+        let text = engine.export_prometheus();
+        assert!(text.contains("# TYPE http_latency_ms histogram\n"));
+        assert!(text.contains("http_latency_ms_bucket{le=\"0.1\"} 1\n"));
+        assert!(text.contains("http_latency_ms_bucket{le=\"1\"} 3\n"));
+        assert!(text.contains("http_latency_ms_bucket{le=\"+Inf\"} 4\n"));
+        assert!(text.contains("http_latency_ms_sum 4.25\n"));
+        assert!(text.contains("http_latency_ms_count 4\n"));
+        assert!(text.contains("# TYPE _2xx_responses_total gauge\n"));
+        assert!(text.contains("_2xx_responses_total 0\n"));
+    }
+
+    #[test]
+    fn test_bucket_snapshot() {
+        let mut h = MetricsHistogram::new(Some(vec![0.1, 1.0, 10.0]), None);
+        for value in [0.05, 0.5, 0.7, 3.0, 50.0] {
+            h.observe(value);
+        }
+
+        let snapshot = h.bucket_snapshot();
+        assert_eq!(snapshot, vec![(0.1, 1), (1.0, 3), (10.0, 4), (f64::INFINITY, 5)]);
+        assert!(snapshot.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+
+    #[test]
+    fn test_percentiles_stay_accurate_past_capacity() {
+        let mut h = MetricsHistogram::new(None, Some(200));
+        // 1M values uniformly spread over [0, 1), streamed in a scrambled order
+        let n: u64 = 1_000_000;
+        for i in 0..n {
+            let value = ((i * 7_919) % n) as f64 / n as f64;
+            h.observe(value);
+        }
+
+        let p = h.get_percentiles();
+        assert_eq!(p.count, n as i64);
+        assert!((p.p50 - 0.50).abs() < 0.01, "p50 = {}", p.p50);
+        assert!((p.p95 - 0.95).abs() < 0.005, "p95 = {}", p.p95);
+        assert!((p.p99 - 0.99).abs() < 0.002, "p99 = {}", p.p99);
+        assert!(h.digest.centroids.len() <= 200, "{} centroids", h.digest.centroids.len());
+    }
+
+    #[test]
+    fn test_histogram_merge() {
+        // Compression well above the sample count keeps every observation,
+        // so the merged percentiles are exact
+        let mut shard_a = MetricsHistogram::new(None, Some(10_000));
+        let mut shard_b = MetricsHistogram::new(None, Some(10_000));
+        let mut combined = MetricsHistogram::new(None, Some(10_000));
+        for i in 0..1000 {
+            let value = (i % 97) as f64 / 10.0;
+            if i % 3 == 0 { shard_a.observe(value) } else { shard_b.observe(value) }
+            combined.observe(value);
+        }
+
+        shard_a.merge(&shard_b).unwrap();
+        let merged = shard_a.get_percentiles();
+        let expected = combined.get_percentiles();
+        assert_eq!(merged.count, 1000);
+        assert_eq!((merged.p50, merged.p95, merged.p99), (expected.p50, expected.p95, expected.p99));
+        assert!((merged.sum - expected.sum).abs() < 1e-9);
+        assert_eq!(shard_a.counts, combined.counts);
+
+        let other_layout = MetricsHistogram::new(Some(vec![1.0, 2.0]), None);
+        assert!(shard_a.merge(&other_layout).is_err());
+    }
+
+    #[test]
+    fn test_remove_and_reset_metrics() {
+        let mut engine = MetricsEngine::new();
+        engine.create_histogram("a".into(), None);
+        engine.create_histogram("b".into(), None);
+        engine.create_histogram("c".into(), None);
+        engine.observe_histogram("c".into(), 1.0);
+
+        // Removing "a" moves "c" into its slot; "c" must still resolve
+        assert!(engine.remove_histogram("a".into()));
+        assert!(!engine.remove_histogram("a".into()));
+        assert!(engine.get_histogram_percentiles("a".into()).is_none());
+        assert_eq!(engine.get_histogram_percentiles("c".into()).unwrap().count, 1);
+        assert_eq!(engine.histogram_store.len(), 2);
+
+        // Recreating with the same name starts fresh
+        engine.create_histogram("a".into(), None);
+        engine.observe_histogram("a".into(), 2.0);
+        assert_eq!(engine.get_histogram_percentiles("a".into()).unwrap().count, 1);
+        assert_eq!(engine.get_histogram_percentiles("c".into()).unwrap().count, 1);
+
+        assert!(engine.reset_histogram("c".into()));
+        assert_eq!(engine.get_histogram_percentiles("c".into()).unwrap().count, 0);
+        assert!(!engine.reset_histogram("zzz".into()));
+
+        let now = 1_000_000i64;
+        engine.create_counter("x".into(), 60, 60);
+        engine.create_counter("y".into(), 60, 60);
+        engine.increment_counter("y".into(), now);
+        assert!(engine.remove_counter("x".into()));
+        assert!(engine.get_counter_rate("y".into(), now) > 0.0);
+        engine.create_counter("x".into(), 60, 60);
+        assert_eq!(engine.get_counter_rate("x".into(), now), 0.0);
+
+        assert!(engine.reset_counter("y".into()));
+        assert_eq!(engine.get_counter_rate("y".into(), now), 0.0);
+        assert!(!engine.remove_counter("zzz".into()));
+    }
+
+    #[test]
+    fn test_observe_many() {
+        let mut bulk = MetricsHistogram::new(Some(vec![1.0, 5.0]), None);
+        let mut single = MetricsHistogram::new(Some(vec![1.0, 5.0]), None);
+        for (value, times) in [(0.5, 30u32), (3.0, 60), (9.0, 10)] {
+            bulk.observe_many(value, times);
+            for _ in 0..times {
+                single.observe(value);
+            }
+        }
+        bulk.observe_many(100.0, 0);
+
+        assert_eq!(bulk.counts, vec![30, 60, 10]);
+        assert_eq!(bulk.counts, single.counts);
+        let (b, s) = (bulk.get_percentiles(), single.get_percentiles());
+        assert_eq!(b.count, 100);
+        assert!((b.sum - 285.0).abs() < 1e-9);
+        assert_eq!((b.p50, b.p95, b.p99), (s.p50, s.p95, s.p99));
+    }
+
+    #[test]
+    fn test_interpolated_percentiles() {
+        let mut h = MetricsHistogram::new(None, None);
+        for i in 1..=10 {
+            h.observe(i as f64);
+        }
+        assert!((h.percentile(0.5) - 5.5).abs() < 1e-9);
+        assert!((h.percentile(0.0) - 1.0).abs() < 1e-9);
+        assert!((h.percentile(1.0) - 10.0).abs() < 1e-9);
+        assert!((h.percentile(0.25) - 3.25).abs() < 1e-9);
+
+        let p = h.get_percentiles();
+        assert!((p.p50 - 5.5).abs() < 1e-9);
+        assert!((p.p95 - 9.55).abs() < 1e-9);
+        assert!((p.p99 - 9.91).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_engine_state_round_trip() {
+        let now = 1_000_000i64;
+        let mut engine = MetricsEngine::new();
+        engine.create_histogram("latency".into(), Some(vec![0.1, 1.0]));
+        for i in 0..50 {
+            engine.observe_histogram("latency".into(), i as f64 / 20.0);
+        }
+        engine.create_counter("requests".into(), 60, 12);
+        for i in 0..30 {
+            engine.increment_counter("requests".into(), now + i * 1000);
+        }
+        let later = now + 30_000;
+
+        let mut restored = MetricsEngine::from_state(engine.export_state()).unwrap();
+        assert_eq!(
+            restored.get_counter_rate("requests".into(), later),
+            engine.get_counter_rate("requests".into(), later)
+        );
+        let (a, b) = (
+            engine.get_histogram_percentiles("latency".into()).unwrap(),
+            restored.get_histogram_percentiles("latency".into()).unwrap(),
+        );
+        assert_eq!(b.count, 50);
+        assert_eq!((a.p50, a.p99, a.sum), (b.p50, b.p99, b.sum));
+        assert_eq!(restored.histogram_store[0].counts, engine.histogram_store[0].counts);
+
+        assert!(MetricsEngine::from_state("{\"counters\": {\"x\": {\"bucket_duration_ms\": 1000, \"buckets\": [], \"timestamps\": [], \"head\": 0}}}".into()).is_err());
+
+        let counter = |duration: i64| {
+            format!(
+                r#"{{"counters": {{"x": {{"bucket_duration_ms": {}, "buckets": [1, 0], "timestamps": [5000, 0], "head": 0}}}}}}"#,
+                duration
+            )
+        };
+        assert!(MetricsEngine::from_state(counter(1000)).is_ok());
+        assert!(MetricsEngine::from_state(counter(0)).is_err());
+        assert!(MetricsEngine::from_state(counter(-1000)).is_err());
+
+        let histogram = |buckets: &str, centroids: &str| {
+            format!(
+                r#"{{"histograms": {{"h": {{"buckets": {}, "counts": [0, 0, 0], "sum": 0.0, "count": 0, "compression": 100.0, "centroids": {}}}}}}}"#,
+                buckets, centroids
+            )
+        };
+        let with_compression = |compression: &str| {
+            format!(
+                r#"{{"histograms": {{"h": {{"buckets": [], "counts": [0], "sum": 0.0, "count": 0, "compression": {}, "centroids": []}}}}}}"#,
+                compression
+            )
+        };
+        assert!(MetricsEngine::from_state(with_compression("10000.0")).is_ok());
+        assert!(MetricsEngine::from_state(with_compression("10001.0")).is_err());
+        assert!(MetricsEngine::from_state(with_compression("1e300")).is_err());
+        assert!(MetricsEngine::from_state(histogram("[0.1, 1.0]", "[[0.5, 1.0]]")).is_ok());
+        assert!(MetricsEngine::from_state(histogram("[1.0, 0.1]", "[]")).is_err());
+        assert!(MetricsEngine::from_state(histogram("[0.1, 1.0]", "[[0.5, 0.0]]")).is_err());
+        assert!(MetricsEngine::from_state(histogram("[0.1, 1.0]", "[[0.5, -2.0]]")).is_err());
+    }
+
+    #[test]
+    fn test_shared_engine_concurrent_observe() {
+        let engine = SharedMetricsEngine::new();
+        engine.create_histogram("latency".into(), None);
+        engine.create_counter("requests".into(), 60, 60);
+        let now = 1_000_000i64;
+
+        let threads: u32 = 8;
+        let per_thread: u32 = 5_000;
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let engine = engine.clone();
+                std::thread::spawn(move || {
+                    for i in 0..per_thread {
+                        engine.observe_histogram("latency".into(), (t * per_thread + i) as f64 / 1000.0);
+                        engine.increment_counter("requests".into(), now);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let total = (threads * per_thread) as i64;
+        let snapshot = engine.get_histogram_percentiles("latency".into()).unwrap();
+        assert_eq!(snapshot.count, total);
+        let expected_sum: f64 = (0..total).map(|v| v as f64 / 1000.0).sum();
+        assert!((snapshot.sum - expected_sum).abs() < 1e-3);
+        assert_eq!(engine.get_counter_rate("requests".into(), now), total as f64 / 60.0);
+    }
+
+    #[test]
+    fn test_shared_engine_registry() {
+        let engine = SharedMetricsEngine::get("test_registry".into());
+        engine.create_histogram("latency".into(), None);
+
+        let handle = std::thread::spawn(|| {
+            let engine = SharedMetricsEngine::get("test_registry".into());
+            engine.observe_histogram("latency".into(), 2.0);
+        });
+        handle.join().unwrap();
+
+        assert_eq!(engine.get_histogram_percentiles("latency".into()).unwrap().count, 1);
+        assert!(SharedMetricsEngine::get("test_registry_other".into())
+            .get_histogram_percentiles("latency".into())
+            .is_none());
+
+        assert!(engine.get_snapshot().contains("\"latency\""));
+        assert!(engine.reset_histogram("latency".into()));
+        assert_eq!(engine.get_histogram_percentiles("latency".into()).unwrap().count, 0);
+        assert!(engine.remove_histogram("latency".into()));
+        assert!(!engine.remove_counter("requests".into()));
+        assert!(!engine.reset_counter("requests".into()));
+    }
 }
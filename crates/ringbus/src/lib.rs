@@ -1,12 +1,18 @@
 //! Topic-based pub/sub message ring buffer.
 //!
 //! High-throughput in-memory message bus for inter-agent communication.
-//! Uses VecDeque channels per topic with priority ordering.
+//! Uses VecDeque channels per topic with priority ordering. Subscriptions
+//! may use `.`-delimited wildcard patterns: `*` matches exactly one segment
+//! and a trailing `**` matches one or more remaining segments.
+//!
+//! Every message carries a sequence number within its topic (1, 2, 3, ...).
+//! Each subscriber keeps a per-topic cursor of consumed sequence numbers, so
+//! reads only return messages past the cursor and `seek` can rewind it.
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
 const MAX_MESSAGES_PER_TOPIC: usize = 10_000;
 
@@ -16,6 +22,9 @@ const MAX_MESSAGES_PER_TOPIC: usize = 10_000;
 pub struct BusMessage {
     /// Unique message ID
     pub id: String,
+    /// Sequence number within the topic, starting at 1 with no reuse;
+    /// a jump between consecutive messages means some were dropped
+    pub seq: i64,
     /// Topic for routing
     pub topic: String,
     /// Sender identifier
@@ -26,8 +35,11 @@ pub struct BusMessage {
     pub payload: String,
     /// Timestamp in milliseconds
     pub timestamp: i64,
-    /// Comma-separated list of handles that have read this message
+    /// Comma-separated list of handles that have read this message,
+    /// filled in from subscriber cursors when the message is returned
     pub read_by: String,
+    /// Expiry time in milliseconds (None = never expires)
+    pub expires_at: Option<i64>,
 }
 
 /// Bus statistics
@@ -47,29 +59,142 @@ pub struct TopicCount {
     pub count: i64,
 }
 
+/// Cumulative publish/consume counters for one topic
+#[napi(object)]
+#[derive(Clone, Debug, Serialize)]
+pub struct TopicThroughput {
+    pub topic: String,
+    /// Messages published to the topic
+    pub published: i64,
+    /// Message deliveries to subscribers via `read`
+    pub consumed: i64,
+}
+
+/// Messages for one topic, ordered by sequence number
+#[derive(Default)]
+struct Channel {
+    messages: VecDeque<BusMessage>,
+    /// Last sequence number assigned in this topic
+    last_seq: i64,
+    /// Messages published since construction or the last throughput reset
+    published: i64,
+    /// Deliveries via `read` since construction or the last throughput reset
+    consumed: i64,
+}
+
+/// A subscriber's read position in one topic: everything at or below
+/// `seq` is consumed, plus any out-of-order sequence numbers in `consumed`.
+#[derive(Default)]
+struct Cursor {
+    seq: i64,
+    consumed: BTreeSet<i64>,
+}
+
+impl Cursor {
+    fn has_consumed(&self, seq: i64) -> bool {
+        seq <= self.seq || self.consumed.contains(&seq)
+    }
+
+    /// Mark `seq` consumed and advance past every contiguous consumed message
+    fn consume(&mut self, seq: i64, messages: &VecDeque<BusMessage>) {
+        if seq <= self.seq {
+            return;
+        }
+        self.consumed.insert(seq);
+        let start = messages.partition_point(|m| m.seq <= self.seq);
+        for msg in messages.range(start..) {
+            if !self.consumed.remove(&msg.seq) {
+                break;
+            }
+            self.seq = msg.seq;
+        }
+        let floor = self.seq;
+        self.consumed.retain(|&s| s > floor);
+    }
+}
+
+/// Which message to drop when a full topic receives a new one
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EvictionPolicy {
+    /// Drop the oldest message
+    Fifo,
+    /// Drop the oldest message among those with the lowest priority
+    Priority,
+}
+
+impl EvictionPolicy {
+    fn parse(value: &str) -> Result<Self> {
+        match value {
+            "fifo" => Ok(EvictionPolicy::Fifo),
+            "priority" => Ok(EvictionPolicy::Priority),
+            other => Err(Error::new(
+                Status::InvalidArg,
+                format!("Unknown eviction policy: {} (expected fifo or priority)", other),
+            )),
+        }
+    }
+
+    /// Index of the message to evict from a non-empty channel
+    fn victim(self, messages: &VecDeque<BusMessage>) -> usize {
+        match self {
+            EvictionPolicy::Fifo => 0,
+            EvictionPolicy::Priority => messages
+                .iter()
+                .enumerate()
+                .min_by_key(|(i, m)| (m.priority, *i))
+                .map_or(0, |(i, _)| i),
+        }
+    }
+}
+
 /// Topic-based pub/sub ring buffer
 #[napi]
 pub struct RingBus {
-    /// Messages per topic, ordered by timestamp
-    channels: HashMap<String, VecDeque<BusMessage>>,
-    /// Subscribers: handle → set of topics
+    /// Messages per topic
+    channels: HashMap<String, Channel>,
+    /// Subscribers: handle → set of topic patterns
     subscribers: HashMap<String, HashSet<String>>,
+    /// Read cursors: handle → topic → cursor
+    cursors: HashMap<String, HashMap<String, Cursor>>,
     /// Auto-incrementing message ID counter
     next_id: u64,
+    /// How full topics make room
+    eviction_policy: EvictionPolicy,
+    /// Per-topic capacity overrides (default MAX_MESSAGES_PER_TOPIC)
+    capacities: HashMap<String, usize>,
+    /// Unread messages lost to eviction or expiry, oldest first
+    dead_letters: VecDeque<BusMessage>,
+    /// Maximum dead letters kept (0 = dead-letter capture disabled)
+    dead_letter_capacity: usize,
+}
+
+impl Default for RingBus {
+    fn default() -> Self {
+        Self::new(None)
+    }
 }
 
 #[napi]
 impl RingBus {
+    /// Create a bus. Passing `dead_letter_capacity` enables a dead-letter
+    /// queue of that size for unread messages that are evicted or expire.
     #[napi(constructor)]
-    pub fn new() -> Self {
+    pub fn new(dead_letter_capacity: Option<u32>) -> Self {
         Self {
             channels: HashMap::new(),
             subscribers: HashMap::new(),
+            cursors: HashMap::new(),
             next_id: 1,
+            eviction_policy: EvictionPolicy::Fifo,
+            capacities: HashMap::new(),
+            dead_letters: VecDeque::new(),
+            dead_letter_capacity: dead_letter_capacity.unwrap_or(0) as usize,
         }
     }
 
-    /// Publish a message to a topic
+    /// Publish a message to a topic. With `ttl_ms` the message expires
+    /// that many milliseconds after publishing; otherwise it never expires.
+    /// A `ttl_ms` of zero or less is rejected.
     #[napi]
     pub fn publish(
         &mut self,
@@ -77,39 +202,58 @@ impl RingBus {
         sender: String,
         priority: u32,
         payload: String,
-    ) -> String {
-        let now = chrono::Utc::now().timestamp_millis();
-        let id = format!("msg_{}", self.next_id);
-        self.next_id += 1;
+        ttl_ms: Option<i64>,
+    ) -> Result<String> {
+        if ttl_ms.is_some_and(|ttl| ttl <= 0) {
+            return Err(Error::new(Status::InvalidArg, "ttl_ms must be positive"));
+        }
+        Ok(self.publish_message(topic, sender, priority, payload, ttl_ms))
+    }
 
-        let msg = BusMessage {
-            id: id.clone(),
-            topic: topic.clone(),
-            sender,
-            priority: priority.min(3),
-            payload,
-            timestamp: now,
-            read_by: String::new(),
-        };
+    /// Publish the same payload to several topics. Each copy gets its own id
+    /// and sequence number; ids are returned in topic order.
+    #[napi]
+    pub fn publish_many(
+        &mut self,
+        topics: Vec<String>,
+        sender: String,
+        priority: u32,
+        payload: String,
+    ) -> Vec<String> {
+        topics
+            .into_iter()
+            .map(|topic| {
+                self.publish_message(topic, sender.clone(), priority, payload.clone(), None)
+            })
+            .collect()
+    }
 
-        let channel = self.channels.entry(topic).or_insert_with(VecDeque::new);
+    /// Choose what a full topic drops: "fifo" (default, the oldest message)
+    /// or "priority" (the oldest of the lowest-priority messages)
+    #[napi]
+    pub fn set_eviction_policy(&mut self, policy: String) -> Result<()> {
+        self.eviction_policy = EvictionPolicy::parse(&policy)?;
+        Ok(())
+    }
 
-        // Evict oldest if at capacity
-        if channel.len() >= MAX_MESSAGES_PER_TOPIC {
-            channel.pop_front();
+    /// Set the maximum number of messages kept for a topic. A topic already
+    /// above the new capacity is trimmed immediately per the eviction policy.
+    #[napi]
+    pub fn set_topic_capacity(&mut self, topic: String, capacity: u32) {
+        let capacity = capacity.max(1) as usize;
+        if let Some(channel) = self.channels.get_mut(&topic) {
+            let evicted = evict_to(&mut channel.messages, capacity, self.eviction_policy);
+            self.bury(evicted);
         }
-
-        channel.push_back(msg);
-
-        id
+        self.capacities.insert(topic, capacity);
     }
 
-    /// Subscribe a handle to a topic
+    /// Subscribe a handle to a topic or wildcard pattern (e.g. `tasks.*`)
     #[napi]
     pub fn subscribe(&mut self, handle: String, topic: String) {
         self.subscribers
             .entry(handle)
-            .or_insert_with(HashSet::new)
+            .or_default()
             .insert(topic);
     }
 
@@ -121,84 +265,162 @@ impl RingBus {
         }
     }
 
-    /// Read messages for a subscriber (only from subscribed topics, optionally unread only)
+    /// Handles subscribed to a topic, directly or through a wildcard pattern
+    #[napi]
+    pub fn subscribers_of(&self, topic: String) -> Vec<String> {
+        let mut handles: Vec<String> = self.subscribers
+            .iter()
+            .filter(|(_, patterns)| patterns.iter().any(|p| topic_matches(p, &topic)))
+            .map(|(handle, _)| handle.clone())
+            .collect();
+        handles.sort();
+        handles
+    }
+
+    /// Topics and patterns a handle is subscribed to
+    #[napi]
+    pub fn topics_of(&self, handle: String) -> Vec<String> {
+        let mut topics: Vec<String> = self.subscribers
+            .get(&handle)
+            .map(|t| t.iter().cloned().collect())
+            .unwrap_or_default();
+        topics.sort();
+        topics
+    }
+
+    /// Read messages for a subscriber (only from subscribed topics, optionally unread only).
+    /// Unread messages are those past the subscriber's cursor. With `auto_ack`
+    /// (default true) returned messages are consumed and the cursor advances;
+    /// otherwise they stay deliverable until passed to `ack`. `from_sender`
+    /// restricts the result to one sender.
     #[napi]
     pub fn read(
         &mut self,
         handle: String,
         limit: Option<u32>,
         unread_only: Option<bool>,
+        auto_ack: Option<bool>,
+        from_sender: Option<String>,
     ) -> Vec<BusMessage> {
         let limit = limit.unwrap_or(50) as usize;
-        let unread_only = unread_only.unwrap_or(true);
-
-        let topics: Vec<String> = self
-            .subscribers
-            .get(&handle)
-            .map(|t| t.iter().cloned().collect())
-            .unwrap_or_default();
+        let picked = self.collect_for(&handle, limit, unread_only.unwrap_or(true), from_sender.as_deref());
+        for msg in &picked {
+            if let Some(channel) = self.channels.get_mut(&msg.topic) {
+                channel.consumed += 1;
+            }
+        }
+        if !auto_ack.unwrap_or(true) {
+            return self.with_read_by(picked);
+        }
 
-        let mut messages: Vec<BusMessage> = Vec::new();
-
-        for topic in &topics {
-            if let Some(channel) = self.channels.get(topic) {
-                for msg in channel.iter().rev() {
-                    if messages.len() >= limit {
-                        break;
-                    }
-                    if unread_only && msg.read_by.contains(&handle) {
-                        continue;
-                    }
-                    messages.push(msg.clone());
-                }
+        // Advance cursors
+        let cursors = self.cursors.entry(handle).or_default();
+        for msg in &picked {
+            if let Some(channel) = self.channels.get(&msg.topic) {
+                cursors
+                    .entry(msg.topic.clone())
+                    .or_default()
+                    .consume(msg.seq, &channel.messages);
             }
         }
 
-        // Sort by priority (desc) then timestamp (asc)
-        messages.sort_by(|a, b| {
-            b.priority.cmp(&a.priority).then(a.timestamp.cmp(&b.timestamp))
-        });
+        self.with_read_by(picked)
+    }
 
-        messages.truncate(limit);
+    /// Mark messages as processed by a subscriber. Returns how many of the
+    /// ids were found.
+    #[napi]
+    pub fn ack(&mut self, handle: String, message_ids: Vec<String>) -> u32 {
+        let ids: HashSet<String> = message_ids.into_iter().collect();
+        let cursors = self.cursors.entry(handle).or_default();
+        let mut acked: u32 = 0;
 
-        // Mark as read
-        for msg in &messages {
-            if let Some(channel) = self.channels.get_mut(&msg.topic) {
-                for m in channel.iter_mut() {
-                    if m.id == msg.id {
-                        if !m.read_by.is_empty() {
-                            m.read_by.push(',');
-                        }
-                        m.read_by.push_str(&handle);
-                        break;
-                    }
-                }
+        for (topic, channel) in &self.channels {
+            for msg in channel.messages.iter().filter(|m| ids.contains(&m.id)) {
+                cursors
+                    .entry(topic.clone())
+                    .or_default()
+                    .consume(msg.seq, &channel.messages);
+                acked += 1;
             }
         }
 
-        messages
+        acked
+    }
+
+    /// Return the messages the next `read` would deliver, without consuming them
+    #[napi]
+    pub fn peek(&mut self, handle: String, limit: Option<u32>) -> Vec<BusMessage> {
+        let limit = limit.unwrap_or(50) as usize;
+        let picked = self.collect_for(&handle, limit, true, None);
+        self.with_read_by(picked)
+    }
+
+    /// Move a subscriber's cursor in a topic so the next read starts after
+    /// sequence number `seq`; seek to 0 to replay everything still retained.
+    #[napi]
+    pub fn seek(&mut self, handle: String, topic: String, seq: i64) {
+        self.cursors.entry(handle).or_default().insert(
+            topic,
+            Cursor { seq: seq.max(0), consumed: BTreeSet::new() },
+        );
     }
 
-    /// Read messages from a specific topic
+    /// Read messages from a specific topic, newest first, optionally only
+    /// those from one sender
     #[napi]
     pub fn read_topic(
-        &self,
+        &mut self,
         topic: String,
         limit: Option<u32>,
+        from_sender: Option<String>,
     ) -> Vec<BusMessage> {
         let limit = limit.unwrap_or(50) as usize;
+        let now = chrono::Utc::now().timestamp_millis();
 
-        self.channels
-            .get(&topic)
+        let mut expired = Vec::new();
+        let picked: Vec<BusMessage> = self.channels
+            .get_mut(&topic)
             .map(|channel| {
-                channel
+                expired = remove_expired(&mut channel.messages, now);
+                channel.messages
                     .iter()
                     .rev()
+                    .filter(|m| from_sender.as_ref().is_none_or(|s| m.sender == *s))
                     .take(limit)
                     .cloned()
                     .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+
+        self.bury(expired);
+        self.with_read_by(picked)
+    }
+
+    /// Find messages whose payload contains `query`, newest first, optionally
+    /// within one topic. This is a diagnostic linear scan over every retained
+    /// message, not an index; keep it off hot paths.
+    #[napi]
+    pub fn search_payload(&self, query: String, topic: Option<String>, limit: Option<u32>) -> Vec<BusMessage> {
+        let limit = limit.unwrap_or(50) as usize;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let mut matches: Vec<BusMessage> = self.channels
+            .iter()
+            .filter(|(name, _)| topic.as_ref().is_none_or(|t| t == *name))
+            .flat_map(|(_, channel)| channel.messages.iter().rev()
+                .filter(|m| m.expires_at.is_none_or(|at| at > now) && m.payload.contains(&query))
+                .take(limit))
+            .cloned()
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.timestamp.cmp(&a.timestamp)
+                .then_with(|| a.topic.cmp(&b.topic))
+                .then_with(|| b.seq.cmp(&a.seq))
+        });
+        matches.truncate(limit);
+        self.with_read_by(matches)
     }
 
     /// Get bus statistics
@@ -208,7 +430,7 @@ impl RingBus {
         let mut per_topic: Vec<TopicCount> = Vec::new();
 
         for (topic, channel) in &self.channels {
-            let count = channel.len() as i64;
+            let count = channel.messages.len() as i64;
             total += count;
             per_topic.push(TopicCount {
                 topic: topic.clone(),
@@ -216,7 +438,7 @@ impl RingBus {
             });
         }
 
-        per_topic.sort_by(|a, b| b.count.cmp(&a.count));
+        per_topic.sort_by_key(|t| std::cmp::Reverse(t.count));
 
         BusStats {
             total_messages: total,
@@ -226,21 +448,286 @@ impl RingBus {
         }
     }
 
+    /// Cumulative published and consumed counts per topic, sorted by topic
+    #[napi]
+    pub fn throughput(&self) -> Vec<TopicThroughput> {
+        let mut per_topic: Vec<TopicThroughput> = self.channels
+            .iter()
+            .map(|(topic, channel)| TopicThroughput {
+                topic: topic.clone(),
+                published: channel.published,
+                consumed: channel.consumed,
+            })
+            .collect();
+        per_topic.sort_by(|a, b| a.topic.cmp(&b.topic));
+        per_topic
+    }
+
+    /// Zero the throughput counters
+    #[napi]
+    pub fn reset_throughput(&mut self) {
+        for channel in self.channels.values_mut() {
+            channel.published = 0;
+            channel.consumed = 0;
+        }
+    }
+
+    /// Remove a single message. Returns false if it was not found.
+    #[napi]
+    pub fn delete_message(&mut self, topic: String, id: String) -> bool {
+        let channel = match self.channels.get_mut(&topic) {
+            Some(channel) => channel,
+            None => return false,
+        };
+        match channel.messages.iter().position(|m| m.id == id) {
+            Some(index) => {
+                channel.messages.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop messages already consumed by every current subscriber of their
+    /// topic. Topics nobody subscribes to are left alone. Returns the number
+    /// of messages removed.
+    #[napi]
+    pub fn compact(&mut self) -> u32 {
+        let mut removed: u32 = 0;
+
+        for (topic, channel) in self.channels.iter_mut() {
+            let cursors: Vec<Option<&Cursor>> = self.subscribers
+                .iter()
+                .filter(|(_, patterns)| patterns.iter().any(|p| topic_matches(p, topic)))
+                .map(|(handle, _)| self.cursors.get(handle).and_then(|c| c.get(topic)))
+                .collect();
+            if cursors.is_empty() {
+                continue;
+            }
+
+            let before = channel.messages.len();
+            channel.messages.retain(|m| {
+                !cursors.iter().all(|c| c.is_some_and(|c| c.has_consumed(m.seq)))
+            });
+            removed += (before - channel.messages.len()) as u32;
+        }
+
+        removed
+    }
+
     /// Remove messages older than max_age_ms
     #[napi]
     pub fn drain_old(&mut self, max_age_ms: i64) -> u32 {
         let now = chrono::Utc::now().timestamp_millis();
         let cutoff = now - max_age_ms;
-        let mut removed: u32 = 0;
+        let mut drained: Vec<BusMessage> = Vec::new();
 
         for channel in self.channels.values_mut() {
-            let before = channel.len();
-            channel.retain(|m| m.timestamp >= cutoff);
-            removed += (before - channel.len()) as u32;
+            drained.extend(take_where(&mut channel.messages, |m| m.timestamp < cutoff));
         }
 
+        let removed = drained.len() as u32;
+        self.bury(drained);
         removed
     }
+
+    /// Unread messages that were evicted or expired, newest first. Empty
+    /// unless the bus was created with a dead-letter capacity.
+    #[napi]
+    pub fn read_dead_letters(&self, limit: Option<u32>) -> Vec<BusMessage> {
+        let limit = limit.unwrap_or(50) as usize;
+        self.with_read_by(self.dead_letters.iter().rev().take(limit).cloned().collect())
+    }
+}
+
+impl RingBus {
+    /// Append a message to its topic, evicting as needed, and return its id
+    fn publish_message(
+        &mut self,
+        topic: String,
+        sender: String,
+        priority: u32,
+        payload: String,
+        ttl_ms: Option<i64>,
+    ) -> String {
+        let now = chrono::Utc::now().timestamp_millis();
+        let id = format!("msg_{}", self.next_id);
+        self.next_id += 1;
+
+        let capacity = self.capacity_of(&topic);
+        let channel = self.channels.entry(topic.clone()).or_default();
+        channel.last_seq += 1;
+        channel.published += 1;
+
+        let msg = BusMessage {
+            id: id.clone(),
+            seq: channel.last_seq,
+            topic,
+            sender,
+            priority: priority.min(3),
+            payload,
+            timestamp: now,
+            read_by: String::new(),
+            expires_at: ttl_ms.map(|ttl| now.saturating_add(ttl)),
+        };
+
+        // Make room according to the eviction policy
+        let evicted = evict_to(&mut channel.messages, capacity - 1, self.eviction_policy);
+
+        channel.messages.push_back(msg);
+        self.bury(evicted);
+
+        id
+    }
+
+    fn capacity_of(&self, topic: &str) -> usize {
+        self.capacities.get(topic).copied().unwrap_or(MAX_MESSAGES_PER_TOPIC)
+    }
+
+    /// Gather messages from every topic the handle subscribes to, dropping
+    /// expired ones along the way. Unread mode considers every message past
+    /// the cursor; otherwise the newest `limit` per topic are candidates.
+    /// The result is ordered by priority (desc) then timestamp (asc).
+    fn collect_for(
+        &mut self,
+        handle: &str,
+        limit: usize,
+        unread_only: bool,
+        from_sender: Option<&str>,
+    ) -> Vec<BusMessage> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let patterns = match self.subscribers.get(handle) {
+            Some(patterns) => patterns,
+            None => return Vec::new(),
+        };
+        let cursors = self.cursors.get(handle);
+
+        let mut picked: Vec<BusMessage> = Vec::new();
+        let mut expired: Vec<BusMessage> = Vec::new();
+        for (topic, channel) in self.channels.iter_mut() {
+            if !patterns.iter().any(|p| topic_matches(p, topic)) {
+                continue;
+            }
+            expired.extend(remove_expired(&mut channel.messages, now));
+            let from = |m: &&BusMessage| from_sender.is_none_or(|s| m.sender == s);
+            if unread_only {
+                let cursor = cursors.and_then(|c| c.get(topic));
+                // Messages are ordered by seq, so skip everything at or
+                // below the cursor without scanning it
+                let start = cursor
+                    .map_or(0, |c| channel.messages.partition_point(|m| m.seq <= c.seq));
+                picked.extend(channel.messages.range(start..)
+                    .filter(from)
+                    .filter(|m| !cursor.is_some_and(|c| c.has_consumed(m.seq)))
+                    .cloned());
+            } else {
+                picked.extend(channel.messages.iter()
+                    .rev()
+                    .filter(from)
+                    .take(limit)
+                    .cloned());
+            }
+        }
+
+        self.bury(expired);
+
+        picked.sort_by(|a, b| {
+            b.priority.cmp(&a.priority).then(a.timestamp.cmp(&b.timestamp))
+        });
+        picked.truncate(limit);
+        picked
+    }
+
+    /// Move removed messages that some subscriber never consumed into the
+    /// dead-letter queue, if enabled. Messages on topics nobody subscribes
+    /// to count as unread.
+    fn bury(&mut self, removed: Vec<BusMessage>) {
+        if self.dead_letter_capacity == 0 {
+            return;
+        }
+        for msg in removed {
+            if !self.fully_consumed(&msg) {
+                if self.dead_letters.len() >= self.dead_letter_capacity {
+                    self.dead_letters.pop_front();
+                }
+                self.dead_letters.push_back(msg);
+            }
+        }
+    }
+
+    /// Whether every current subscriber of the message's topic has consumed it
+    fn fully_consumed(&self, msg: &BusMessage) -> bool {
+        let mut subscribers = self.subscribers
+            .iter()
+            .filter(|(_, patterns)| patterns.iter().any(|p| topic_matches(p, &msg.topic)))
+            .peekable();
+        subscribers.peek().is_some()
+            && subscribers.all(|(handle, _)| {
+                self.cursors
+                    .get(handle)
+                    .and_then(|c| c.get(&msg.topic))
+                    .is_some_and(|c| c.has_consumed(msg.seq))
+            })
+    }
+
+    /// Fill in `read_by` from the subscriber cursors
+    fn with_read_by(&self, picked: Vec<BusMessage>) -> Vec<BusMessage> {
+        picked
+            .into_iter()
+            .map(|mut msg| {
+                let mut handles: Vec<&str> = self.cursors
+                    .iter()
+                    .filter(|(_, topics)| topics.get(&msg.topic).is_some_and(|c| c.has_consumed(msg.seq)))
+                    .map(|(handle, _)| handle.as_str())
+                    .collect();
+                handles.sort_unstable();
+                msg.read_by = handles.join(",");
+                msg
+            })
+            .collect()
+    }
+}
+
+/// Evict messages until at most `capacity` remain, returning the evicted ones
+fn evict_to(messages: &mut VecDeque<BusMessage>, capacity: usize, policy: EvictionPolicy) -> Vec<BusMessage> {
+    let mut evicted = Vec::new();
+    while messages.len() > capacity {
+        let victim = policy.victim(messages);
+        evicted.extend(messages.remove(victim));
+    }
+    evicted
+}
+
+/// Drop messages whose TTL has elapsed, returning them
+fn remove_expired(channel: &mut VecDeque<BusMessage>, now: i64) -> Vec<BusMessage> {
+    take_where(channel, |m| m.expires_at.is_some_and(|at| at <= now))
+}
+
+/// Remove and return the messages matching `pred`, keeping the rest in order
+fn take_where(messages: &mut VecDeque<BusMessage>, pred: impl Fn(&BusMessage) -> bool) -> Vec<BusMessage> {
+    if !messages.iter().any(&pred) {
+        return Vec::new();
+    }
+    let (taken, kept): (VecDeque<BusMessage>, VecDeque<BusMessage>) =
+        std::mem::take(messages).into_iter().partition(|m| pred(m));
+    *messages = kept;
+    taken.into()
+}
+
+/// Match a topic against a subscription pattern, segment by segment.
+/// `*` matches exactly one segment; `**` matches one or more remaining segments.
+fn topic_matches(pattern: &str, topic: &str) -> bool {
+    let mut topic_segments = topic.split('.');
+    for segment in pattern.split('.') {
+        if segment == "**" {
+            return topic_segments.next().is_some();
+        }
+        match topic_segments.next() {
+            Some(t) if segment == "*" || segment == t => {}
+            _ => return false,
+        }
+    }
+    topic_segments.next().is_none()
 }
 
 #[cfg(test)]
@@ -249,13 +736,13 @@ mod tests {
 
     #[test]
     fn test_publish_and_read() {
-        let mut bus = RingBus::new();
+        let mut bus = RingBus::new(None);
         bus.subscribe("w1".into(), "tasks".into());
 
-        bus.publish("tasks".into(), "lead".into(), 1, r#"{"task":"build"}"#.into());
-        bus.publish("tasks".into(), "lead".into(), 2, r#"{"task":"test"}"#.into());
+        bus.publish("tasks".into(), "lead".into(), 1, r#"{"task":"build"}"#.into(), None).unwrap();
+        bus.publish("tasks".into(), "lead".into(), 2, r#"{"task":"test"}"#.into(), None).unwrap();
 
-        let msgs = bus.read("w1".into(), Some(10), Some(true));
+        let msgs = bus.read("w1".into(), Some(10), Some(true), None, None);
         assert_eq!(msgs.len(), 2);
         // Higher priority first
         assert_eq!(msgs[0].priority, 2);
@@ -263,24 +750,165 @@ mod tests {
 
     #[test]
     fn test_unread_filtering() {
-        let mut bus = RingBus::new();
+        let mut bus = RingBus::new(None);
         bus.subscribe("w1".into(), "chat".into());
 
-        bus.publish("chat".into(), "lead".into(), 1, "hello".into());
+        bus.publish("chat".into(), "lead".into(), 1, "hello".into(), None).unwrap();
 
-        let first = bus.read("w1".into(), Some(10), Some(true));
+        let first = bus.read("w1".into(), Some(10), Some(true), None, None);
         assert_eq!(first.len(), 1);
 
-        let second = bus.read("w1".into(), Some(10), Some(true));
+        let second = bus.read("w1".into(), Some(10), Some(true), None, None);
         assert_eq!(second.len(), 0); // Already read
     }
 
+    #[test]
+    fn test_cursors_do_not_collide_on_handle_prefix() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "chat".into());
+        bus.subscribe("w12".into(), "chat".into());
+        bus.publish("chat".into(), "lead".into(), 1, "hello".into(), None).unwrap();
+
+        assert_eq!(bus.read("w12".into(), None, None, None, None).len(), 1);
+        let msgs = bus.read("w1".into(), None, None, None, None);
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].read_by, "w1,w12");
+    }
+
+    #[test]
+    fn test_peek_and_seek() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "log".into());
+        for i in 1..=3 {
+            bus.publish("log".into(), "lead".into(), 1, format!("m{}", i), None).unwrap();
+        }
+
+        assert_eq!(bus.peek("w1".into(), None).len(), 3);
+        let first = bus.read("w1".into(), Some(2), None, None, None);
+        assert_eq!(first.iter().map(|m| m.payload.as_str()).collect::<Vec<_>>(), vec!["m1", "m2"]);
+        let rest = bus.read("w1".into(), None, None, None, None);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].payload, "m3");
+        assert!(bus.peek("w1".into(), None).is_empty());
+
+        bus.seek("w1".into(), "log".into(), 1);
+        let replay = bus.read("w1".into(), None, None, None, None);
+        assert_eq!(replay.iter().map(|m| m.payload.as_str()).collect::<Vec<_>>(), vec!["m2", "m3"]);
+    }
+
+    #[test]
+    fn test_out_of_order_consumption() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "jobs".into());
+        bus.publish("jobs".into(), "lead".into(), 0, "low".into(), None).unwrap();
+        bus.publish("jobs".into(), "lead".into(), 3, "urgent".into(), None).unwrap();
+
+        // The urgent message is delivered first; the older one must not be skipped
+        assert_eq!(bus.read("w1".into(), Some(1), None, None, None)[0].payload, "urgent");
+        assert_eq!(bus.read("w1".into(), Some(1), None, None, None)[0].payload, "low");
+        assert!(bus.read("w1".into(), None, None, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_single_segment_wildcard() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "tasks.*".into());
+
+        bus.publish("tasks.build".into(), "lead".into(), 1, "b".into(), None).unwrap();
+        bus.publish("tasks.test".into(), "lead".into(), 1, "t".into(), None).unwrap();
+        bus.publish("tasks.test.unit".into(), "lead".into(), 1, "u".into(), None).unwrap();
+        bus.publish("tasks".into(), "lead".into(), 1, "root".into(), None).unwrap();
+        bus.publish("chat.tasks".into(), "lead".into(), 1, "c".into(), None).unwrap();
+
+        let mut payloads: Vec<String> = bus.read("w1".into(), Some(10), Some(true), None, None)
+            .into_iter()
+            .map(|m| m.payload)
+            .collect();
+        payloads.sort();
+        assert_eq!(payloads, vec!["b", "t"]);
+    }
+
+    #[test]
+    fn test_multi_segment_wildcard() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "tasks.**".into());
+        bus.subscribe("w1".into(), "tasks.build".into());
+
+        bus.publish("tasks.build".into(), "lead".into(), 1, "b".into(), None).unwrap();
+        bus.publish("tasks.test.unit".into(), "lead".into(), 1, "u".into(), None).unwrap();
+        bus.publish("tasks".into(), "lead".into(), 1, "root".into(), None).unwrap();
+        bus.publish("other.test".into(), "lead".into(), 1, "o".into(), None).unwrap();
+
+        let mut payloads: Vec<String> = bus.read("w1".into(), Some(10), Some(true), None, None)
+            .into_iter()
+            .map(|m| m.payload)
+            .collect();
+        payloads.sort();
+        // Overlapping patterns must not deliver a message twice
+        assert_eq!(payloads, vec!["b", "u"]);
+
+        assert!(topic_matches("a.*.c", "a.b.c"));
+        assert!(!topic_matches("a.*.c", "a.b.d"));
+        assert!(topic_matches("**", "anything.at.all"));
+    }
+
+    #[test]
+    fn test_message_ttl() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "events".into());
+
+        bus.publish("events".into(), "lead".into(), 1, "short".into(), Some(20)).unwrap();
+        bus.publish("events".into(), "lead".into(), 1, "forever".into(), None).unwrap();
+        assert_eq!(bus.read_topic("events".into(), None, None).len(), 2);
+
+        std::thread::sleep(std::time::Duration::from_millis(40));
+        let msgs = bus.read("w1".into(), Some(10), Some(true), None, None);
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].payload, "forever");
+        assert!(msgs[0].expires_at.is_none());
+        assert_eq!(bus.read_topic("events".into(), None, None).len(), 1);
+        assert_eq!(bus.stats().total_messages, 1);
+
+        assert!(bus.publish("events".into(), "lead".into(), 1, "x".into(), Some(0)).is_err());
+        assert!(bus.publish("events".into(), "lead".into(), 1, "x".into(), Some(-5)).is_err());
+        // A huge TTL saturates rather than overflowing into the past
+        bus.publish("events".into(), "lead".into(), 1, "long".into(), Some(i64::MAX)).unwrap();
+        let msgs = bus.read_topic("events".into(), None, None);
+        assert_eq!(msgs.len(), 2);
+        let long = msgs.iter().find(|m| m.payload == "long").unwrap();
+        assert_eq!(long.expires_at, Some(i64::MAX));
+    }
+
+    #[test]
+    fn test_unacked_messages_are_redelivered() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "jobs".into());
+        let first = bus.publish("jobs".into(), "lead".into(), 1, "a".into(), None).unwrap();
+        let second = bus.publish("jobs".into(), "lead".into(), 1, "b".into(), None).unwrap();
+
+        // Delivered but not acknowledged: the consumer "crashes" here
+        let msgs = bus.read("w1".into(), None, None, Some(false), None);
+        assert_eq!(msgs.len(), 2);
+        assert!(msgs[0].read_by.is_empty());
+
+        let again = bus.read("w1".into(), None, None, Some(false), None);
+        assert_eq!(again.len(), 2);
+
+        assert_eq!(bus.ack("w1".into(), vec![second, "msg_missing".into()]), 1);
+        let remaining = bus.read("w1".into(), None, None, Some(false), None);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, first);
+
+        bus.ack("w1".into(), vec![first]);
+        assert!(bus.read("w1".into(), None, None, Some(false), None).is_empty());
+    }
+
     #[test]
     fn test_stats() {
-        let mut bus = RingBus::new();
-        bus.publish("a".into(), "s".into(), 0, "p".into());
-        bus.publish("a".into(), "s".into(), 0, "p".into());
-        bus.publish("b".into(), "s".into(), 0, "p".into());
+        let mut bus = RingBus::new(None);
+        bus.publish("a".into(), "s".into(), 0, "p".into(), None).unwrap();
+        bus.publish("a".into(), "s".into(), 0, "p".into(), None).unwrap();
+        bus.publish("b".into(), "s".into(), 0, "p".into(), None).unwrap();
 
         let stats = bus.stats();
         assert_eq!(stats.total_messages, 3);
@@ -289,9 +917,9 @@ mod tests {
 
     #[test]
     fn test_ring_buffer_eviction() {
-        let mut bus = RingBus::new();
+        let mut bus = RingBus::new(None);
         for i in 0..(MAX_MESSAGES_PER_TOPIC + 100) {
-            bus.publish("flood".into(), "s".into(), 0, format!("{}", i));
+            bus.publish("flood".into(), "s".into(), 0, format!("{}", i), None).unwrap();
         }
 
         let stats = bus.stats();
@@ -301,4 +929,250 @@ mod tests {
             .unwrap_or(0);
         assert_eq!(flood_count, MAX_MESSAGES_PER_TOPIC as i64);
     }
+
+    #[test]
+    fn test_priority_eviction_keeps_urgent_messages() {
+        let mut bus = RingBus::new(None);
+        assert!(bus.set_eviction_policy("lifo".into()).is_err());
+        bus.set_eviction_policy("priority".into()).unwrap();
+
+        bus.publish("flood".into(), "s".into(), 3, "urgent".into(), None).unwrap();
+        for i in 0..(MAX_MESSAGES_PER_TOPIC + 100) {
+            bus.publish("flood".into(), "s".into(), 0, format!("{}", i), None).unwrap();
+        }
+
+        let msgs = bus.read_topic("flood".into(), Some(MAX_MESSAGES_PER_TOPIC as u32), None);
+        assert_eq!(msgs.len(), MAX_MESSAGES_PER_TOPIC);
+        assert_eq!(msgs.last().unwrap().payload, "urgent");
+        // The oldest low-priority messages went first
+        assert_eq!(msgs[msgs.len() - 2].payload, "101");
+    }
+
+    #[test]
+    fn test_topic_capacity() {
+        let mut bus = RingBus::new(None);
+        bus.set_topic_capacity("decisions".into(), 3);
+        for i in 0..10 {
+            bus.publish("decisions".into(), "s".into(), 1, format!("d{}", i), None).unwrap();
+            bus.publish("logs".into(), "s".into(), 1, format!("l{}", i), None).unwrap();
+        }
+
+        let decisions = bus.read_topic("decisions".into(), None, None);
+        assert_eq!(decisions.iter().map(|m| m.payload.as_str()).collect::<Vec<_>>(), vec!["d9", "d8", "d7"]);
+        assert_eq!(bus.read_topic("logs".into(), None, None).len(), 10);
+
+        // Lowering below the current size trims right away
+        bus.set_eviction_policy("priority".into()).unwrap();
+        bus.publish("logs".into(), "s".into(), 3, "urgent".into(), None).unwrap();
+        bus.set_topic_capacity("logs".into(), 2);
+        let logs = bus.read_topic("logs".into(), None, None);
+        assert_eq!(logs.iter().map(|m| m.payload.as_str()).collect::<Vec<_>>(), vec!["urgent", "l9"]);
+    }
+
+    #[test]
+    fn test_delete_message() {
+        let mut bus = RingBus::new(None);
+        let id = bus.publish("t".into(), "s".into(), 1, "a".into(), None).unwrap();
+        bus.publish("t".into(), "s".into(), 1, "b".into(), None).unwrap();
+
+        assert!(!bus.delete_message("other".into(), id.clone()));
+        assert!(bus.delete_message("t".into(), id.clone()));
+        assert!(!bus.delete_message("t".into(), id));
+        let msgs = bus.read_topic("t".into(), None, None);
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].payload, "b");
+    }
+
+    #[test]
+    fn test_compact_keeps_unread_messages() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "jobs".into());
+        bus.subscribe("w2".into(), "jobs.*".into());
+        bus.publish("jobs".into(), "s".into(), 1, "shared".into(), None).unwrap();
+        bus.publish("jobs".into(), "s".into(), 1, "pending".into(), None).unwrap();
+        bus.publish("orphan".into(), "s".into(), 1, "nobody listens".into(), None).unwrap();
+
+        // w2's pattern does not match "jobs", so only w1 has to consume it
+        bus.read("w1".into(), Some(1), None, None, None);
+        assert_eq!(bus.compact(), 1);
+        let msgs = bus.read_topic("jobs".into(), None, None);
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].payload, "pending");
+        assert_eq!(bus.read_topic("orphan".into(), None, None).len(), 1);
+
+        bus.subscribe("w3".into(), "jobs".into());
+        bus.read("w1".into(), None, None, None, None);
+        assert_eq!(bus.compact(), 0);
+        bus.read("w3".into(), None, None, None, None);
+        assert_eq!(bus.compact(), 1);
+        assert_eq!(bus.stats().total_messages, 1);
+    }
+
+    #[test]
+    fn test_subscription_introspection() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w2".into(), "tasks.*".into());
+        bus.subscribe("w1".into(), "tasks.build".into());
+        bus.subscribe("w1".into(), "chat".into());
+        bus.subscribe("w3".into(), "chat".into());
+        bus.unsubscribe("w3".into(), "chat".into());
+
+        assert_eq!(bus.subscribers_of("tasks.build".into()), vec!["w1", "w2"]);
+        assert_eq!(bus.subscribers_of("tasks.test".into()), vec!["w2"]);
+        assert_eq!(bus.subscribers_of("chat".into()), vec!["w1"]);
+        assert_eq!(bus.topics_of("w1".into()), vec!["chat", "tasks.build"]);
+        assert!(bus.topics_of("w3".into()).is_empty());
+        assert!(bus.topics_of("nobody".into()).is_empty());
+    }
+
+    #[test]
+    fn test_filter_by_sender() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "chat".into());
+        for i in 0..3 {
+            bus.publish("chat".into(), "alice".into(), 1, format!("a{}", i), None).unwrap();
+            bus.publish("chat".into(), "bob".into(), 1, format!("b{}", i), None).unwrap();
+        }
+
+        let from_bob = bus.read_topic("chat".into(), Some(2), Some("bob".into()));
+        assert_eq!(from_bob.iter().map(|m| m.payload.as_str()).collect::<Vec<_>>(), vec!["b2", "b1"]);
+
+        let from_alice = bus.read("w1".into(), None, None, None, Some("alice".into()));
+        assert_eq!(from_alice.len(), 3);
+        assert!(from_alice.iter().all(|m| m.sender == "alice"));
+
+        // Bob's messages are still unread
+        let rest = bus.read("w1".into(), None, None, None, None);
+        assert_eq!(rest.len(), 3);
+        assert!(rest.iter().all(|m| m.sender == "bob"));
+    }
+
+    #[test]
+    fn test_per_topic_sequence_numbers() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "a".into());
+        bus.set_topic_capacity("a".into(), 2);
+        for i in 0..4 {
+            bus.publish("a".into(), "s".into(), 1, format!("a{}", i), None).unwrap();
+            bus.publish("b".into(), "s".into(), 1, format!("b{}", i), None).unwrap();
+        }
+
+        let a = bus.read_topic("a".into(), None, None);
+        assert_eq!(a.iter().map(|m| m.seq).collect::<Vec<_>>(), vec![4, 3]);
+        let b = bus.read_topic("b".into(), None, None);
+        assert_eq!(b.iter().map(|m| m.seq).collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        // Global ids stay unique across topics
+        assert_eq!(a[0].id, "msg_7");
+        assert_eq!(b[0].id, "msg_8");
+
+        // Resume from a known sequence number
+        bus.seek("w1".into(), "a".into(), 3);
+        let resumed = bus.read("w1".into(), None, None, None, None);
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].seq, 4);
+    }
+
+    #[test]
+    fn test_search_payload() {
+        let mut bus = RingBus::new(None);
+        bus.publish("tasks".into(), "lead".into(), 1, r#"{"task":"build","target":"api"}"#.into(), None).unwrap();
+        bus.publish("tasks".into(), "lead".into(), 1, r#"{"task":"test"}"#.into(), None).unwrap();
+        bus.publish("chat".into(), "w1".into(), 1, r#"{"text":"api is down"}"#.into(), None).unwrap();
+        bus.publish("tasks".into(), "lead".into(), 1, r#"{"task":"deploy","target":"api"}"#.into(), None).unwrap();
+
+        let all = bus.search_payload("api".into(), None, None);
+        assert_eq!(all.len(), 3);
+        assert!(all.windows(2).all(|w| w[0].timestamp >= w[1].timestamp));
+
+        let tasks = bus.search_payload("api".into(), Some("tasks".into()), None);
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks[0].payload.contains("deploy"));
+
+        assert_eq!(bus.search_payload("api".into(), Some("tasks".into()), Some(1)).len(), 1);
+        assert!(bus.search_payload("nothing".into(), None, None).is_empty());
+    }
+
+    #[test]
+    fn test_publish_many() {
+        let mut bus = RingBus::new(None);
+        bus.set_topic_capacity("small".into(), 1);
+        bus.publish("small".into(), "s".into(), 1, "old".into(), None).unwrap();
+        bus.publish("big".into(), "s".into(), 1, "old".into(), None).unwrap();
+
+        let ids = bus.publish_many(
+            vec!["big".into(), "small".into(), "fresh".into()],
+            "lead".into(),
+            2,
+            "announce".into(),
+        );
+        assert_eq!(ids, vec!["msg_3", "msg_4", "msg_5"]);
+
+        for topic in ["big", "small", "fresh"] {
+            let latest = &bus.read_topic(topic.into(), Some(1), None)[0];
+            assert_eq!(latest.payload, "announce");
+            assert_eq!(latest.sender, "lead");
+        }
+        assert_eq!(bus.read_topic("big".into(), None, None)[0].seq, 2);
+        assert_eq!(bus.read_topic("fresh".into(), None, None)[0].seq, 1);
+        // Eviction applies per topic
+        assert_eq!(bus.read_topic("small".into(), None, None).len(), 1);
+        assert_eq!(bus.read_topic("big".into(), None, None).len(), 2);
+    }
+
+    #[test]
+    fn test_throughput() {
+        let mut bus = RingBus::new(None);
+        bus.subscribe("w1".into(), "hot".into());
+        bus.subscribe("w2".into(), "hot".into());
+        bus.set_topic_capacity("hot".into(), 2);
+        for i in 0..5 {
+            bus.publish("hot".into(), "s".into(), 1, format!("{}", i), None).unwrap();
+        }
+        bus.publish("cold".into(), "s".into(), 1, "x".into(), None).unwrap();
+        bus.read("w1".into(), None, None, None, None);
+        bus.read("w2".into(), Some(1), None, None, None);
+        bus.peek("w2".into(), None);
+
+        let stats = bus.throughput();
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].topic.as_str(), stats[0].published, stats[0].consumed), ("cold", 1, 0));
+        // Counters are cumulative, not limited to retained messages
+        assert_eq!((stats[1].topic.as_str(), stats[1].published, stats[1].consumed), ("hot", 5, 3));
+
+        bus.reset_throughput();
+        assert!(bus.throughput().iter().all(|t| t.published == 0 && t.consumed == 0));
+    }
+
+    #[test]
+    fn test_dead_letters_capture_evicted_unread_messages() {
+        let mut bus = RingBus::new(Some(3));
+        bus.subscribe("w1".into(), "flood".into());
+        bus.set_topic_capacity("flood".into(), 2);
+
+        bus.publish("flood".into(), "s".into(), 1, "read".into(), None).unwrap();
+        bus.read("w1".into(), None, None, None, None);
+        for i in 0..6 {
+            bus.publish("flood".into(), "s".into(), 1, format!("m{}", i), None).unwrap();
+        }
+
+        // "read" was consumed before eviction; m0..m3 were not, and the queue keeps the last 3
+        let dead = bus.read_dead_letters(None);
+        assert_eq!(dead.iter().map(|m| m.payload.as_str()).collect::<Vec<_>>(), vec!["m3", "m2", "m1"]);
+        assert_eq!(bus.read_dead_letters(Some(1)).len(), 1);
+
+        bus.publish("flood".into(), "s".into(), 1, "ttl".into(), Some(1)).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        bus.read_topic("flood".into(), None, None);
+        assert_eq!(bus.read_dead_letters(Some(1))[0].payload, "ttl");
+    }
+
+    #[test]
+    fn test_dead_letters_disabled_by_default() {
+        let mut bus = RingBus::new(None);
+        bus.set_topic_capacity("flood".into(), 1);
+        for i in 0..5 {
+            bus.publish("flood".into(), "s".into(), 1, format!("m{}", i), None).unwrap();
+        }
+        assert!(bus.read_dead_letters(None).is_empty());
+    }
 }
@@ -33,8 +33,18 @@ export declare class SearchIndex {
   commit(): void
   /** Search for sessions matching the query */
   search(query: string, limit?: number | undefined | null): Array<SearchResult>
+  /**
+   * Search with per-field score boosts
+   *
+   * Every boosted field is added to the default search fields alongside
+   * `content`, so matches in higher-boosted fields rank first. Boosts
+   * must be finite and positive.
+   */
+  searchBoosted(query: string, fieldBoosts: Record<string, number>, limit?: number | undefined | null): Array<SearchResult>
   /** Delete a session from the index */
   deleteSession(sessionId: string): void
+  /** Reload the reader to see the latest committed changes */
+  reload(): void
   /** Get index statistics */
   stats(): IndexStats
   /** Launch the interactive TUI for searching */
@@ -5,14 +5,15 @@
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use tantivy::{
     collector::TopDocs,
     directory::MmapDirectory,
     doc,
-    query::QueryParser,
-    schema::{Schema, Value, STORED, TEXT},
+    query::{Query, QueryParser},
+    schema::{Field, FieldType, Schema, Value, STORED, TEXT},
     Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument,
 };
 
@@ -141,61 +142,62 @@ impl SearchIndex {
     /// Search for sessions matching the query
     #[napi]
     pub fn search(&self, query: String, limit: Option<u32>) -> Result<Vec<SearchResult>> {
-        let limit = limit.unwrap_or(20) as usize;
-
-        let searcher = self.reader.searcher();
         let content_field = self.schema.get_field("content").unwrap();
-        let session_id_field = self.schema.get_field("session_id").unwrap();
-        let timestamp_field = self.schema.get_field("timestamp").unwrap();
-        let model_field = self.schema.get_field("model").unwrap();
 
         let query_parser = QueryParser::for_index(&self.index, vec![content_field]);
         let parsed_query = query_parser.parse_query(&query).map_err(|e| {
             Error::new(Status::GenericFailure, format!("Failed to parse query: {}", e))
         })?;
 
-        let top_docs = searcher
-            .search(&parsed_query, &TopDocs::with_limit(limit))
-            .map_err(|e| Error::new(Status::GenericFailure, format!("Search failed: {}", e)))?;
+        self.execute(&*parsed_query, limit)
+    }
 
-        let mut results = Vec::new();
-        for (score, doc_address) in top_docs {
-            let retrieved_doc: TantivyDocument = searcher.doc(doc_address).map_err(|e| {
-                Error::new(Status::GenericFailure, format!("Failed to retrieve doc: {}", e))
+    /// Search with per-field score boosts
+    ///
+    /// Every boosted field is added to the default search fields alongside
+    /// `content`, so matches in higher-boosted fields rank first. Boosts
+    /// must be finite and positive.
+    #[napi]
+    pub fn search_boosted(
+        &self,
+        query: String,
+        field_boosts: HashMap<String, f64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<SearchResult>> {
+        let mut default_fields = vec![self.schema.get_field("content").unwrap()];
+        let mut boosts: Vec<(Field, f64)> = Vec::with_capacity(field_boosts.len());
+
+        for (name, boost) in &field_boosts {
+            if !boost.is_finite() || *boost <= 0.0 {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Boost for {} must be a positive number, got {}", name, boost),
+                ));
+            }
+            let field = self.schema.get_field(name).map_err(|_| {
+                Error::new(Status::InvalidArg, format!("Unknown field: {}", name))
             })?;
+            if !matches!(self.schema.get_field_entry(field).field_type(), FieldType::Str(_)) {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Field is not a text field: {}", name),
+                ));
+            }
+            if !default_fields.contains(&field) {
+                default_fields.push(field);
+            }
+            boosts.push((field, *boost));
+        }
 
-            let session_id = retrieved_doc
-                .get_first(session_id_field)
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-
-            let snippet = retrieved_doc
-                .get_first(content_field)
-                .and_then(|v| v.as_str())
-                .map(|s: &str| s.chars().take(200).collect::<String>())
-                .unwrap_or_default();
-
-            let timestamp = retrieved_doc
-                .get_first(timestamp_field)
-                .and_then(|v| v.as_i64())
-                .unwrap_or(0);
-
-            let model = retrieved_doc
-                .get_first(model_field)
-                .and_then(|v| v.as_str())
-                .map(|s: &str| s.to_string());
-
-            results.push(SearchResult {
-                session_id,
-                score: score as f64,
-                snippet,
-                timestamp,
-                model,
-            });
+        let mut query_parser = QueryParser::for_index(&self.index, default_fields);
+        for (field, boost) in boosts {
+            query_parser.set_field_boost(field, boost as f32);
         }
+        let parsed_query = query_parser.parse_query(&query).map_err(|e| {
+            Error::new(Status::GenericFailure, format!("Failed to parse query: {}", e))
+        })?;
 
-        Ok(results)
+        self.execute(&*parsed_query, limit)
     }
 
     /// Delete a session from the index
@@ -240,6 +242,62 @@ impl SearchIndex {
     }
 }
 
+impl SearchIndex {
+    /// Run a parsed query and collect the top results
+    fn execute(&self, query: &dyn Query, limit: Option<u32>) -> Result<Vec<SearchResult>> {
+        let limit = limit.unwrap_or(20) as usize;
+
+        let searcher = self.reader.searcher();
+        let content_field = self.schema.get_field("content").unwrap();
+        let session_id_field = self.schema.get_field("session_id").unwrap();
+        let timestamp_field = self.schema.get_field("timestamp").unwrap();
+        let model_field = self.schema.get_field("model").unwrap();
+
+        let top_docs = searcher
+            .search(query, &TopDocs::with_limit(limit))
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Search failed: {}", e)))?;
+
+        let mut results = Vec::new();
+        for (score, doc_address) in top_docs {
+            let retrieved_doc: TantivyDocument = searcher.doc(doc_address).map_err(|e| {
+                Error::new(Status::GenericFailure, format!("Failed to retrieve doc: {}", e))
+            })?;
+
+            let session_id = retrieved_doc
+                .get_first(session_id_field)
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string();
+
+            let snippet = retrieved_doc
+                .get_first(content_field)
+                .and_then(|v| v.as_str())
+                .map(|s: &str| s.chars().take(200).collect::<String>())
+                .unwrap_or_default();
+
+            let timestamp = retrieved_doc
+                .get_first(timestamp_field)
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+
+            let model = retrieved_doc
+                .get_first(model_field)
+                .and_then(|v| v.as_str())
+                .map(|s: &str| s.to_string());
+
+            results.push(SearchResult {
+                session_id,
+                score: score as f64,
+                snippet,
+                timestamp,
+                model,
+            });
+        }
+
+        Ok(results)
+    }
+}
+
 /// Index statistics
 #[napi(object)]
 pub struct IndexStats {
@@ -335,4 +393,31 @@ mod tests {
         let results = idx.search("rust programming".to_string(), Some(3)).unwrap();
         assert_eq!(results.len(), 3);
     }
+
+    #[test]
+    fn test_search_boosted() {
+        let (idx, _dir) = temp_index();
+        idx.index_session(make_session("body", "notes about the parser rewrite")).unwrap();
+        let mut titled = make_session("title", "unrelated content");
+        titled.project_path = Some("/work/parser".to_string());
+        idx.index_session(titled).unwrap();
+        idx.commit().unwrap();
+        idx.reload().unwrap();
+
+        let mut boosts = HashMap::new();
+        boosts.insert("project_path".to_string(), 10.0);
+        let results = idx.search_boosted("parser".to_string(), boosts, None).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].session_id, "title");
+
+        let mut unknown = HashMap::new();
+        unknown.insert("nope".to_string(), 2.0);
+        assert!(idx.search_boosted("parser".to_string(), unknown, None).is_err());
+
+        for bad in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let mut boosts = HashMap::new();
+            boosts.insert("project_path".to_string(), bad);
+            assert!(idx.search_boosted("parser".to_string(), boosts, None).is_err());
+        }
+    }
 }
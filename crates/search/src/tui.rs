@@ -43,7 +43,6 @@ struct SearchResultItem {
     session_id: String,
     snippet: String,
     score: f32,
-    timestamp: i64,
 }
 
 impl App {
@@ -118,7 +117,6 @@ pub fn run_tui(index: &Index, reader: &IndexReader, schema: &Schema) -> Result<(
     let mut app = App::new();
     let content_field = schema.get_field("content").unwrap();
     let session_id_field = schema.get_field("session_id").unwrap();
-    let timestamp_field = schema.get_field("timestamp").unwrap();
 
     loop {
         terminal.draw(|f| ui(f, &app))?;
@@ -132,45 +130,38 @@ pub fn run_tui(index: &Index, reader: &IndexReader, schema: &Schema) -> Result<(
                 match app.mode {
                     Mode::Search => match key.code {
                         KeyCode::Esc => break,
-                        KeyCode::Enter => {
+                        KeyCode::Enter if !app.input.is_empty() => {
                             // Execute search
-                            if !app.input.is_empty() {
-                                let searcher = reader.searcher();
-                                let query_parser = QueryParser::for_index(index, vec![content_field]);
-                                if let Ok(query) = query_parser.parse_query(&app.input) {
-                                    if let Ok(top_docs) = searcher.search(&query, &TopDocs::with_limit(50)) {
-                                        app.results.clear();
-                                        for (score, doc_address) in top_docs {
-                                            if let Ok(doc) = searcher.doc::<TantivyDocument>(doc_address) {
-                                                let session_id = doc
-                                                    .get_first(session_id_field)
-                                                    .and_then(|v| v.as_str())
-                                                    .unwrap_or("")
-                                                    .to_string();
-                                                let snippet = doc
-                                                    .get_first(content_field)
-                                                    .and_then(|v| v.as_str())
-                                                    .map(|s: &str| s.chars().take(100).collect::<String>())
-                                                    .unwrap_or_default();
-                                                let timestamp = doc
-                                                    .get_first(timestamp_field)
-                                                    .and_then(|v| v.as_i64())
-                                                    .unwrap_or(0);
+                            let searcher = reader.searcher();
+                            let query_parser = QueryParser::for_index(index, vec![content_field]);
+                            if let Ok(query) = query_parser.parse_query(&app.input) {
+                                if let Ok(top_docs) = searcher.search(&query, &TopDocs::with_limit(50)) {
+                                    app.results.clear();
+                                    for (score, doc_address) in top_docs {
+                                        if let Ok(doc) = searcher.doc::<TantivyDocument>(doc_address) {
+                                            let session_id = doc
+                                                .get_first(session_id_field)
+                                                .and_then(|v| v.as_str())
+                                                .unwrap_or("")
+                                                .to_string();
+                                            let snippet = doc
+                                                .get_first(content_field)
+                                                .and_then(|v| v.as_str())
+                                                .map(|s: &str| s.chars().take(100).collect::<String>())
+                                                .unwrap_or_default();
 
-                                                app.results.push(SearchResultItem {
-                                                    session_id,
-                                                    snippet,
-                                                    score,
-                                                    timestamp,
-                                                });
-                                            }
-                                        }
-                                        if !app.results.is_empty() {
-                                            app.selected = 0;
-                                            app.list_state.select(Some(0));
-                                            app.mode = Mode::Browse;
+                                            app.results.push(SearchResultItem {
+                                                session_id,
+                                                snippet,
+                                                score,
+                                            });
                                         }
                                     }
+                                    if !app.results.is_empty() {
+                                        app.selected = 0;
+                                        app.list_state.select(Some(0));
+                                        app.mode = Mode::Browse;
+                                    }
                                 }
                             }
                         }
@@ -2,16 +2,19 @@
 //!
 //! Pure computation — no storage access. Accepts data as JSON/NAPI objects,
 //! returns computed results. Covers:
-//! - Batch pheromone decay
+//! - Batch pheromone decay and reinforcement
 //! - Multi-factor bid evaluation
-//! - Vote tallying (majority, supermajority, ranked Borda, weighted)
-//! - Game-theoretic payoff calculation
+//! - Vote tallying (majority, supermajority, ranked Borda, weighted, instant-runoff,
+//!   approval, Condorcet)
+//! - Game-theoretic payoff calculation and pure-strategy Nash equilibria
 //! - ACO-style task routing
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 // ============================================================================
 // PHEROMONE DECAY
@@ -62,6 +65,21 @@ pub struct BidEvaluationResult {
     pub winner_score: f64,
 }
 
+#[napi(object)]
+#[derive(Clone, Debug, Serialize)]
+pub struct AuctionResult {
+    /// Bids sorted by composite score (best first)
+    pub ranked_bids: Vec<ScoredBid>,
+    /// The winning bid ID (empty when no bid meets the reserve)
+    pub winner_id: String,
+    /// Winner's composite score
+    pub winner_score: f64,
+    /// Score the winner "pays": the runner-up's score, floored at the reserve
+    pub price: f64,
+    /// Whether the best bid met the reserve score
+    pub reserve_met: bool,
+}
+
 #[napi(object)]
 #[derive(Clone, Debug, Serialize)]
 pub struct ScoredBid {
@@ -71,6 +89,41 @@ pub struct ScoredBid {
     pub reputation_component: f64,
     pub confidence_component: f64,
     pub bid_component: f64,
+    pub duration_component: f64,
+}
+
+/// Ordering applied to bids whose composite scores are equal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TieBreak {
+    Reputation,
+    LowestBid,
+    HandleAsc,
+}
+
+impl TieBreak {
+    fn parse(value: &str) -> Result<Self> {
+        match value {
+            "reputation" => Ok(TieBreak::Reputation),
+            "lowest_bid" => Ok(TieBreak::LowestBid),
+            "handle_asc" => Ok(TieBreak::HandleAsc),
+            other => Err(Error::new(
+                Status::InvalidArg,
+                format!("Unknown tie_break: {} (expected reputation, lowest_bid or handle_asc)", other),
+            )),
+        }
+    }
+
+    fn compare(self, a: &BidData, b: &BidData) -> std::cmp::Ordering {
+        let primary = match self {
+            TieBreak::Reputation => b.reputation.partial_cmp(&a.reputation),
+            TieBreak::LowestBid => a.bid_amount.partial_cmp(&b.bid_amount),
+            TieBreak::HandleAsc => Some(std::cmp::Ordering::Equal),
+        };
+        primary
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.bidder_handle.cmp(&b.bidder_handle))
+            .then_with(|| a.id.cmp(&b.id))
+    }
 }
 
 // ============================================================================
@@ -93,7 +146,14 @@ pub struct ConsensusResult {
     pub quorum_met: bool,
     pub total_votes: u32,
     pub weighted_total: f64,
+    /// Votes received over eligible voters, in [0, 1]; 1.0 when any votes
+    /// exist but no eligible count was given
     pub participation_rate: f64,
+    /// Per-round tallies for multi-round methods ("irv"); empty otherwise
+    pub rounds: Vec<TallyRound>,
+    /// Options caught in a majority cycle when "condorcet" finds no winner;
+    /// empty when only pairwise ties prevent one
+    pub cycle: Vec<String>,
 }
 
 #[napi(object)]
@@ -103,13 +163,41 @@ pub struct TallyEntry {
     pub count: f64,
 }
 
+#[napi(object)]
+#[derive(Clone, Debug, Serialize)]
+pub struct TallyRound {
+    pub round: u32,
+    pub tally: Vec<TallyEntry>,
+    /// Option eliminated at the end of this round, if any
+    pub eliminated: Option<String>,
+}
+
+/// Method-specific outcome of a vote tally
+struct TallyOutcome {
+    tally: Vec<TallyEntry>,
+    winner: Option<String>,
+    quorum_met: bool,
+    total_weight: f64,
+    rounds: Vec<TallyRound>,
+    cycle: Vec<String>,
+}
+
 // ============================================================================
 // SWARM ENGINE
 // ============================================================================
 
+/// Trail intensity assumed for unseen (worker, task) pairs, so they can still be explored
+const DEFAULT_TRAIL_INTENSITY: f64 = 0.1;
+
 #[napi]
 pub struct SwarmEngine {}
 
+impl Default for SwarmEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[napi]
 impl SwarmEngine {
     #[napi(constructor)]
@@ -131,30 +219,88 @@ impl SwarmEngine {
             .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid trails JSON: {}", e)))?;
 
         let factor = 1.0 - decay_rate;
-        let mut removed_ids: Vec<String> = Vec::new();
-
         for trail in &mut trails {
             trail.intensity *= factor;
-            if trail.intensity < min_intensity {
-                removed_ids.push(trail.id.clone());
-            }
         }
 
-        let removed_count = removed_ids.len() as u32;
+        Ok(remove_weak_trails(trails, min_intensity))
+    }
+
+    /// Process time-based pheromone evaporation.
+    /// Each trail decays by 0.5^((now - created_at) / half_life), so older
+    /// trails fade more. Trails created after `now_ms` are left unchanged.
+    #[napi]
+    pub fn process_decay_time_based(
+        &self,
+        trails_json: String,
+        half_life_ms: f64,
+        now_ms: i64,
+        min_intensity: f64,
+    ) -> Result<DecayResult> {
+        let mut trails: Vec<PheromoneTrailData> = serde_json::from_str(&trails_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid trails JSON: {}", e)))?;
 
-        // Keep only surviving trails in result
-        trails.retain(|t| t.intensity >= min_intensity);
+        if !half_life_ms.is_finite() || half_life_ms <= 0.0 {
+            return Err(Error::new(Status::InvalidArg, "half_life_ms must be a positive finite number"));
+        }
 
-        Ok(DecayResult {
-            trails,
-            removed_count,
-            removed_ids,
-        })
+        for trail in &mut trails {
+            let age = (now_ms - trail.created_at).max(0) as f64;
+            trail.intensity *= 0.5f64.powf(age / half_life_ms);
+        }
+
+        Ok(remove_weak_trails(trails, min_intensity))
+    }
+
+    /// Reinforce trails by adding deposit amounts (`{trail_id: amount}`) to their intensity.
+    /// Intensities are capped at `max_intensity` when given. Deposits to unknown
+    /// trail IDs are ignored unless `create_missing` is set, in which case new
+    /// trails are appended (ordered by ID) with `created_at` set to now.
+    #[napi]
+    pub fn deposit(
+        &self,
+        trails_json: String,
+        deposits_json: String,
+        max_intensity: Option<f64>,
+        create_missing: Option<bool>,
+    ) -> Result<Vec<PheromoneTrailData>> {
+        let mut trails: Vec<PheromoneTrailData> = serde_json::from_str(&trails_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid trails JSON: {}", e)))?;
+        let mut deposits: BTreeMap<String, f64> = serde_json::from_str(&deposits_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid deposits JSON: {}", e)))?;
+
+        let clamp = |intensity: f64| max_intensity.map_or(intensity, |max| intensity.min(max));
+
+        for trail in &mut trails {
+            if let Some(amount) = deposits.remove(&trail.id) {
+                trail.intensity = clamp(trail.intensity + amount);
+            }
+        }
+
+        if create_missing.unwrap_or(false) && !deposits.is_empty() {
+            let now_ms = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or(0);
+            for (id, amount) in deposits {
+                trails.push(PheromoneTrailData { id, intensity: clamp(amount), created_at: now_ms });
+            }
+        }
+
+        Ok(trails)
     }
 
     /// Evaluate bids using weighted multi-factor scoring.
-    /// Factors: reputation, confidence, bid amount (lower is better by default).
+    /// Factors: reputation, confidence, bid amount (lower is better by default),
+    /// and estimated duration (always lower is better, weighted by
+    /// `duration_weight`, default 0).
+    ///
+    /// Equal composite scores are ordered by `tie_break`: "reputation" (higher
+    /// first, the default), "lowest_bid" (smaller bid amount first) or
+    /// "handle_asc" (bidder handle alphabetically). Remaining ties fall back to
+    /// bidder handle, then bid ID, so the ranking never depends on input order.
     #[napi]
+    #[allow(clippy::too_many_arguments)]
     pub fn evaluate_bids(
         &self,
         bids_json: String,
@@ -162,9 +308,12 @@ impl SwarmEngine {
         confidence_weight: f64,
         bid_weight: f64,
         prefer_lower_bids: bool,
+        tie_break: Option<String>,
+        duration_weight: Option<f64>,
     ) -> Result<BidEvaluationResult> {
         let bids: Vec<BidData> = serde_json::from_str(&bids_json)
             .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid bids JSON: {}", e)))?;
+        let tie_break = TieBreak::parse(tie_break.as_deref().unwrap_or("reputation"))?;
 
         if bids.is_empty() {
             return Ok(BidEvaluationResult {
@@ -177,29 +326,41 @@ impl SwarmEngine {
         // Normalize scores
         let max_bid = bids.iter().map(|b| b.bid_amount).fold(f64::MIN, f64::max);
         let max_rep = bids.iter().map(|b| b.reputation).fold(f64::MIN, f64::max);
-        let total_weight = reputation_weight + confidence_weight + bid_weight;
+        let max_duration = bids.iter().map(|b| b.estimated_duration).fold(f64::MIN, f64::max);
+        let duration_weight = duration_weight.unwrap_or(0.0);
+        let total_weight = reputation_weight + confidence_weight + bid_weight + duration_weight;
 
-        let mut scored: Vec<ScoredBid> = bids.iter().map(|b| {
+        let mut scored: Vec<(ScoredBid, &BidData)> = bids.iter().map(|b| {
             let rep_norm = if max_rep > 0.0 { b.reputation / max_rep } else { 0.0 };
             let bid_norm = if max_bid > 0.0 {
                 if prefer_lower_bids { 1.0 - (b.bid_amount / max_bid) } else { b.bid_amount / max_bid }
             } else { 0.0 };
+            let duration_norm = if max_duration > 0.0 { 1.0 - (b.estimated_duration / max_duration) } else { 0.0 };
 
             let rep_component = rep_norm * reputation_weight / total_weight;
             let conf_component = b.confidence * confidence_weight / total_weight;
             let bid_component = bid_norm * bid_weight / total_weight;
+            let duration_component = duration_norm * duration_weight / total_weight;
 
-            ScoredBid {
+            let scored_bid = ScoredBid {
                 id: b.id.clone(),
                 bidder_handle: b.bidder_handle.clone(),
-                composite_score: rep_component + conf_component + bid_component,
+                composite_score: rep_component + conf_component + bid_component + duration_component,
                 reputation_component: rep_component,
                 confidence_component: conf_component,
                 bid_component,
-            }
+                duration_component,
+            };
+            (scored_bid, b)
         }).collect();
 
-        scored.sort_by(|a, b| b.composite_score.partial_cmp(&a.composite_score).unwrap_or(std::cmp::Ordering::Equal));
+        scored.sort_by(|(a, a_bid), (b, b_bid)| {
+            b.composite_score
+                .partial_cmp(&a.composite_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| tie_break.compare(a_bid, b_bid))
+        });
+        let scored: Vec<ScoredBid> = scored.into_iter().map(|(scored_bid, _)| scored_bid).collect();
 
         let winner_id = scored.first().map(|b| b.id.clone()).unwrap_or_default();
         let winner_score = scored.first().map(|b| b.composite_score).unwrap_or(0.0);
@@ -211,8 +372,71 @@ impl SwarmEngine {
         })
     }
 
+    /// Run a second-price (Vickrey) auction over the bids, scored as in `evaluate_bids`.
+    /// The best bid wins if its score reaches `reserve_score` (default 0) and pays
+    /// the runner-up's score, or the reserve if that is higher. With a single bid
+    /// the price is the reserve when one is given, otherwise the bid's own score.
+    /// `tie_break` and `duration_weight` are passed through to `evaluate_bids`.
+    #[napi]
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_auction(
+        &self,
+        bids_json: String,
+        reputation_weight: f64,
+        confidence_weight: f64,
+        bid_weight: f64,
+        prefer_lower_bids: bool,
+        reserve_score: Option<f64>,
+        tie_break: Option<String>,
+        duration_weight: Option<f64>,
+    ) -> Result<AuctionResult> {
+        let evaluation = self.evaluate_bids(
+            bids_json,
+            reputation_weight,
+            confidence_weight,
+            bid_weight,
+            prefer_lower_bids,
+            tie_break,
+            duration_weight,
+        )?;
+        let ranked = evaluation.ranked_bids;
+
+        let reserve_met = match ranked.first() {
+            Some(best) => best.composite_score >= reserve_score.unwrap_or(0.0),
+            None => false,
+        };
+        if !reserve_met {
+            return Ok(AuctionResult {
+                ranked_bids: ranked,
+                winner_id: String::new(),
+                winner_score: 0.0,
+                price: 0.0,
+                reserve_met: false,
+            });
+        }
+
+        let price = match (ranked.get(1), reserve_score) {
+            (Some(runner_up), reserve) => runner_up.composite_score.max(reserve.unwrap_or(0.0)),
+            (None, Some(reserve)) => reserve,
+            (None, None) => evaluation.winner_score,
+        };
+
+        Ok(AuctionResult {
+            ranked_bids: ranked,
+            winner_id: evaluation.winner_id,
+            winner_score: evaluation.winner_score,
+            price,
+            reserve_met,
+        })
+    }
+
     /// Tally votes using the specified method.
-    /// Methods: "majority", "supermajority", "unanimous", "ranked", "weighted"
+    /// Methods: "majority", "supermajority", "unanimous", "ranked", "weighted", "irv",
+    /// "approval", "condorcet", "schulze"
+    ///
+    /// When `eligible_count` is given, quorum also requires
+    /// `votes / eligible_count >= quorum_value`, on top of the method's
+    /// winner threshold.
     #[napi]
     pub fn tally_votes(
         &self,
@@ -220,74 +444,41 @@ impl SwarmEngine {
         options_json: String,
         method: String,
         quorum_value: f64,
+        eligible_count: Option<u32>,
     ) -> Result<ConsensusResult> {
         let votes: Vec<VoteData> = serde_json::from_str(&votes_json)
             .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid votes JSON: {}", e)))?;
         let options: Vec<String> = serde_json::from_str(&options_json)
             .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid options JSON: {}", e)))?;
-
-        let mut tally: HashMap<String, f64> = HashMap::new();
-        for opt in &options {
-            tally.insert(opt.clone(), 0.0);
+        if eligible_count == Some(0) {
+            return Err(Error::new(Status::InvalidArg, "eligible_count must be positive"));
         }
 
-        let mut total_weight = 0.0;
-        for vote in &votes {
-            if method == "ranked" {
-                // Borda count: parse rankings as JSON array
-                if let Ok(rankings) = serde_json::from_str::<Vec<String>>(&vote.vote_value) {
-                    let n = rankings.len() as f64;
-                    for (i, option) in rankings.iter().enumerate() {
-                        let points = (n - i as f64) * vote.vote_weight;
-                        *tally.entry(option.clone()).or_insert(0.0) += points;
-                    }
-                    total_weight += vote.vote_weight;
-                }
-            } else {
-                *tally.entry(vote.vote_value.clone()).or_insert(0.0) += vote.vote_weight;
-                total_weight += vote.vote_weight;
-            }
-        }
-
-        // Find winner
-        let mut winner: Option<String> = None;
-        let mut max_votes: f64 = 0.0;
-        for (opt, &count) in &tally {
-            if count > max_votes {
-                max_votes = count;
-                winner = Some(opt.clone());
-            }
-        }
-
-        // Check quorum
-        let quorum_met = if total_weight > 0.0 {
-            let winner_ratio = max_votes / total_weight;
-            match method.as_str() {
-                "supermajority" => winner_ratio >= 0.667,
-                "unanimous" => winner_ratio >= 1.0,
-                _ => winner_ratio > 0.5 || options.len() <= 2,
-            }
-        } else {
-            false
+        let outcome = match method.as_str() {
+            "irv" => tally_instant_runoff(&votes, &options),
+            "approval" => tally_approval(&votes, &options),
+            "condorcet" => tally_condorcet(&votes, &options),
+            "schulze" => tally_schulze(&votes, &options),
+            _ => tally_single_round(&votes, &options, &method),
         };
 
-        let participation_rate = if total_weight > 0.0 {
-            votes.len() as f64 / total_weight
-        } else {
-            0.0
+        let participation_rate = match eligible_count {
+            Some(eligible) => (votes.len() as f64 / eligible as f64).min(1.0),
+            None if !votes.is_empty() => 1.0,
+            None => 0.0,
         };
-
-        let tally_entries: Vec<TallyEntry> = tally.into_iter()
-            .map(|(option, count)| TallyEntry { option, count })
-            .collect();
+        let participation_met = eligible_count.is_none() || participation_rate >= quorum_value;
+        let quorum_met = outcome.quorum_met && participation_met;
 
         Ok(ConsensusResult {
-            winner: if quorum_met { winner } else { None },
-            tally: tally_entries,
+            winner: if quorum_met { outcome.winner } else { None },
+            tally: outcome.tally,
             quorum_met,
             total_votes: votes.len() as u32,
-            weighted_total: total_weight,
+            weighted_total: outcome.total_weight,
             participation_rate,
+            rounds: outcome.rounds,
+            cycle: outcome.cycle,
         })
     }
 
@@ -335,6 +526,82 @@ impl SwarmEngine {
             .map_err(|e| Error::new(Status::GenericFailure, format!("Serialization error: {}", e)))
     }
 
+    /// Find all pure-strategy Nash equilibria of a 2-player normal-form game.
+    /// Both matrices are `{row_strategy: {col_strategy: payoff}}`, A for the row
+    /// player and B for the column player, and must cover the same cells.
+    /// A cell is an equilibrium when neither player can gain by deviating alone.
+    /// Returns `{equilibria: [{row_strategy, col_strategy, payoff_a, payoff_b}], has_equilibrium}`.
+    #[napi]
+    pub fn find_nash_equilibrium(
+        &self,
+        payoff_a_json: String,
+        payoff_b_json: String,
+    ) -> Result<String> {
+        let payoff_a: BTreeMap<String, BTreeMap<String, f64>> = serde_json::from_str(&payoff_a_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid payoff matrix A: {}", e)))?;
+        let payoff_b: BTreeMap<String, BTreeMap<String, f64>> = serde_json::from_str(&payoff_b_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid payoff matrix B: {}", e)))?;
+
+        let rows: Vec<&String> = payoff_a.keys().collect();
+        let cols: Vec<&String> = payoff_a
+            .values()
+            .flat_map(|row| row.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        // Dense (a, b) grid; every cell must be present in both matrices
+        let mut grid: Vec<Vec<(f64, f64)>> = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let mut cells = Vec::with_capacity(cols.len());
+            for col in &cols {
+                let a = payoff_a.get(*row).and_then(|r| r.get(*col));
+                let b = payoff_b.get(*row).and_then(|r| r.get(*col));
+                match (a, b) {
+                    (Some(&a), Some(&b)) => cells.push((a, b)),
+                    _ => {
+                        return Err(Error::new(
+                            Status::InvalidArg,
+                            format!("Missing payoff for ({}, {})", row, col),
+                        ))
+                    }
+                }
+            }
+            grid.push(cells);
+        }
+        if payoff_b.len() != rows.len() || payoff_b.values().any(|r| r.len() != cols.len()) {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "Payoff matrices must have the same strategies".to_string(),
+            ));
+        }
+
+        let mut equilibria = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            for (j, col) in cols.iter().enumerate() {
+                let (a, b) = grid[i][j];
+                let row_best = grid.iter().all(|cells| cells[j].0 <= a);
+                let col_best = grid[i].iter().all(|cell| cell.1 <= b);
+                if row_best && col_best {
+                    equilibria.push(serde_json::json!({
+                        "row_strategy": row,
+                        "col_strategy": col,
+                        "payoff_a": a,
+                        "payoff_b": b,
+                    }));
+                }
+            }
+        }
+
+        let result = serde_json::json!({
+            "has_equilibrium": !equilibria.is_empty(),
+            "equilibria": equilibria,
+        });
+
+        serde_json::to_string(&result)
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Serialization error: {}", e)))
+    }
+
     /// ACO-style task routing: assign tasks to workers based on pheromone trails.
     /// Higher trail intensity on a (worker, task_type) pair = higher assignment probability.
     #[napi]
@@ -357,39 +624,571 @@ impl SwarmEngine {
             return Ok("{}".to_string());
         }
 
-        let mut assignments: HashMap<String, String> = HashMap::new();
-        let mut worker_load: HashMap<String, usize> = HashMap::new();
+        let (assignments, _) = greedy_assign(&tasks, &workers, &trails, alpha, &HashMap::new());
+
+        serde_json::to_string(&assignments)
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Serialization error: {}", e)))
+    }
+
+    /// `route_tasks` with hard per-worker limits. `capacities_json` is
+    /// `{worker: max_tasks}`; workers without an entry are unlimited. Tasks that
+    /// find every worker at capacity are listed in `unassigned` instead of being
+    /// forced onto someone. Returns `{assignments: {task: worker}, unassigned: [task]}`.
+    #[napi]
+    pub fn route_tasks_with_capacity(
+        &self,
+        tasks_json: String,
+        workers_json: String,
+        trail_strengths_json: String,
+        alpha: f64,
+        capacities_json: String,
+    ) -> Result<String> {
+        let tasks: Vec<String> = serde_json::from_str(&tasks_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid tasks: {}", e)))?;
+        let workers: Vec<String> = serde_json::from_str(&workers_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid workers: {}", e)))?;
+        let trails: HashMap<String, HashMap<String, f64>> = serde_json::from_str(&trail_strengths_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid trail strengths: {}", e)))?;
+        let capacities: HashMap<String, usize> = serde_json::from_str(&capacities_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid capacities: {}", e)))?;
+
+        let (assignments, unassigned) = greedy_assign(&tasks, &workers, &trails, alpha, &capacities);
+
+        serde_json::to_string(&serde_json::json!({
+            "assignments": assignments,
+            "unassigned": unassigned,
+        }))
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Serialization error: {}", e)))
+    }
+
+    /// ACO transition probabilities for each task over all workers:
+    /// p(worker) = trail^alpha * heuristic^beta / sum over workers.
+    /// `heuristics_json` has the same `{worker: {task: value}}` shape as the
+    /// trails; missing heuristics default to 1.0. Returns `{task: {worker: prob}}`.
+    #[napi]
+    pub fn route_tasks_probabilistic(
+        &self,
+        tasks_json: String,
+        workers_json: String,
+        trail_strengths_json: String,
+        alpha: f64,
+        beta: f64,
+        heuristics_json: Option<String>,
+    ) -> Result<String> {
+        let tasks: Vec<String> = serde_json::from_str(&tasks_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid tasks: {}", e)))?;
+        let workers: Vec<String> = serde_json::from_str(&workers_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid workers: {}", e)))?;
+        let trails: HashMap<String, HashMap<String, f64>> = serde_json::from_str(&trail_strengths_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid trail strengths: {}", e)))?;
+        let heuristics: HashMap<String, HashMap<String, f64>> = match heuristics_json {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid heuristics: {}", e)))?,
+            None => HashMap::new(),
+        };
+
+        let distributions =
+            assignment_probabilities(&tasks, &workers, &trails, &heuristics, alpha, beta);
+
+        serde_json::to_string(&distributions)
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Serialization error: {}", e)))
+    }
+
+    /// Sample one worker per task from the `route_tasks_probabilistic` distribution.
+    /// The same inputs and `seed` always yield the same assignments; without a
+    /// seed the RNG is seeded from OS entropy. Returns `{task: worker}`.
+    #[napi]
+    #[allow(clippy::too_many_arguments)]
+    pub fn route_tasks_sampled(
+        &self,
+        tasks_json: String,
+        workers_json: String,
+        trail_strengths_json: String,
+        alpha: f64,
+        beta: f64,
+        heuristics_json: Option<String>,
+        seed: Option<u32>,
+    ) -> Result<String> {
+        let tasks: Vec<String> = serde_json::from_str(&tasks_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid tasks: {}", e)))?;
+        let workers: Vec<String> = serde_json::from_str(&workers_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid workers: {}", e)))?;
+        let trails: HashMap<String, HashMap<String, f64>> = serde_json::from_str(&trail_strengths_json)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid trail strengths: {}", e)))?;
+        let heuristics: HashMap<String, HashMap<String, f64>> = match heuristics_json {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid heuristics: {}", e)))?,
+            None => HashMap::new(),
+        };
+
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed as u64),
+            None => StdRng::from_entropy(),
+        };
+
+        let distributions =
+            assignment_probabilities(&tasks, &workers, &trails, &heuristics, alpha, beta);
+        let mut assignments: BTreeMap<String, String> = BTreeMap::new();
 
         for task in &tasks {
-            let mut best_worker: Option<&String> = None;
-            let mut best_score: f64 = f64::MIN;
+            let row = &distributions[task];
+            let mut remaining: f64 = rng.gen();
+            // Walk workers in input order; the last one absorbs rounding error
+            let chosen = workers.iter().find(|worker| {
+                remaining -= row[*worker];
+                remaining < 0.0
+            });
+            if let Some(worker) = chosen.or(workers.last()) {
+                assignments.insert(task.clone(), worker.clone());
+            }
+        }
+
+        serde_json::to_string(&assignments)
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Serialization error: {}", e)))
+    }
+}
 
-            for worker in &workers {
-                let trail_intensity = trails
-                    .get(worker)
-                    .and_then(|t| t.get(task))
-                    .copied()
-                    .unwrap_or(0.1); // small default for exploration
+/// Greedy ACO assignment: each task goes to the worker with the highest
+/// trail^alpha * 1/(1+load), skipping workers that have reached their capacity.
+fn greedy_assign(
+    tasks: &[String],
+    workers: &[String],
+    trails: &HashMap<String, HashMap<String, f64>>,
+    alpha: f64,
+    capacities: &HashMap<String, usize>,
+) -> (HashMap<String, String>, Vec<String>) {
+    let mut assignments: HashMap<String, String> = HashMap::new();
+    let mut unassigned: Vec<String> = Vec::new();
+    let mut worker_load: HashMap<String, usize> = HashMap::new();
 
-                let load = *worker_load.get(worker).unwrap_or(&0) as f64;
-                let load_penalty = 1.0 / (1.0 + load);
+    for task in tasks {
+        let mut best_worker: Option<&String> = None;
+        let mut best_score: f64 = f64::MIN;
 
-                let score = trail_intensity.powf(alpha) * load_penalty;
+        for worker in workers {
+            let load = *worker_load.get(worker).unwrap_or(&0);
+            if capacities.get(worker).is_some_and(|&cap| load >= cap) {
+                continue;
+            }
 
-                if score > best_score {
-                    best_score = score;
-                    best_worker = Some(worker);
-                }
+            let trail_intensity = trails
+                .get(worker)
+                .and_then(|t| t.get(task))
+                .copied()
+                .unwrap_or(DEFAULT_TRAIL_INTENSITY);
+
+            let load_penalty = 1.0 / (1.0 + load as f64);
+
+            let score = trail_intensity.powf(alpha) * load_penalty;
+
+            if score > best_score {
+                best_score = score;
+                best_worker = Some(worker);
             }
+        }
 
-            if let Some(worker) = best_worker {
+        match best_worker {
+            Some(worker) => {
                 assignments.insert(task.clone(), worker.clone());
                 *worker_load.entry(worker.clone()).or_insert(0) += 1;
             }
+            None => unassigned.push(task.clone()),
         }
+    }
 
-        serde_json::to_string(&assignments)
-            .map_err(|e| Error::new(Status::GenericFailure, format!("Serialization error: {}", e)))
+    (assignments, unassigned)
+}
+
+/// Per-task probability distribution over workers (see `route_tasks_probabilistic`).
+/// Falls back to a uniform distribution when every weight is zero.
+fn assignment_probabilities(
+    tasks: &[String],
+    workers: &[String],
+    trails: &HashMap<String, HashMap<String, f64>>,
+    heuristics: &HashMap<String, HashMap<String, f64>>,
+    alpha: f64,
+    beta: f64,
+) -> BTreeMap<String, BTreeMap<String, f64>> {
+    let lookup = |table: &HashMap<String, HashMap<String, f64>>, worker: &str, task: &str| {
+        table.get(worker).and_then(|t| t.get(task)).copied()
+    };
+
+    tasks
+        .iter()
+        .map(|task| {
+            let weights: Vec<f64> = workers
+                .iter()
+                .map(|worker| {
+                    let trail = lookup(trails, worker, task).unwrap_or(DEFAULT_TRAIL_INTENSITY);
+                    let heuristic = lookup(heuristics, worker, task).unwrap_or(1.0);
+                    trail.max(0.0).powf(alpha) * heuristic.max(0.0).powf(beta)
+                })
+                .collect();
+            let total: f64 = weights.iter().sum();
+
+            let row = workers
+                .iter()
+                .zip(&weights)
+                .map(|(worker, &weight)| {
+                    let p = if total > 0.0 { weight / total } else { 1.0 / workers.len() as f64 };
+                    (worker.clone(), p)
+                })
+                .collect();
+            (task.clone(), row)
+        })
+        .collect()
+}
+
+/// Tally a single round of plurality, supermajority, unanimous, weighted, or Borda ("ranked") votes.
+fn tally_single_round(votes: &[VoteData], options: &[String], method: &str) -> TallyOutcome {
+    let mut tally: HashMap<String, f64> = HashMap::new();
+    for opt in options {
+        tally.insert(opt.clone(), 0.0);
+    }
+
+    let mut total_weight = 0.0;
+    for vote in votes {
+        if method == "ranked" {
+            // Borda count: parse rankings as JSON array
+            if let Ok(rankings) = serde_json::from_str::<Vec<String>>(&vote.vote_value) {
+                let n = rankings.len() as f64;
+                for (i, option) in rankings.iter().enumerate() {
+                    let points = (n - i as f64) * vote.vote_weight;
+                    *tally.entry(option.clone()).or_insert(0.0) += points;
+                }
+                total_weight += vote.vote_weight;
+            }
+        } else {
+            *tally.entry(vote.vote_value.clone()).or_insert(0.0) += vote.vote_weight;
+            total_weight += vote.vote_weight;
+        }
+    }
+
+    // Find winner; a tie for first place has none
+    let mut winner: Option<String> = None;
+    let mut max_votes: f64 = 0.0;
+    for (opt, &count) in &tally {
+        if count > max_votes {
+            max_votes = count;
+            winner = Some(opt.clone());
+        }
+    }
+    if tally.values().filter(|&&count| count == max_votes).count() > 1 {
+        winner = None;
+    }
+
+    // Check quorum
+    let quorum_met = if total_weight > 0.0 && winner.is_some() {
+        let winner_ratio = max_votes / total_weight;
+        match method {
+            "supermajority" => winner_ratio >= 0.667,
+            "unanimous" => winner_ratio >= 1.0,
+            _ => winner_ratio > 0.5,
+        }
+    } else {
+        false
+    };
+
+    let tally = tally.into_iter()
+        .map(|(option, count)| TallyEntry { option, count })
+        .collect();
+
+    TallyOutcome { tally, winner, quorum_met, total_weight, rounds: vec![], cycle: vec![] }
+}
+
+/// Parse ranked ballots (JSON arrays of options), skipping malformed ones.
+fn parse_ranked_ballots(votes: &[VoteData]) -> Vec<(Vec<String>, f64)> {
+    votes
+        .iter()
+        .filter_map(|v| {
+            serde_json::from_str::<Vec<String>>(&v.vote_value)
+                .ok()
+                .map(|ranking| (ranking, v.vote_weight))
+        })
+        .collect()
+}
+
+/// Options in `options` order, followed by any others found on ballots.
+fn ranked_candidates(ballots: &[(Vec<String>, f64)], options: &[String]) -> Vec<String> {
+    let mut candidates: Vec<String> = options.to_vec();
+    for (ranking, _) in ballots {
+        for option in ranking {
+            if !candidates.contains(option) {
+                candidates.push(option.clone());
+            }
+        }
+    }
+    candidates
+}
+
+/// Instant-runoff voting. Each round counts every ballot for its highest-ranked
+/// remaining option; an option with a strict majority of the non-exhausted
+/// weight wins. Otherwise the lowest option is eliminated. Ties for last place
+/// eliminate the option with the fewest first-round votes, then the one listed
+/// latest in `options`.
+fn tally_instant_runoff(votes: &[VoteData], options: &[String]) -> TallyOutcome {
+    let ballots = parse_ranked_ballots(votes);
+    let total_weight: f64 = ballots.iter().map(|(_, w)| w).sum();
+    let mut remaining = ranked_candidates(&ballots, options);
+    let mut rounds: Vec<TallyRound> = Vec::new();
+    let mut first_round: HashMap<String, f64> = HashMap::new();
+
+    loop {
+        let mut counts: Vec<f64> = vec![0.0; remaining.len()];
+        for (ranking, weight) in &ballots {
+            if let Some(i) = ranking.iter().find_map(|o| remaining.iter().position(|r| r == o)) {
+                counts[i] += weight;
+            }
+        }
+        let active_weight: f64 = counts.iter().sum();
+
+        if rounds.is_empty() {
+            first_round = remaining.iter().cloned().zip(counts.iter().copied()).collect();
+        }
+
+        let tally: Vec<TallyEntry> = remaining
+            .iter()
+            .zip(&counts)
+            .map(|(option, &count)| TallyEntry { option: option.clone(), count })
+            .collect();
+
+        let leader = (0..remaining.len())
+            .max_by(|&a, &b| counts[a].total_cmp(&counts[b]).then(b.cmp(&a)));
+        let majority = leader.filter(|&i| active_weight > 0.0 && counts[i] > active_weight / 2.0);
+
+        if majority.is_some() || remaining.len() <= 1 {
+            let round = rounds.len() as u32 + 1;
+            rounds.push(TallyRound { round, tally: tally.clone(), eliminated: None });
+            let winner = majority
+                .or(leader)
+                .filter(|_| active_weight > 0.0)
+                .map(|i| remaining[i].clone());
+            return TallyOutcome {
+                tally,
+                quorum_met: winner.is_some(),
+                winner,
+                total_weight,
+                rounds,
+                cycle: vec![],
+            };
+        }
+
+        // Lowest count loses; break ties by first-round count, then latest in option order
+        let loser = (0..remaining.len())
+            .min_by(|&a, &b| {
+                counts[a]
+                    .total_cmp(&counts[b])
+                    .then(first_round[&remaining[a]].total_cmp(&first_round[&remaining[b]]))
+                    .then(b.cmp(&a))
+            })
+            .unwrap();
+        let eliminated = remaining.remove(loser);
+        let round = rounds.len() as u32 + 1;
+        rounds.push(TallyRound { round, tally, eliminated: Some(eliminated) });
+    }
+}
+
+/// Approval voting. Each vote approves a JSON array of options and every
+/// approved option gains the vote's weight. The most-approved option wins;
+/// a tie for first place yields no winner.
+fn tally_approval(votes: &[VoteData], options: &[String]) -> TallyOutcome {
+    let ballots = parse_ranked_ballots(votes);
+    let total_weight: f64 = ballots.iter().map(|(_, w)| w).sum();
+    let candidates = ranked_candidates(&ballots, options);
+
+    let mut counts: Vec<f64> = vec![0.0; candidates.len()];
+    for (approved, weight) in &ballots {
+        for (i, candidate) in candidates.iter().enumerate() {
+            if approved.contains(candidate) {
+                counts[i] += weight;
+            }
+        }
+    }
+
+    let max = counts.iter().copied().fold(0.0, f64::max);
+    let leaders: Vec<usize> = (0..candidates.len()).filter(|&i| counts[i] == max).collect();
+    let winner = match leaders.as_slice() {
+        [i] if max > 0.0 => Some(candidates[*i].clone()),
+        _ => None,
+    };
+
+    let tally = candidates
+        .into_iter()
+        .zip(counts)
+        .map(|(option, count)| TallyEntry { option, count })
+        .collect();
+
+    TallyOutcome {
+        tally,
+        quorum_met: winner.is_some(),
+        winner,
+        total_weight,
+        rounds: vec![],
+        cycle: vec![],
+    }
+}
+
+/// Weighted pairwise preferences: `d[i][j]` is the weight of ballots ranking
+/// candidate `i` above `j`. Unranked candidates count as below all ranked ones.
+fn pairwise_preferences(ballots: &[(Vec<String>, f64)], candidates: &[String]) -> Vec<Vec<f64>> {
+    let n = candidates.len();
+    let mut d = vec![vec![0.0; n]; n];
+
+    for (ranking, weight) in ballots {
+        let rank: Vec<usize> = candidates
+            .iter()
+            .map(|c| ranking.iter().position(|r| r == c).unwrap_or(usize::MAX))
+            .collect();
+        for i in 0..n {
+            for j in 0..n {
+                if rank[i] < rank[j] {
+                    d[i][j] += weight;
+                }
+            }
+        }
+    }
+    d
+}
+
+/// Condorcet method. The winner beats every other option head-to-head.
+/// Tally counts are the number of pairwise victories for each option.
+/// A lone option wins as soon as any ballot is cast. Without a winner,
+/// `cycle` lists the options of the Smith set (the smallest set that beats
+/// or ties all others) that lie on a strict majority cycle; it is empty
+/// when only pairwise ties prevent a winner.
+fn tally_condorcet(votes: &[VoteData], options: &[String]) -> TallyOutcome {
+    let ballots = parse_ranked_ballots(votes);
+    let total_weight: f64 = ballots.iter().map(|(_, w)| w).sum();
+    let candidates = ranked_candidates(&ballots, options);
+    let n = candidates.len();
+    let d = pairwise_preferences(&ballots, &candidates);
+
+    let wins: Vec<usize> = (0..n).map(|i| (0..n).filter(|&j| d[i][j] > d[j][i]).count()).collect();
+    let winner_index = (0..n).find(|&i| total_weight > 0.0 && wins[i] == n - 1);
+
+    let cycle = match winner_index {
+        Some(_) => vec![],
+        None if total_weight > 0.0 => {
+            // The Smith set is every option that reaches all others through
+            // "beats or ties"; of those, keep the ones that reach themselves
+            // through strict defeats alone
+            let beats_or_ties = transitive_closure(&d, |a, b| a >= b);
+            let beats = transitive_closure(&d, |a, b| a > b);
+            (0..n)
+                .filter(|&i| beats_or_ties[i].iter().all(|&r| r) && beats[i][i])
+                .map(|i| candidates[i].clone())
+                .collect()
+        }
+        None => vec![],
+    };
+
+    let winner = winner_index.map(|i| candidates[i].clone());
+    let tally = candidates
+        .into_iter()
+        .zip(wins)
+        .map(|(option, wins)| TallyEntry { option, count: wins as f64 })
+        .collect();
+
+    TallyOutcome {
+        tally,
+        quorum_met: winner.is_some(),
+        winner,
+        total_weight,
+        rounds: vec![],
+        cycle,
+    }
+}
+
+/// Which options reach which through chains of pairwise results, where
+/// `edge(d[i][j], d[j][i])` decides whether `i` leads directly to `j`
+fn transitive_closure(d: &[Vec<f64>], edge: impl Fn(f64, f64) -> bool) -> Vec<Vec<bool>> {
+    let n = d.len();
+    let mut reach: Vec<Vec<bool>> =
+        (0..n).map(|i| (0..n).map(|j| i != j && edge(d[i][j], d[j][i])).collect()).collect();
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                if reach[i][k] && reach[k][j] {
+                    reach[i][j] = true;
+                }
+            }
+        }
+    }
+    reach
+}
+
+/// Schulze method. Pairwise defeats weighted by `vote_weight` are chained
+/// into strongest paths; an option ranks above another when its strongest
+/// path to it is stronger than the reverse. Tally counts are the number of
+/// options each one beats this way, and entries are ordered by that
+/// standing. Ties in standing fall back to total pairwise support, then to
+/// the option's position in `options` (first-seen order for unlisted ones),
+/// so the first entry is always the winner when any ballot was cast.
+fn tally_schulze(votes: &[VoteData], options: &[String]) -> TallyOutcome {
+    let ballots = parse_ranked_ballots(votes);
+    let total_weight: f64 = ballots.iter().map(|(_, w)| w).sum();
+    let candidates = ranked_candidates(&ballots, options);
+    let n = candidates.len();
+    let d = pairwise_preferences(&ballots, &candidates);
+
+    // Widest-path Floyd–Warshall over the defeat strengths
+    let mut p: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if d[i][j] > d[j][i] { d[i][j] } else { 0.0 }).collect())
+        .collect();
+    for k in 0..n {
+        for i in (0..n).filter(|&i| i != k) {
+            for j in (0..n).filter(|&j| j != k && j != i) {
+                p[i][j] = p[i][j].max(p[i][k].min(p[k][j]));
+            }
+        }
+    }
+
+    let wins: Vec<usize> = (0..n).map(|i| (0..n).filter(|&j| p[i][j] > p[j][i]).count()).collect();
+    let support: Vec<f64> = d.iter().map(|row| row.iter().sum()).collect();
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        wins[b]
+            .cmp(&wins[a])
+            .then(support[b].total_cmp(&support[a]))
+            .then(a.cmp(&b))
+    });
+
+    let winner = order
+        .first()
+        .filter(|_| total_weight > 0.0)
+        .map(|&i| candidates[i].clone());
+    let tally = order
+        .into_iter()
+        .map(|i| TallyEntry { option: candidates[i].clone(), count: wins[i] as f64 })
+        .collect();
+
+    TallyOutcome {
+        tally,
+        quorum_met: winner.is_some(),
+        winner,
+        total_weight,
+        rounds: vec![],
+        cycle: vec![],
+    }
+}
+
+/// Split decayed trails into survivors and IDs that fell below `min_intensity`.
+fn remove_weak_trails(mut trails: Vec<PheromoneTrailData>, min_intensity: f64) -> DecayResult {
+    let removed_ids: Vec<String> = trails
+        .iter()
+        .filter(|t| t.intensity < min_intensity)
+        .map(|t| t.id.clone())
+        .collect();
+    let removed_count = removed_ids.len() as u32;
+
+    // Keep only surviving trails in result
+    trails.retain(|t| t.intensity >= min_intensity);
+
+    DecayResult {
+        trails,
+        removed_count,
+        removed_ids,
     }
 }
 
@@ -418,7 +1217,7 @@ mod tests {
             BidData { id: "b2".into(), bidder_handle: "w2".into(), bid_amount: 5.0, confidence: 0.7, reputation: 0.9, estimated_duration: 90.0 },
         ]).unwrap();
 
-        let result = engine.evaluate_bids(bids, 0.4, 0.3, 0.3, true).unwrap();
+        let result = engine.evaluate_bids(bids, 0.4, 0.3, 0.3, true, None, None).unwrap();
         assert_eq!(result.ranked_bids.len(), 2);
         assert!(!result.winner_id.is_empty());
     }
@@ -433,7 +1232,7 @@ mod tests {
         ]).unwrap();
         let options = serde_json::to_string(&vec!["yes", "no"]).unwrap();
 
-        let result = engine.tally_votes(votes, options, "majority".into(), 0.5).unwrap();
+        let result = engine.tally_votes(votes, options, "majority".into(), 0.5, None).unwrap();
         assert!(result.quorum_met);
         assert_eq!(result.winner, Some("yes".to_string()));
         assert_eq!(result.total_votes, 3);
@@ -453,4 +1252,457 @@ mod tests {
         let assignments: HashMap<String, String> = serde_json::from_str(&result).unwrap();
         assert_eq!(assignments.len(), 2);
     }
+
+    #[test]
+    fn test_nash_equilibrium() {
+        let engine = SwarmEngine::new();
+
+        // Prisoner's dilemma: mutual defection is the only equilibrium
+        let a = r#"{"cooperate": {"cooperate": 3, "defect": 0}, "defect": {"cooperate": 5, "defect": 1}}"#;
+        let b = r#"{"cooperate": {"cooperate": 3, "defect": 5}, "defect": {"cooperate": 0, "defect": 1}}"#;
+        let result = engine.find_nash_equilibrium(a.to_string(), b.to_string()).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed["has_equilibrium"], true);
+        assert_eq!(parsed["equilibria"].as_array().unwrap().len(), 1);
+        assert_eq!(parsed["equilibria"][0]["row_strategy"], "defect");
+        assert_eq!(parsed["equilibria"][0]["col_strategy"], "defect");
+
+        // Matching pennies has no pure-strategy equilibrium
+        let a = r#"{"heads": {"heads": 1, "tails": -1}, "tails": {"heads": -1, "tails": 1}}"#;
+        let b = r#"{"heads": {"heads": -1, "tails": 1}, "tails": {"heads": 1, "tails": -1}}"#;
+        let result = engine.find_nash_equilibrium(a.to_string(), b.to_string()).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed["has_equilibrium"], false);
+        assert!(parsed["equilibria"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_route_tasks_with_capacity() {
+        let engine = SwarmEngine::new();
+        let tasks = serde_json::to_string(&vec!["t1", "t2", "t3", "t4"]).unwrap();
+        let workers = serde_json::to_string(&vec!["w1", "w2"]).unwrap();
+        let trails = serde_json::to_string(&HashMap::from([(
+            "w1".to_string(),
+            HashMap::from([
+                ("t1".to_string(), 10.0),
+                ("t2".to_string(), 10.0),
+                ("t3".to_string(), 10.0),
+                ("t4".to_string(), 10.0),
+            ]),
+        )])).unwrap();
+        let capacities = r#"{"w1": 2, "w2": 1}"#.to_string();
+
+        let result = engine
+            .route_tasks_with_capacity(tasks, workers, trails, 1.0, capacities)
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(parsed["assignments"]["t1"], "w1");
+        assert_eq!(parsed["assignments"]["t2"], "w1");
+        assert_eq!(parsed["assignments"]["t3"], "w2");
+        assert_eq!(parsed["unassigned"], serde_json::json!(["t4"]));
+    }
+
+    #[test]
+    fn test_time_based_decay() {
+        let engine = SwarmEngine::new();
+        let trails = serde_json::to_string(&vec![
+            PheromoneTrailData { id: "fresh".into(), intensity: 1.0, created_at: 10_000 },
+            PheromoneTrailData { id: "old".into(), intensity: 1.0, created_at: 0 },
+            PheromoneTrailData { id: "ancient".into(), intensity: 1.0, created_at: -90_000 },
+            PheromoneTrailData { id: "future".into(), intensity: 1.0, created_at: 20_000 },
+        ]).unwrap();
+
+        for bad in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(engine.process_decay_time_based(trails.clone(), bad, 10_000, 0.01).is_err());
+        }
+
+        let result = engine.process_decay_time_based(trails, 10_000.0, 10_000, 0.01).unwrap();
+        let intensity = |id: &str| result.trails.iter().find(|t| t.id == id).unwrap().intensity;
+        assert_eq!(intensity("fresh"), 1.0);
+        assert!((intensity("old") - 0.5).abs() < 1e-9);
+        assert_eq!(intensity("future"), 1.0);
+        assert_eq!(result.removed_ids, vec!["ancient".to_string()]);
+        assert_eq!(result.removed_count, 1);
+    }
+
+    #[test]
+    fn test_deposit() {
+        let engine = SwarmEngine::new();
+        let trails = serde_json::to_string(&vec![
+            PheromoneTrailData { id: "t1".into(), intensity: 0.5, created_at: 0 },
+            PheromoneTrailData { id: "t2".into(), intensity: 0.9, created_at: 0 },
+        ]).unwrap();
+        let deposits = r#"{"t1": 0.25, "t2": 0.5, "t3": 0.4}"#.to_string();
+
+        let updated = engine.deposit(trails.clone(), deposits.clone(), Some(1.0), None).unwrap();
+        assert_eq!(updated.len(), 2);
+        assert_eq!(updated[0].intensity, 0.75);
+        assert_eq!(updated[1].intensity, 1.0);
+
+        let created = engine.deposit(trails, deposits, None, Some(true)).unwrap();
+        assert_eq!(created.len(), 3);
+        assert_eq!(created[1].intensity, 1.4);
+        assert_eq!(created[2].id, "t3");
+        assert_eq!(created[2].intensity, 0.4);
+    }
+
+    fn ballot(voter: &str, ranking: &[&str]) -> VoteData {
+        VoteData {
+            voter_handle: voter.into(),
+            vote_value: serde_json::to_string(ranking).unwrap(),
+            vote_weight: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_instant_runoff() {
+        let engine = SwarmEngine::new();
+        // a leads on first preferences but c's voters transfer to b
+        let votes = serde_json::to_string(&vec![
+            ballot("v1", &["a", "b", "c"]),
+            ballot("v2", &["a", "c", "b"]),
+            ballot("v3", &["b", "a", "c"]),
+            ballot("v4", &["b", "c", "a"]),
+            ballot("v5", &["c", "b", "a"]),
+        ]).unwrap();
+        let options = serde_json::to_string(&vec!["a", "b", "c"]).unwrap();
+
+        let result = engine.tally_votes(votes, options, "irv".into(), 0.5, None).unwrap();
+        assert_eq!(result.winner, Some("b".to_string()));
+        assert!(result.quorum_met);
+        assert_eq!(result.rounds.len(), 2);
+        assert_eq!(result.rounds[0].eliminated, Some("c".to_string()));
+        let b_final = result.tally.iter().find(|e| e.option == "b").unwrap();
+        assert_eq!(b_final.count, 3.0);
+    }
+
+    #[test]
+    fn test_instant_runoff_last_place_tie() {
+        let engine = SwarmEngine::new();
+        // b and c tie for last; c is listed later so it is eliminated first.
+        // Then a, b, and d tie; b had the fewest first-round votes.
+        let votes = serde_json::to_string(&vec![
+            ballot("v1", &["a"]),
+            ballot("v2", &["a"]),
+            ballot("v3", &["b", "a"]),
+            ballot("v4", &["c", "b"]),
+            ballot("v5", &["d"]),
+            ballot("v6", &["d"]),
+        ]).unwrap();
+        let options = serde_json::to_string(&vec!["a", "b", "c", "d"]).unwrap();
+
+        let result = engine.tally_votes(votes, options, "irv".into(), 0.5, None).unwrap();
+        assert_eq!(result.rounds[0].eliminated, Some("c".to_string()));
+        assert_eq!(result.rounds[1].eliminated, Some("b".to_string()));
+        assert_eq!(result.winner, Some("a".to_string()));
+    }
+
+    #[test]
+    fn test_approval_vote() {
+        let engine = SwarmEngine::new();
+        let votes = serde_json::to_string(&vec![
+            ballot("v1", &["a", "b"]),
+            ballot("v2", &["b"]),
+            ballot("v3", &["b", "c"]),
+            ballot("v4", &["a", "c"]),
+        ]).unwrap();
+        let options = serde_json::to_string(&vec!["a", "b", "c"]).unwrap();
+
+        let result = engine.tally_votes(votes, options, "approval".into(), 0.5, None).unwrap();
+        assert_eq!(result.winner, Some("b".to_string()));
+        assert_eq!(result.tally[1].count, 3.0);
+        assert_eq!(result.weighted_total, 4.0);
+    }
+
+    #[test]
+    fn test_condorcet_vote() {
+        let engine = SwarmEngine::new();
+        let options = serde_json::to_string(&vec!["a", "b", "c"]).unwrap();
+
+        let votes = serde_json::to_string(&vec![
+            ballot("v1", &["a", "b", "c"]),
+            ballot("v2", &["b", "a", "c"]),
+            ballot("v3", &["a", "c", "b"]),
+        ]).unwrap();
+        let result = engine.tally_votes(votes, options.clone(), "condorcet".into(), 0.5, None).unwrap();
+        assert_eq!(result.winner, Some("a".to_string()));
+        assert!(result.cycle.is_empty());
+
+        // Rock-paper-scissors preferences form a cycle
+        let votes = serde_json::to_string(&vec![
+            ballot("v1", &["a", "b", "c"]),
+            ballot("v2", &["b", "c", "a"]),
+            ballot("v3", &["c", "a", "b"]),
+        ]).unwrap();
+        let result = engine.tally_votes(votes, options, "condorcet".into(), 0.5, None).unwrap();
+        assert_eq!(result.winner, None);
+        assert!(!result.quorum_met);
+        assert_eq!(result.cycle, vec!["a", "b", "c"]);
+
+        // A pairwise tie is not a cycle
+        let pair = serde_json::to_string(&vec!["a", "b"]).unwrap();
+        let votes = serde_json::to_string(&vec![
+            ballot("v1", &["a", "b"]),
+            ballot("v2", &["b", "a"]),
+        ]).unwrap();
+        let result = engine.tally_votes(votes, pair, "condorcet".into(), 0.5, None).unwrap();
+        assert_eq!(result.winner, None);
+        assert!(result.cycle.is_empty());
+
+        // A lone candidate wins once anyone votes
+        let lone = serde_json::to_string(&vec!["a"]).unwrap();
+        let votes = serde_json::to_string(&vec![ballot("v1", &["a"])]).unwrap();
+        let result = engine.tally_votes(votes, lone.clone(), "condorcet".into(), 0.5, None).unwrap();
+        assert_eq!(result.winner, Some("a".to_string()));
+        assert!(result.quorum_met);
+        let result = engine.tally_votes("[]".into(), lone, "condorcet".into(), 0.5, None).unwrap();
+        assert_eq!(result.winner, None);
+    }
+
+    #[test]
+    fn test_schulze_vote() {
+        let engine = SwarmEngine::new();
+        let options = serde_json::to_string(&vec!["a", "b", "c"]).unwrap();
+        let weighted = |voter: &str, ranking: &[&str], weight: f64| VoteData {
+            vote_weight: weight,
+            ..ballot(voter, ranking)
+        };
+
+        // a>b (5:2) and b>c (5:2) outweigh the c>a (4:3) defeat
+        let votes = serde_json::to_string(&vec![
+            weighted("v1", &["a", "b", "c"], 3.0),
+            weighted("v2", &["b", "c", "a"], 2.0),
+            weighted("v3", &["c", "a", "b"], 2.0),
+        ]).unwrap();
+        let result = engine.tally_votes(votes, options.clone(), "schulze".into(), 0.5, None).unwrap();
+        assert_eq!(result.winner, Some("a".to_string()));
+        assert!(result.quorum_met);
+        let ranking: Vec<&str> = result.tally.iter().map(|e| e.option.as_str()).collect();
+        assert_eq!(ranking, vec!["a", "b", "c"]);
+        assert_eq!(result.tally[0].count, 2.0);
+        assert_eq!(result.weighted_total, 7.0);
+
+        // A perfect cycle ties every option; options order decides
+        let votes = serde_json::to_string(&vec![
+            ballot("v1", &["c", "a", "b"]),
+            ballot("v2", &["a", "b", "c"]),
+            ballot("v3", &["b", "c", "a"]),
+        ]).unwrap();
+        let result = engine.tally_votes(votes, options, "schulze".into(), 0.5, None).unwrap();
+        assert_eq!(result.winner, Some("a".to_string()));
+        assert!(result.tally.iter().all(|e| e.count == 0.0));
+    }
+
+    #[test]
+    fn test_participation_quorum() {
+        let engine = SwarmEngine::new();
+        let votes = serde_json::to_string(&vec![
+            VoteData { voter_handle: "a1".into(), vote_value: "yes".into(), vote_weight: 1.0 },
+            VoteData { voter_handle: "a2".into(), vote_value: "yes".into(), vote_weight: 1.0 },
+            VoteData { voter_handle: "a3".into(), vote_value: "yes".into(), vote_weight: 1.0 },
+            VoteData { voter_handle: "a4".into(), vote_value: "no".into(), vote_weight: 1.0 },
+        ]).unwrap();
+        let options = serde_json::to_string(&vec!["yes", "no"]).unwrap();
+
+        // 4 of 9 agents voted: below a 5/9 participation quorum
+        let result = engine
+            .tally_votes(votes.clone(), options.clone(), "majority".into(), 5.0 / 9.0, Some(9))
+            .unwrap();
+        assert!(!result.quorum_met);
+        assert_eq!(result.winner, None);
+        assert!((result.participation_rate - 4.0 / 9.0).abs() < 1e-9);
+
+        let result = engine
+            .tally_votes(votes.clone(), options.clone(), "majority".into(), 5.0 / 9.0, Some(7))
+            .unwrap();
+        assert!(result.quorum_met);
+        assert_eq!(result.winner, Some("yes".to_string()));
+
+        assert!(engine.tally_votes(votes, options, "majority".into(), 0.5, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_two_option_tie_has_no_winner() {
+        let engine = SwarmEngine::new();
+        let options = serde_json::to_string(&vec!["yes", "no"]).unwrap();
+        let vote = |voter: &str, value: &str| VoteData {
+            voter_handle: voter.into(),
+            vote_value: value.into(),
+            vote_weight: 1.0,
+        };
+
+        let one_one = serde_json::to_string(&vec![vote("a1", "yes"), vote("a2", "no")]).unwrap();
+        let result = engine.tally_votes(one_one, options.clone(), "majority".into(), 0.5, None).unwrap();
+        assert!(!result.quorum_met);
+        assert_eq!(result.winner, None);
+
+        let two_two = serde_json::to_string(&vec![
+            vote("a1", "yes"), vote("a2", "no"), vote("a3", "no"), vote("a4", "yes"),
+        ]).unwrap();
+        let result = engine.tally_votes(two_two, options.clone(), "majority".into(), 0.5, None).unwrap();
+        assert!(!result.quorum_met);
+        assert_eq!(result.winner, None);
+
+        let two_one = serde_json::to_string(&vec![vote("a1", "yes"), vote("a2", "no"), vote("a3", "no")]).unwrap();
+        let result = engine.tally_votes(two_one, options, "majority".into(), 0.5, None).unwrap();
+        assert!(result.quorum_met);
+        assert_eq!(result.winner, Some("no".to_string()));
+    }
+
+    #[test]
+    fn test_participation_rate_with_fractional_weights() {
+        let engine = SwarmEngine::new();
+        let votes = serde_json::to_string(&vec![
+            VoteData { voter_handle: "a1".into(), vote_value: "yes".into(), vote_weight: 0.5 },
+            VoteData { voter_handle: "a2".into(), vote_value: "yes".into(), vote_weight: 0.3 },
+            VoteData { voter_handle: "a3".into(), vote_value: "no".into(), vote_weight: 0.2 },
+        ]).unwrap();
+        let options = serde_json::to_string(&vec!["yes", "no"]).unwrap();
+
+        // Weights sum to 1.0 but three votes were cast; the old formula reported 3.0
+        let result = engine
+            .tally_votes(votes.clone(), options.clone(), "weighted".into(), 0.5, None)
+            .unwrap();
+        assert_eq!(result.participation_rate, 1.0);
+
+        for eligible in [3, 4, 10] {
+            let result = engine
+                .tally_votes(votes.clone(), options.clone(), "weighted".into(), 0.0, Some(eligible))
+                .unwrap();
+            assert!((0.0..=1.0).contains(&result.participation_rate));
+            assert!((result.participation_rate - 3.0 / eligible as f64).abs() < 1e-9);
+        }
+
+        let result = engine.tally_votes(votes, options.clone(), "weighted".into(), 0.0, Some(2)).unwrap();
+        assert_eq!(result.participation_rate, 1.0);
+
+        let result = engine.tally_votes("[]".into(), options, "weighted".into(), 0.5, None).unwrap();
+        assert_eq!(result.participation_rate, 0.0);
+    }
+
+    fn make_bid(id: &str, bid_amount: f64, confidence: f64, reputation: f64) -> BidData {
+        BidData {
+            id: id.into(),
+            bidder_handle: format!("w-{}", id),
+            bid_amount,
+            confidence,
+            reputation,
+            estimated_duration: 60.0,
+        }
+    }
+
+    #[test]
+    fn test_bid_tie_break() {
+        let engine = SwarmEngine::new();
+        // Only confidence is weighted, so both bids score identically
+        let bids = serde_json::to_string(&vec![
+            BidData { bidder_handle: "zed".into(), ..make_bid("b1", 200.0, 0.8, 0.9) },
+            BidData { bidder_handle: "amy".into(), ..make_bid("b2", 100.0, 0.8, 0.5) },
+        ]).unwrap();
+
+        let winner = |tie_break: Option<&str>| {
+            engine
+                .evaluate_bids(bids.clone(), 0.0, 1.0, 0.0, true, tie_break.map(String::from), None)
+                .unwrap()
+                .winner_id
+        };
+        assert_eq!(winner(None), "b1");
+        assert_eq!(winner(Some("reputation")), "b1");
+        assert_eq!(winner(Some("lowest_bid")), "b2");
+        assert_eq!(winner(Some("handle_asc")), "b2");
+        assert!(engine.evaluate_bids(bids, 0.0, 1.0, 0.0, true, Some("coin_flip".into()), None).is_err());
+    }
+
+    #[test]
+    fn test_duration_weight() {
+        let engine = SwarmEngine::new();
+        let bids = serde_json::to_string(&vec![
+            BidData { estimated_duration: 120.0, ..make_bid("slow", 100.0, 0.8, 0.9) },
+            BidData { estimated_duration: 30.0, ..make_bid("fast", 100.0, 0.8, 0.9) },
+        ]).unwrap();
+
+        let result = engine
+            .evaluate_bids(bids.clone(), 0.3, 0.3, 0.3, true, None, Some(0.5))
+            .unwrap();
+        assert_eq!(result.winner_id, "fast");
+        assert!(result.ranked_bids[0].duration_component > result.ranked_bids[1].duration_component);
+
+        // Without a duration weight the component is zero
+        let result = engine.evaluate_bids(bids, 0.3, 0.3, 0.3, true, None, None).unwrap();
+        assert!(result.ranked_bids.iter().all(|b| b.duration_component == 0.0));
+    }
+
+    #[test]
+    fn test_vickrey_auction() {
+        let engine = SwarmEngine::new();
+        let bids = serde_json::to_string(&vec![
+            make_bid("b1", 10.0, 0.9, 1.0),
+            make_bid("b2", 10.0, 0.5, 1.0),
+        ]).unwrap();
+
+        let result = engine.run_auction(bids.clone(), 0.0, 1.0, 0.0, true, None, None, None).unwrap();
+        assert_eq!(result.winner_id, "b1");
+        assert_eq!(result.winner_score, 0.9);
+        assert_eq!(result.price, 0.5);
+
+        let result = engine.run_auction(bids.clone(), 0.0, 1.0, 0.0, true, Some(0.6), None, None).unwrap();
+        assert_eq!(result.price, 0.6);
+
+        let result = engine.run_auction(bids, 0.0, 1.0, 0.0, true, Some(0.95), None, None).unwrap();
+        assert!(!result.reserve_met);
+        assert!(result.winner_id.is_empty());
+
+        let single = serde_json::to_string(&vec![make_bid("b1", 10.0, 0.9, 1.0)]).unwrap();
+        let result = engine.run_auction(single.clone(), 0.0, 1.0, 0.0, true, None, None, None).unwrap();
+        assert_eq!(result.price, 0.9);
+        let result = engine.run_auction(single, 0.0, 1.0, 0.0, true, Some(0.3), None, None).unwrap();
+        assert_eq!(result.price, 0.3);
+    }
+
+    #[test]
+    fn test_route_tasks_probabilistic() {
+        let engine = SwarmEngine::new();
+        let tasks = serde_json::to_string(&vec!["build", "test"]).unwrap();
+        let workers = serde_json::to_string(&vec!["w1", "w2", "w3"]).unwrap();
+        let trails = serde_json::to_string(&HashMap::from([
+            ("w1".to_string(), HashMap::from([("build".to_string(), 3.0)])),
+            ("w2".to_string(), HashMap::from([("build".to_string(), 1.0)])),
+        ])).unwrap();
+        let heuristics = serde_json::to_string(&HashMap::from([
+            ("w3".to_string(), HashMap::from([("test".to_string(), 4.0)])),
+        ])).unwrap();
+
+        let result = engine
+            .route_tasks_probabilistic(tasks, workers, trails, 1.0, 1.0, Some(heuristics))
+            .unwrap();
+        let probs: HashMap<String, HashMap<String, f64>> = serde_json::from_str(&result).unwrap();
+
+        for row in probs.values() {
+            assert!((row.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        }
+        let build = &probs["build"];
+        assert!((build["w1"] / build["w2"] - 3.0).abs() < 1e-9);
+        assert!(probs["test"]["w3"] > probs["test"]["w1"]);
+    }
+
+    #[test]
+    fn test_route_tasks_sampled_is_reproducible() {
+        let engine = SwarmEngine::new();
+        let tasks: Vec<String> = (0..20).map(|i| format!("task{}", i)).collect();
+        let tasks = serde_json::to_string(&tasks).unwrap();
+        let workers = serde_json::to_string(&vec!["w1", "w2", "w3"]).unwrap();
+        let trails = "{}".to_string();
+
+        let run = |seed| {
+            engine
+                .route_tasks_sampled(tasks.clone(), workers.clone(), trails.clone(), 1.0, 1.0, None, seed)
+                .unwrap()
+        };
+        assert_eq!(run(Some(42)), run(Some(42)));
+
+        let assignments: HashMap<String, String> = serde_json::from_str(&run(Some(7))).unwrap();
+        assert_eq!(assignments.len(), 20);
+        assert!(assignments.values().any(|w| w != "w1"));
+    }
 }
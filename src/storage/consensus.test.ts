@@ -255,6 +255,27 @@ describe('ConsensusStorage', () => {
       expect(closed!.status).not.toBe('open');
     });
 
+    it('should require percentage participation of eligible voters', () => {
+      const proposal = consensus.createProposal({
+        swarmId,
+        proposerHandle: 'lead',
+        proposalType: 'decision',
+        title: 'Turnout',
+        options: ['yes', 'no'],
+        quorumType: 'percentage',
+        quorumValue: 0.5,
+      });
+
+      consensus.castVote({ proposalId: proposal.id, voterHandle: 'a1', voteValue: 'yes' });
+      consensus.castVote({ proposalId: proposal.id, voterHandle: 'a2', voteValue: 'yes' });
+
+      const result = consensus.closeAndTally(proposal.id, 10);
+
+      expect(result!.participationRate).toBe(0.2);
+      expect(result!.quorumMet).toBe(false);
+      expect(result!.winner).toBeNull();
+    });
+
     it('should return null for non-open proposal', () => {
       const proposal = consensus.createProposal({ swarmId, proposerHandle: 'lead', proposalType: 'decision', title: 'X', options: ['a', 'b'] });
       consensus.cancelProposal(proposal.id, 'lead');
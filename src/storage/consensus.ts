@@ -300,9 +300,12 @@ export class ConsensusStorage {
   // ============================================================================
 
   /**
-   * Close a proposal and tally votes
+   * Close a proposal and tally votes.
+   *
+   * `eligibleCount` is the number of agents allowed to vote; without it,
+   * percentage quorum counts as met whenever anyone voted.
    */
-  closeAndTally(proposalId: string): ConsensusResult | null {
+  closeAndTally(proposalId: string, eligibleCount?: number): ConsensusResult | null {
     const db = this.storage.getDatabase();
     const proposal = this.getProposal(proposalId);
 
@@ -311,7 +314,7 @@ export class ConsensusStorage {
     }
 
     const votes = this.getVotes(proposalId);
-    const result = this.calculateResult(proposal, votes);
+    const result = this.calculateResult(proposal, votes, eligibleCount);
 
     // Update proposal with result
     const now = Date.now();
@@ -330,7 +333,11 @@ export class ConsensusStorage {
    * Calculate result based on voting method.
    * Delegates computation to native Rust accelerator when available.
    */
-  private calculateResult(proposal: ConsensusProposal, votes: ConsensusVote[]): ConsensusResult {
+  private calculateResult(
+    proposal: ConsensusProposal,
+    votes: ConsensusVote[],
+    eligibleCount?: number
+  ): ConsensusResult {
     // Delegate tallying to native accelerator
     const voteInput = votes.map((v) => ({
       voterHandle: v.voterHandle,
@@ -338,11 +345,15 @@ export class ConsensusStorage {
       voteWeight: v.voteWeight,
     }));
 
+    // Only percentage quorum is a participation ratio; the other types
+    // check their own threshold below
+    const participationQuorum = proposal.quorumType === 'percentage' ? proposal.quorumValue : 0;
     const nativeResult = this.accelerator.tallyVotes(
       voteInput,
       proposal.options,
       proposal.votingMethod,
-      proposal.quorumValue
+      participationQuorum,
+      eligibleCount
     );
 
     // Convert tally array back to record
@@ -351,16 +362,15 @@ export class ConsensusStorage {
       tally[entry.option] = entry.count;
     }
 
-    // Apply quorum type checks (native only does voting method thresholds)
+    // Apply quorum type checks (native does the method threshold and,
+    // given the eligible count, percentage participation)
     let quorumMet = nativeResult.quorumMet;
-    const participationRate = votes.length > 0 ? votes.length / (nativeResult.weightedTotal || 1) : 0;
+    const participationRate = nativeResult.participationRate;
 
     if (proposal.quorumType === 'none') {
       quorumMet = votes.length > 0 && nativeResult.quorumMet;
     } else if (proposal.quorumType === 'absolute') {
       quorumMet = votes.length >= proposal.quorumValue && nativeResult.quorumMet;
-    } else {
-      quorumMet = (participationRate >= proposal.quorumValue || votes.length >= 2) && nativeResult.quorumMet;
     }
 
     return {
@@ -160,6 +160,44 @@ describe('Log Parser (JS Fallback)', () => {
     });
   });
 
+  // ======================================================================
+  // parseBatchWithCallback
+  // ======================================================================
+
+  describe('parseBatchWithCallback', () => {
+    const flush = () => new Promise((resolve) => setImmediate(resolve));
+
+    it('should hand each event to the callback in order', async () => {
+      const seen: string[] = [];
+      const chunk = 'plain text\n{"type":"system","subtype":"init","session_id":"s1"}\n{"type":"res';
+
+      const count = parser.parseBatchWithCallback(chunk, (event) => seen.push(event.eventType));
+      expect(count).toBe(1);
+      await flush();
+      expect(seen).toEqual(['system']);
+
+      parser.parseBatchWithCallback('ult"}\n', (event) => seen.push(event.eventType));
+      await flush();
+      expect(seen).toEqual(['system', 'result']);
+    });
+
+    it('should not run callbacks before returning', async () => {
+      const seen: string[] = [];
+      parser.parseBatchWithCallback('{"type":"result"}\n', (event) => seen.push(event.eventType));
+      expect(seen).toEqual([]);
+      await flush();
+      expect(seen).toEqual(['result']);
+    });
+
+    it('should have finished parsing before the callback runs', async () => {
+      const chunk = '{"type":"system","subtype":"init","session_id":"s1"}\n{"type":"result"}\n';
+      const sessions: string[] = [];
+      parser.parseBatchWithCallback(chunk, () => sessions.push(parser.getSessionId()));
+      await flush();
+      expect(sessions).toEqual(['s1', 's1']);
+    });
+  });
+
   // ======================================================================
   // getHealthSignal
   // ======================================================================
@@ -29,6 +29,16 @@ export interface HealthSignal {
 export interface LogParser {
   parseLine(line: string): ParsedEvent | null;
   parseBatch(chunk: string): ParsedEvent[];
+  /**
+   * Parse a chunk and hand each event to `onEvent`. Returns the number of
+   * events dispatched.
+   *
+   * Callbacks are deferred with both parsers: none runs before this method
+   * returns, they run in order once the whole chunk has been parsed, and an
+   * exception thrown by `onEvent` surfaces as `uncaughtException` rather
+   * than being thrown from this method.
+   */
+  parseBatchWithCallback(chunk: string, onEvent: (event: ParsedEvent) => void): number;
   getHealthSignal(): HealthSignal;
   getRecentOutput(limit?: number): string[];
   getSessionId(): string;
@@ -94,6 +104,13 @@ class JSLogParser implements LogParser {
     return results;
   }
 
+  parseBatchWithCallback(chunk: string, onEvent: (event: ParsedEvent) => void): number {
+    // Deferred to match the native parser's threadsafe-function calls
+    const events = this.parseBatch(chunk);
+    for (const event of events) queueMicrotask(() => onEvent(event));
+    return events.length;
+  }
+
   getHealthSignal(): HealthSignal {
     const now = Date.now();
     const msSince = this.lastEventAt > 0 ? now - this.lastEventAt : 0;
@@ -223,6 +240,18 @@ export function createLogParser(): LogParser {
           timestamp: r.timestamp,
         }));
       },
+      parseBatchWithCallback(chunk: string, onEvent: (event: ParsedEvent) => void): number {
+        return inst.parseBatchWithCallback(chunk, null, (r: ParsedEvent) => {
+          onEvent({
+            eventType: r.eventType,
+            subtype: r.subtype,
+            sessionId: r.sessionId,
+            text: r.text,
+            isError: r.isError,
+            timestamp: r.timestamp,
+          });
+        });
+      },
       getHealthSignal(): HealthSignal {
         const h = inst.getHealthSignal();
         return {
@@ -145,8 +145,38 @@ describe('SwarmAccelerator (JS fallback)', () => {
       const result = accelerator.tallyVotes(votes, ['alpha', 'beta', 'gamma'], 'ranked', 0.5);
       expect(result.totalVotes).toBe(2);
       // alpha: 3+2=5, beta: 2+3=5, gamma: 1+1=2
-      // Tie between alpha and beta
-      expect(['alpha', 'beta']).toContain(result.winner);
+      // A tie for first place has no winner
+      expect(result.winner).toBeNull();
+      expect(result.quorumMet).toBe(false);
+    });
+
+    it('should measure participation against the eligible count', () => {
+      const votes = [
+        { voterHandle: 'a', voteValue: 'yes', voteWeight: 3 },
+        { voterHandle: 'b', voteValue: 'yes', voteWeight: 1 },
+      ];
+
+      const low = accelerator.tallyVotes(votes, ['yes', 'no'], 'majority', 0.5, 10);
+      expect(low.participationRate).toBe(0.2);
+      expect(low.quorumMet).toBe(false);
+      expect(low.winner).toBeNull();
+
+      const enough = accelerator.tallyVotes(votes, ['yes', 'no'], 'majority', 0.5, 4);
+      expect(enough.participationRate).toBe(0.5);
+      expect(enough.winner).toBe('yes');
+
+      expect(() => accelerator.tallyVotes(votes, ['yes', 'no'], 'majority', 0.5, 0)).toThrow();
+    });
+
+    it('should not pick a winner on a two-option tie', () => {
+      const votes = [
+        { voterHandle: 'a', voteValue: 'yes', voteWeight: 1 },
+        { voterHandle: 'b', voteValue: 'no', voteWeight: 1 },
+      ];
+
+      const result = accelerator.tallyVotes(votes, ['yes', 'no'], 'majority', 0.5);
+      expect(result.winner).toBeNull();
+      expect(result.quorumMet).toBe(false);
     });
   });
 
@@ -77,11 +77,17 @@ export interface SwarmAccelerator {
     bidWeight: number,
     preferLowerBids: boolean
   ): BidEvaluationOutput;
+  /**
+   * When `eligibleCount` is given, `participationRate` is
+   * `votes.length / eligibleCount` and quorum also requires it to reach
+   * `quorumValue`; otherwise the rate is 1 whenever anyone voted.
+   */
   tallyVotes(
     votes: VoteInput[],
     options: string[],
     method: string,
-    quorumValue: number
+    quorumValue: number,
+    eligibleCount?: number
   ): ConsensusOutput;
   calculatePayoff(strategies: string[], payoffMatrix: Record<string, Record<string, number>>): string;
   routeTasks(
@@ -148,7 +154,7 @@ function createNativeAccelerator(native: Record<string, unknown>): SwarmAccelera
       return result;
     },
 
-    tallyVotes(votes, options, method, quorumValue) {
+    tallyVotes(votes, options, method, quorumValue, eligibleCount) {
       const input = votes.map((v) => ({
         voter_handle: v.voterHandle,
         vote_value: v.voteValue,
@@ -158,7 +164,8 @@ function createNativeAccelerator(native: Record<string, unknown>): SwarmAccelera
         JSON.stringify(input),
         JSON.stringify(options),
         method,
-        quorumValue
+        quorumValue,
+        eligibleCount
       ) as ConsensusOutput;
       return result;
     },
@@ -255,8 +262,13 @@ class JSSwarmAccelerator implements SwarmAccelerator {
     votes: VoteInput[],
     options: string[],
     method: string,
-    _quorumValue: number
+    quorumValue: number,
+    eligibleCount?: number
   ): ConsensusOutput {
+    if (eligibleCount === 0) {
+      throw new Error('eligibleCount must be positive');
+    }
+
     const tally = new Map<string, number>();
     for (const opt of options) {
       tally.set(opt, 0);
@@ -282,6 +294,7 @@ class JSSwarmAccelerator implements SwarmAccelerator {
       }
     }
 
+    // Find winner; a tie for first place has none
     let winner: string | null = null;
     let maxVotes = 0;
     for (const [opt, count] of tally) {
@@ -290,9 +303,12 @@ class JSSwarmAccelerator implements SwarmAccelerator {
         winner = opt;
       }
     }
+    if (Array.from(tally.values()).filter((count) => count === maxVotes).length > 1) {
+      winner = null;
+    }
 
     let quorumMet = false;
-    if (totalWeight > 0) {
+    if (totalWeight > 0 && winner !== null) {
       const winnerRatio = maxVotes / totalWeight;
       switch (method) {
         case 'supermajority':
@@ -302,12 +318,16 @@ class JSSwarmAccelerator implements SwarmAccelerator {
           quorumMet = winnerRatio >= 1.0;
           break;
         default:
-          quorumMet = winnerRatio > 0.5 || options.length <= 2;
+          quorumMet = winnerRatio > 0.5;
           break;
       }
     }
 
-    const participationRate = totalWeight > 0 ? votes.length / totalWeight : 0;
+    let participationRate = votes.length > 0 ? 1 : 0;
+    if (eligibleCount !== undefined) {
+      participationRate = Math.min(votes.length / eligibleCount, 1);
+      quorumMet = quorumMet && participationRate >= quorumValue;
+    }
 
     const tallyEntries = Array.from(tally.entries()).map(([option, count]) => ({
       option,